@@ -1 +1,2 @@
+#[allow(clippy::module_inception)]
 pub mod cors;
\ No newline at end of file
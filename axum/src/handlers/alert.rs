@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Json, Query, State},
+    http::StatusCode,
+};
+use tracing::error;
+
+use crate::models::alert_stats::{AlertStatsQuery, AlertStatsReport};
+use crate::models::alert_store::{AlertPage, AlertQuery};
+use crate::models::rule::ApiResponse;
+use crate::services::alert_store::AlertStoreError;
+use crate::state::AppState;
+
+fn error_response<T>(e: AlertStoreError) -> (StatusCode, Json<ApiResponse<T>>) {
+    let status = match e {
+        AlertStoreError::Disabled(_) => StatusCode::SERVICE_UNAVAILABLE,
+        AlertStoreError::Invalid(_) => StatusCode::BAD_REQUEST,
+        AlertStoreError::Database(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("alert store operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+// 로컬에 보관한 경보/이벤트 조회 (최신순, 페이지 단위)
+pub async fn get_alerts(
+    State(state): State<AppState>,
+    Query(query): Query<AlertQuery>,
+) -> (StatusCode, Json<ApiResponse<AlertPage>>) {
+    let result = tokio::task::spawn_blocking(move || state.alerts.query(&query))
+        .await
+        .unwrap_or_else(|e| Err(AlertStoreError::Database(format!("Alert query task failed: {}", e))));
+    match result {
+        Ok(page) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(page),
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+// 경보 통계 (상위 시그니처/IP/포트, severity/분류별 개수, 시간 구간별 개수)
+pub async fn get_alert_stats(
+    State(state): State<AppState>,
+    Query(query): Query<AlertStatsQuery>,
+) -> (StatusCode, Json<ApiResponse<AlertStatsReport>>) {
+    match state.alert_stats.report(&query) {
+        Ok(report) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(report),
+            })
+        ),
+        Err(e) => {
+            error!("alert stats query failed: {}", e.message());
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse {
+                    success: false,
+                    message: Some(e.message().to_string()),
+                    data: None,
+                })
+            )
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use axum::{
+    body::Body,
+    extract::{Json, Path as PathExtractor, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::{error, info};
+
+use crate::models::capture::{CaptureInfo, CaptureList, CaptureRequest, FlowCaptureRequest};
+use crate::models::rule::ApiResponse;
+use crate::services::capture::CaptureError;
+use crate::state::AppState;
+
+fn error_status(e: &CaptureError) -> StatusCode {
+    match e {
+        CaptureError::Invalid(_) => StatusCode::BAD_REQUEST,
+        CaptureError::NotFound(_) => StatusCode::NOT_FOUND,
+        CaptureError::Busy(_) => StatusCode::TOO_MANY_REQUESTS,
+        CaptureError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn error_response<T>(e: CaptureError) -> (StatusCode, Json<ApiResponse<T>>) {
+    error!("Capture operation failed: {}", e.message());
+    (
+        error_status(&e),
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+fn capture_response(
+    status: StatusCode,
+    result: Result<CaptureInfo, CaptureError>,
+) -> (StatusCode, Json<ApiResponse<CaptureInfo>>) {
+    match result {
+        Ok(capture) => (
+            status,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(capture),
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+// 인터페이스 실시간 캡처 시작 (완료 여부는 GET /captures/{id}로 확인)
+pub async fn start_capture(
+    State(state): State<AppState>,
+    Json(payload): Json<CaptureRequest>,
+) -> (StatusCode, Json<ApiResponse<CaptureInfo>>) {
+    info!("Starting capture on {} with filter {:?}", payload.interface, payload.filter);
+    capture_response(StatusCode::ACCEPTED, state.captures.start_live(&state.config, payload).await)
+}
+
+// pcap-log 링 버퍼에서 플로우 패킷 추출
+pub async fn start_flow_capture(
+    State(state): State<AppState>,
+    Json(payload): Json<FlowCaptureRequest>,
+) -> (StatusCode, Json<ApiResponse<CaptureInfo>>) {
+    info!("Extracting flow {} <-> {} from pcap-log", payload.src_ip, payload.dest_ip);
+    capture_response(StatusCode::ACCEPTED, state.captures.start_flow(&state.config, payload).await)
+}
+
+// 캡처 목록 (최신순)
+pub async fn get_captures(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<CaptureList>>) {
+    let captures = state.captures.list().await;
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(CaptureList {
+                count: captures.len(),
+                captures,
+            }),
+        })
+    )
+}
+
+pub async fn get_capture(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+) -> (StatusCode, Json<ApiResponse<CaptureInfo>>) {
+    capture_response(StatusCode::OK, state.captures.get(&id).await)
+}
+
+// pcap 파일 다운로드
+pub async fn download_capture(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+) -> Response {
+    let path = match state.captures.pcap_path(&id).await {
+        Ok(path) => path,
+        Err(e) => return error_response::<()>(e).into_response(),
+    };
+
+    match tokio::fs::read(&path).await {
+        Ok(data) => (
+            [
+                (header::CONTENT_TYPE, "application/vnd.tcpdump.pcap".to_string()),
+                (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}.pcap\"", id)),
+            ],
+            Body::from(data),
+        )
+            .into_response(),
+        Err(e) => error_response::<()>(CaptureError::Io(format!("Failed to read {}: {}", path.display(), e)))
+            .into_response(),
+    }
+}
+
+pub async fn delete_capture(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    match state.captures.delete(&id).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: Some(format!("Capture {} deleted", id)),
+                data: None,
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
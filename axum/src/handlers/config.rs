@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+
+use crate::models::config::{ConfigPatchRequest, SuricataConfigView};
+use crate::models::rule::ApiResponse;
+use crate::services::config::ConfigError;
+use crate::state::AppState;
+
+fn error_response(e: ConfigError) -> (StatusCode, Json<ApiResponse<SuricataConfigView>>) {
+    let status = match e {
+        ConfigError::Invalid(_) => StatusCode::BAD_REQUEST,
+        ConfigError::NotFound(_) => StatusCode::NOT_FOUND,
+        ConfigError::Conflict(_) => StatusCode::CONFLICT,
+        ConfigError::TestFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        ConfigError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("suricata.yaml operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+fn view_response(
+    state: &AppState,
+    message: Option<String>,
+) -> (StatusCode, Json<ApiResponse<SuricataConfigView>>) {
+    match state.config.view() {
+        Ok(view) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message,
+                data: Some(view),
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+// 주요 설정 섹션 조회
+pub async fn get_suricata_config(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<SuricataConfigView>>) {
+    view_response(&state, None)
+}
+
+// 설정 변경 (suricata -T 검증 통과 시에만 반영)
+pub async fn patch_suricata_config(
+    State(state): State<AppState>,
+    Json(payload): Json<ConfigPatchRequest>,
+) -> (StatusCode, Json<ApiResponse<SuricataConfigView>>) {
+    if payload.changes.is_empty() {
+        return error_response(ConfigError::Invalid("No changes given".to_string()));
+    }
+
+    if let Err(e) = state.config.patch(&payload.changes).await {
+        return error_response(e);
+    }
+    info!("Applied {} suricata.yaml changes", payload.changes.len());
+
+    let message = if payload.reload_rules {
+        match state.controller.suricatasc("reload-rules").await {
+            Ok(_) => {
+                state.metrics.rule_reload(true);
+                "Config applied and rules reloaded".to_string()
+            }
+            Err(e) => {
+                state.metrics.rule_reload(false);
+                format!("Config applied but rule reload failed: {}", e)
+            }
+        }
+    } else {
+        "Config applied, restart Suricata to take effect".to_string()
+    };
+
+    view_response(&state, Some(message))
+}
+
+// 직전 백업으로 복원
+pub async fn rollback_suricata_config(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<SuricataConfigView>>) {
+    if let Err(e) = state.config.rollback().await {
+        return error_response(e);
+    }
+    view_response(&state, Some("Config rolled back to previous version".to_string()))
+}
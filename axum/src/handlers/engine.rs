@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+use tracing::error;
+
+use crate::models::engine::{EngineModeRequest, EngineModeView, EngineReport};
+use crate::models::rule::ApiResponse;
+use crate::services::config::ConfigError;
+use crate::services::engine::EngineError;
+use crate::state::AppState;
+
+fn error_response<T>(e: EngineError) -> (StatusCode, Json<ApiResponse<T>>) {
+    let status = match &e {
+        EngineError::Busy => StatusCode::CONFLICT,
+        EngineError::Config(ConfigError::Invalid(_)) => StatusCode::BAD_REQUEST,
+        EngineError::Config(ConfigError::NotFound(_)) => StatusCode::NOT_FOUND,
+        EngineError::Config(ConfigError::Conflict(_)) => StatusCode::CONFLICT,
+        EngineError::Config(ConfigError::TestFailed(_)) => StatusCode::UNPROCESSABLE_ENTITY,
+        EngineError::Config(ConfigError::Io(_)) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("Engine operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+// 절차 결과 응답 (실패 시 단계별 결과와 함께 502)
+fn report_response(result: Result<EngineReport, EngineError>) -> (StatusCode, Json<ApiResponse<EngineReport>>) {
+    match result {
+        Ok(report) => {
+            let (status, message) = match (report.success, report.rolled_back) {
+                (true, _) => (StatusCode::OK, None),
+                (false, true) => (StatusCode::BAD_GATEWAY, Some("Engine action failed, previous configuration restored")),
+                (false, false) => (StatusCode::BAD_GATEWAY, Some("Engine action failed")),
+            };
+            (
+                status,
+                Json(ApiResponse {
+                    success: report.success,
+                    message: message.map(str::to_string),
+                    data: Some(report),
+                })
+            )
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// 현재 IDS/IPS 모드 조회
+pub async fn get_engine_mode(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<EngineModeView>>) {
+    match state.engine.mode() {
+        Ok(mode) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(mode),
+            })
+        ),
+        Err(e) => error_response(EngineError::Config(e)),
+    }
+}
+
+// 점검 후 재시작하고 캡처가 재개되는지 확인
+pub async fn restart_engine(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<EngineReport>>) {
+    report_response(state.engine.restart().await)
+}
+
+// IDS/IPS 모드 전환 (실패 시 직전 설정으로 자동 복구)
+pub async fn put_engine_mode(
+    State(state): State<AppState>,
+    Json(payload): Json<EngineModeRequest>,
+) -> (StatusCode, Json<ApiResponse<EngineReport>>) {
+    report_response(state.engine.switch_mode(&payload).await)
+}
@@ -1,20 +1,22 @@
 use axum::{
+    extract::State,
     http::StatusCode,
     Json,
 };
-use reqwest;
-use dotenvy::dotenv;
-use std::env;
-use tracing::{error, warn, info};
+use tracing::{error, info, warn};
 use serde_json::Value;
 
-use crate::models::eve_json_log::EveJsonLog;
+use crate::models::eve_json_log::EveIngestResult;
+use crate::models::rule::ApiResponse;
+use crate::models::sink::SinkStatus;
+use crate::models::spool::ForwardStatus;
+use crate::models::tailer::TailStatus;
+use crate::state::AppState;
 
 pub async fn send_eve_json_log(
+    State(state): State<AppState>,
     body: axum::body::Body
-) -> Result<Json<Value>, (StatusCode, String)> {
-    dotenv().ok();
-    
+) -> Result<(StatusCode, Json<EveIngestResult>), (StatusCode, String)> {
     // 요청 본문을 문자열로 변환
     let bytes = match axum::body::to_bytes(body, usize::MAX).await {
         Ok(bytes) => bytes,
@@ -59,76 +61,67 @@ pub async fn send_eve_json_log(
         }
     };
     
-    let external_api_url = match env::var("CENTRAL_API_SERVER_URL") {
-        Ok(url) => url,
-        Err(e) => {
-            error!("환경 변수 읽기 실패: {}", e);
-            return Err((
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "서버 구성 오류".to_string(),
-            ));
-        }
-    };
-
-    let client = reqwest::Client::new();
-    let mut results = Vec::new();
-    
-    // 각 이벤트 항목을 개별적으로 처리
-    for item in items {
-        // 항목을 EveJsonLog로 변환 시도
-        let log_item = match serde_json::from_value::<EveJsonLog>(item.clone()) {
-            Ok(log) => log,
-            Err(e) => {
-                warn!("항목을 EveJsonLog로 변환 실패: {}", e);
-                // 에러 로깅 후 다음 항목 처리
-                continue;
-            }
-        };
+    // EVE stats 이벤트는 처리된 뒤 카운터 시계열에도 기록 (줄 단위 수신과 같은 순서)
+    let stats: Vec<Value> = items.iter()
+        .filter(|item| item.get("event_type").and_then(Value::as_str) == Some("stats"))
+        .cloned()
+        .collect();
 
-        info!("EveJsonLog 항목: {:?}", log_item);
-        
-        // 외부 API 호출
-        let response: reqwest::Response = match client
-            .post(&external_api_url)
-            .json(&log_item)
-            .send()
-            .await {
-                Ok(res) => res,
-                Err(e) => {
-                    error!("외부 API 호출 실패: {}", e);
-                    continue;  // 에러 로깅 후 다음 항목 처리
-                }
+    // 디스크 스풀에 기록한 뒤 응답 (전송은 백그라운드 배치 전송기가 담당)
+    // fluent-bit http 출력은 200~205 외의 응답을 모두 재전송하므로
+    // 재전송해도 소용없는 형식 오류만 있으면 2xx, 스풀 기록 실패는 503으로 응답
+    match state.forwarder.enqueue(items).await {
+        Ok(result) => {
+            let status = if result.failed > 0 {
+                warn!("{}개 이벤트를 스풀에 기록하지 못함", result.failed);
+                StatusCode::SERVICE_UNAVAILABLE
+            } else if result.accepted > 0 {
+                StatusCode::ACCEPTED
+            } else {
+                warn!("유효한 이벤트가 없습니다 (거부 {}개)", result.rejected);
+                StatusCode::OK
             };
-
-        if !response.status().is_success() {
-            let status = response.status();
-            warn!("외부 API 오류 응답: {}", status);
-            // 에러 로깅 후 다음 항목 처리
-            continue;
-        }
-
-        match response.json::<Value>().await {
-            Ok(result) => results.push(result),
-            Err(e) => {
-                error!("응답 JSON 파싱 실패: {}", e);
-                continue;  // 에러 로깅 후 다음 항목 처리
+            if result.failed == 0 {
+                state.counters.record_eve_stats(&stats).await;
             }
+            Ok((status, Json(result)))
         }
+        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.message().to_string())),
     }
-    
-    // 처리 결과 반환
-    if results.is_empty() {
-        Err((
-            StatusCode::BAD_GATEWAY,
-            "모든 이벤트 처리에 실패했습니다".to_string(),
-        ))
-    } else {
-        let response = if results.len() == 1 {
-            results[0].clone()  // 단일 결과인 경우
-        } else {
-            Value::Array(results)  // 여러 결과인 경우 배열로 반환
-        };
-        
-        Ok(Json(response))
-    }
-}
\ No newline at end of file
+}
+
+// 스풀 깊이와 재시도 상태 조회
+pub async fn get_spool_status(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<ForwardStatus>>) {
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(state.forwarder.status()),
+        })
+    )
+}
+
+// 추가 전달 대상(syslog 싱크)별 큐와 재시도 상태 조회
+pub async fn get_sink_status(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<Vec<SinkStatus>>>) {
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(state.sinks.status()),
+        })
+    )
+}
+
+// 내장 eve.json tailer 읽기 위치와 지연 조회
+pub async fn get_tail_status(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<TailStatus>>) {
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(state.tailer.status()),
+        })
+    )
+}
@@ -0,0 +1,95 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{
+        ws::{rejection::WebSocketUpgradeRejection, Message, WebSocket, WebSocketUpgrade},
+        Json, Query, State,
+    },
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use futures_util::Stream;
+use tracing::{error, info};
+
+use crate::models::event_stream::{EventStreamQuery, StreamDropped};
+use crate::models::rule::ApiResponse;
+use crate::services::event_stream::{EventStreamError, StreamItem, Subscription};
+use crate::state::AppState;
+
+fn error_response(e: EventStreamError) -> Response {
+    let status = match e {
+        EventStreamError::Invalid(_) => StatusCode::BAD_REQUEST,
+        EventStreamError::Busy(_) => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    error!("live event stream failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse::<()> {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    ).into_response()
+}
+
+fn dropped_notice(dropped: u64) -> String {
+    serde_json::to_string(&StreamDropped { dropped }).unwrap_or_default()
+}
+
+// 실시간 이벤트 구독 (WebSocket 업그레이드 요청이면 WebSocket, 아니면 SSE)
+pub async fn stream_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventStreamQuery>,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Response {
+    let transport = if upgrade.is_ok() { "websocket" } else { "sse" };
+    let subscription = match state.event_stream.subscribe(&query, transport) {
+        Ok(subscription) => subscription,
+        Err(e) => return error_response(e),
+    };
+    info!("Live event stream subscriber connected ({})", transport);
+
+    match upgrade {
+        Ok(upgrade) => upgrade.on_upgrade(move |socket| serve_websocket(socket, subscription)),
+        Err(_) => Sse::new(sse_events(subscription))
+            .keep_alive(KeepAlive::default())
+            .into_response(),
+    }
+}
+
+// 이벤트는 기본 message, 버린 이벤트 알림은 dropped 이벤트로 보냄
+fn sse_events(subscription: Subscription) -> impl Stream<Item = Result<Event, Infallible>> {
+    futures_util::stream::unfold(subscription, |mut subscription| async move {
+        let event = match subscription.next().await? {
+            StreamItem::Event(line) => Event::default().data(line.as_ref()),
+            StreamItem::Dropped(dropped) => Event::default().event("dropped").data(dropped_notice(dropped)),
+        };
+        Some((Ok(event), subscription))
+    })
+}
+
+// 이벤트마다 텍스트 메시지 하나, 버린 이벤트 알림은 {"dropped": n}
+async fn serve_websocket(mut socket: WebSocket, mut subscription: Subscription) {
+    loop {
+        tokio::select! {
+            item = subscription.next() => {
+                let text = match item {
+                    Some(StreamItem::Event(line)) => line.to_string(),
+                    Some(StreamItem::Dropped(dropped)) => dropped_notice(dropped),
+                    None => break,
+                };
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    info!("Live event stream subscriber disconnected (websocket)");
+}
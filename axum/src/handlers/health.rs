@@ -0,0 +1,56 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json,
+};
+
+use crate::models::health::{EngineState, HealthReport, Liveness, Readiness};
+use crate::state::AppState;
+
+// 생존 및 준비 상태 상세
+pub async fn get_health(State(state): State<AppState>) -> (StatusCode, Json<HealthReport>) {
+    let readiness = readiness(&state).await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status,
+        Json(HealthReport {
+            liveness: Liveness {
+                alive: true,
+                uptime: state.supervisor.uptime(),
+            },
+            readiness,
+        })
+    )
+}
+
+// 에이전트 생존 여부 (프로세스가 응답하면 항상 200)
+pub async fn get_liveness(State(state): State<AppState>) -> Json<Liveness> {
+    Json(Liveness {
+        alive: true,
+        uptime: state.supervisor.uptime(),
+    })
+}
+
+// Suricata 정상 동작 여부 (비정상이면 503)
+pub async fn get_readiness(State(state): State<AppState>) -> (StatusCode, Json<Readiness>) {
+    let readiness = readiness(&state).await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(readiness))
+}
+
+async fn readiness(state: &AppState) -> Readiness {
+    let engine = state.supervisor.health().await;
+    Readiness {
+        ready: engine.state == EngineState::Healthy,
+        engine,
+    }
+}
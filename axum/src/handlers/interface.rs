@@ -0,0 +1,95 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+
+use crate::models::interface::{InterfaceOverview, UpdateInterfacesRequest};
+use crate::models::rule::ApiResponse;
+use crate::services::config::ConfigError;
+use crate::services::engine::EngineError;
+use crate::services::interfaces::{apply_af_packet_interfaces, overview, set_af_packet_interfaces};
+use crate::state::AppState;
+
+fn error_response(status: StatusCode, message: String) -> (StatusCode, Json<ApiResponse<InterfaceOverview>>) {
+    error!("Capture interface operation failed: {}", message);
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(message),
+            data: None,
+        })
+    )
+}
+
+fn config_error_response(e: ConfigError) -> (StatusCode, Json<ApiResponse<InterfaceOverview>>) {
+    let status = match e {
+        ConfigError::Invalid(_) => StatusCode::BAD_REQUEST,
+        ConfigError::NotFound(_) => StatusCode::NOT_FOUND,
+        ConfigError::Conflict(_) => StatusCode::CONFLICT,
+        ConfigError::TestFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        ConfigError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error_response(status, e.message().to_string())
+}
+
+fn overview_response(
+    state: &AppState,
+    message: Option<String>,
+) -> (StatusCode, Json<ApiResponse<InterfaceOverview>>) {
+    match overview(&state.config) {
+        Ok(overview) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message,
+                data: Some(overview),
+            })
+        ),
+        Err(e) => config_error_response(e),
+    }
+}
+
+// 호스트 인터페이스와 캡처 설정 조회
+pub async fn get_capture_interfaces(
+    State(state): State<AppState>,
+) -> (StatusCode, Json<ApiResponse<InterfaceOverview>>) {
+    overview_response(&state, None)
+}
+
+// af-packet 캡처 인터페이스 변경 후 엔진 재시작 (실패 시 이전 설정으로 복구)
+pub async fn put_capture_interfaces(
+    State(state): State<AppState>,
+    Json(payload): Json<UpdateInterfacesRequest>,
+) -> (StatusCode, Json<ApiResponse<InterfaceOverview>>) {
+    if !payload.restart {
+        if let Err(e) = set_af_packet_interfaces(&state.config, &payload.interfaces).await {
+            return config_error_response(e);
+        }
+        info!("Updated af-packet capture interfaces");
+        return overview_response(&state, Some("Interfaces saved, restart Suricata to take effect".to_string()));
+    }
+
+    // 편집과 재시작, 복구를 설정 잠금 하나로 묶어 실행
+    let edit = |text: &str| apply_af_packet_interfaces(text, &payload.interfaces);
+    match state.engine.apply_and_restart("apply_interfaces", edit).await {
+        Ok(report) if report.success => {}
+        Ok(report) => {
+            let failed = report.steps.iter().rev().find(|step| !step.ok);
+            let detail = failed.and_then(|step| step.message.clone()).unwrap_or_default();
+            return error_response(
+                if report.rolled_back { StatusCode::BAD_GATEWAY } else { StatusCode::INTERNAL_SERVER_ERROR },
+                if report.rolled_back {
+                    format!("Restart failed, previous interfaces restored: {}", detail)
+                } else {
+                    format!("Restart failed and previous interfaces could not be restored: {}", detail)
+                },
+            );
+        }
+        Err(EngineError::Config(e)) => return config_error_response(e),
+        Err(e) => return error_response(StatusCode::CONFLICT, e.message().to_string()),
+    }
+
+    overview_response(&state, Some("Interfaces applied and Suricata restarted".to_string()))
+}
@@ -0,0 +1,29 @@
+use axum::{
+    extract::State,
+    http::{header, StatusCode},
+    response::IntoResponse,
+};
+use tracing::error;
+
+use crate::state::AppState;
+
+// Prometheus 텍스트 포맷 메트릭
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let counters = state.counters.latest().await;
+
+    match state.metrics.render(&counters) {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+            body,
+        ),
+        Err(e) => {
+            error!("{}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(header::CONTENT_TYPE, "text/plain")],
+                e,
+            )
+        }
+    }
+}
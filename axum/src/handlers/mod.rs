@@ -1,3 +1,14 @@
+pub mod alert;
+pub mod capture;
+pub mod config;
+pub mod engine;
 pub mod eve_json_log;
+pub mod event_stream;
+pub mod health;
+pub mod interface;
+pub mod metrics;
+pub mod pcap_job;
+pub mod policy;
 pub mod rule;
-pub mod suricata;
\ No newline at end of file
+pub mod suricata;
+pub mod vars;
\ No newline at end of file
@@ -0,0 +1,126 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Json, Path as PathExtractor, Query, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+
+use crate::models::pcap_job::{
+    PcapJobEvents, PcapJobEventsQuery, PcapJobInfo, PcapJobList, PcapJobRequest,
+};
+use crate::models::rule::ApiResponse;
+use crate::services::pcap_jobs::{file_only_outputs, PcapJobError};
+use crate::state::AppState;
+
+fn error_response<T>(e: PcapJobError) -> (StatusCode, Json<ApiResponse<T>>) {
+    let status = match e {
+        PcapJobError::Invalid(_) => StatusCode::BAD_REQUEST,
+        PcapJobError::NotFound(_) => StatusCode::NOT_FOUND,
+        PcapJobError::Conflict(_) => StatusCode::CONFLICT,
+        PcapJobError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("pcap job operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+fn ok_response<T>(status: StatusCode, data: T) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        status,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(data),
+        })
+    )
+}
+
+// pcap 업로드 후 분석 작업 생성 (본문은 pcap 원본)
+pub async fn create_pcap_job(
+    State(state): State<AppState>,
+    Query(request): Query<PcapJobRequest>,
+    body: Body,
+) -> (StatusCode, Json<ApiResponse<PcapJobInfo>>) {
+    let limit = state.pcap_jobs.max_upload_bytes();
+    let data = match to_bytes(body, limit).await {
+        Ok(data) => data,
+        Err(e) => {
+            error!("Failed to read pcap upload: {}", e);
+            return (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                Json(ApiResponse {
+                    success: false,
+                    message: Some(format!("pcap must be at most {} bytes", limit)),
+                    data: None,
+                })
+            );
+        }
+    };
+
+    // 운영 설정의 출력 대상 대신 작업 디렉터리의 eve.json에만 기록
+    let overrides = match state.config.read_value()
+        .map_err(|e| PcapJobError::Io(e.message().to_string()))
+        .and_then(|config| file_only_outputs(&config)) {
+        Ok(overrides) => overrides,
+        Err(e) => return error_response(e),
+    };
+    let engine_config = state.config.engine_path().to_string();
+    match state.pcap_jobs.submit(engine_config, overrides, state.forwarder.clone(), request, &data).await {
+        Ok(job) => {
+            info!("Queued pcap job {} ({} bytes)", job.id, job.size);
+            ok_response(StatusCode::ACCEPTED, job)
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// 작업 목록 (최신순)
+pub async fn get_pcap_jobs(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<PcapJobList>>) {
+    let jobs = state.pcap_jobs.list().await;
+    ok_response(StatusCode::OK, PcapJobList { count: jobs.len(), jobs })
+}
+
+pub async fn get_pcap_job(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+) -> (StatusCode, Json<ApiResponse<PcapJobInfo>>) {
+    match state.pcap_jobs.get(&id).await {
+        Ok(job) => ok_response(StatusCode::OK, job),
+        Err(e) => error_response(e),
+    }
+}
+
+// 분석 결과 EVE 이벤트 조회
+pub async fn get_pcap_job_events(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+    Query(query): Query<PcapJobEventsQuery>,
+) -> (StatusCode, Json<ApiResponse<PcapJobEvents>>) {
+    match state.pcap_jobs.events(&id, &query).await {
+        Ok(events) => ok_response(StatusCode::OK, events),
+        Err(e) => error_response(e),
+    }
+}
+
+pub async fn delete_pcap_job(
+    State(state): State<AppState>,
+    PathExtractor(id): PathExtractor<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    match state.pcap_jobs.delete(&id).await {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: Some(format!("Job {} deleted", id)),
+                data: None,
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
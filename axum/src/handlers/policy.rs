@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+
+use crate::models::policy::{PolicyConfig, PolicyStatus};
+use crate::models::rule::ApiResponse;
+use crate::services::policy::PolicyError;
+use crate::state::AppState;
+
+fn error_response(e: PolicyError) -> (StatusCode, Json<ApiResponse<PolicyStatus>>) {
+    let status = match e {
+        PolicyError::Invalid(_) => StatusCode::BAD_REQUEST,
+        PolicyError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("EVE policy operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+fn status_response(status: PolicyStatus, message: Option<String>) -> (StatusCode, Json<ApiResponse<PolicyStatus>>) {
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message,
+            data: Some(status),
+        })
+    )
+}
+
+// 현재 정책과 규칙별 유지/폐기 건수 조회
+pub async fn get_policy(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<PolicyStatus>>) {
+    status_response(state.policy.status(), None)
+}
+
+// 정책 교체 (파일에 저장 후 즉시 적용)
+pub async fn update_policy(
+    State(state): State<AppState>,
+    Json(payload): Json<PolicyConfig>,
+) -> (StatusCode, Json<ApiResponse<PolicyStatus>>) {
+    let rules = payload.rules.len();
+    match state.policy.replace(payload) {
+        Ok(status) => {
+            info!("EVE policy replaced with {} rules", rules);
+            status_response(status, Some("Policy applied".to_string()))
+        }
+        Err(e) => error_response(e),
+    }
+}
+
+// 정책 파일 다시 읽기
+pub async fn reload_policy(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<PolicyStatus>>) {
+    match state.policy.reload() {
+        Ok(()) => status_response(state.policy.status(), Some("Policy reloaded".to_string())),
+        Err(e) => error_response(e),
+    }
+}
@@ -2,17 +2,23 @@ use std::path::Path;
 use std::fs::{self, OpenOptions, File};
 use std::io::{Write, Read};
 use axum::{
-    extract::{Json, Path as PathExtractor},
+    extract::{Json, Path as PathExtractor, State},
     http::StatusCode,
     response::IntoResponse,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::models::rule::{ApiResponse, RuleRequest, Rule, RulesList};
-use crate::utils::suricata::{extract_option, generate_rule_id, get_env,  validate_rule_syntax};
+use crate::state::AppState;
+use crate::utils::suricata::{
+    extract_option, generate_rule_id, get_env, validate_rule_syntax, validate_rule_variables,
+};
 
 // 룰 추가 핸들러
-pub async fn create_rule(Json(payload): Json<RuleRequest>) -> impl IntoResponse {
+pub async fn create_rule(
+    State(state): State<AppState>,
+    Json(payload): Json<RuleRequest>,
+) -> impl IntoResponse {
     let (rules_dir, filename) = get_env();
     let file_path = Path::new(&rules_dir).join(&filename);
 
@@ -28,21 +34,41 @@ pub async fn create_rule(Json(payload): Json<RuleRequest>) -> impl IntoResponse
         );
     }
 
-    // 디렉토리 존재 확인
-    if let Some(parent) = file_path.parent() {
-        if !parent.exists() {
-            if let Err(e) = fs::create_dir_all(parent) {
-                error!("Failed to create directory: {}", e);
+    // 규칙이 참조하는 $변수가 suricata.yaml에 정의되어 있는지 검증
+    match state.config.vars() {
+        Ok(vars) => {
+            let address_vars: Vec<String> = vars.address_groups.keys().cloned().collect();
+            let port_vars: Vec<String> = vars.port_groups.keys().cloned().collect();
+            if let Err(validation_error) = validate_rule_variables(&payload.rule_content, &address_vars, &port_vars) {
                 return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
+                    StatusCode::BAD_REQUEST,
                     Json(ApiResponse::<()> {
                         success: false,
-                        message: Some(format!("Failed to create directory: {}", e)),
+                        message: Some(validation_error),
                         data: None,
                     })
                 );
             }
         }
+        Err(e) => {
+            // 설정 파일을 읽을 수 없는 환경에서는 변수 검증 생략
+            warn!("Skipping rule variable validation: {}", e.message());
+        }
+    }
+
+    // 디렉토리 존재 확인
+    if let Some(parent) = file_path.parent()
+        && !parent.exists()
+        && let Err(e) = fs::create_dir_all(parent) {
+        error!("Failed to create directory: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()> {
+                success: false,
+                message: Some(format!("Failed to create directory: {}", e)),
+                data: None,
+            })
+        );
     }
 
     // 파일이 너무 크면 백업
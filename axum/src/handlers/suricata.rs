@@ -1,132 +1,99 @@
-use tokio::process::Command;
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde_json::{Map, Value};
 use tracing::{error, info};
 
+use crate::models::counters::{CounterQuery, CounterSeriesList};
+use crate::models::interface::InterfaceStatsQuery;
+use crate::models::rule::ApiResponse;
+use crate::state::AppState;
+use crate::utils::interfaces::is_valid_interface_name;
+
 // 수리카타 상태 확인
-pub async fn get_suricata_status() -> Result<String, String> {
-    // 컨테이너 환경에서는 다음과 같이 구현할 수 있음
-    let output = match Command::new("docker")
-        .args(&["exec", "suricata", "suricatasc", "-c", "uptime"])
-        .output()
-        .await {
-            Ok(output) => output,
-            Err(e) => {
-                error!("Failed to execute Suricata status command: {}", e);
-                return Err(format!("Failed to execute command: {}", e));
-            }
-        };
-    
-    if !output.status.success() {
-        let stderr = match std::str::from_utf8(&output.stderr) {
-            Ok(s) => s.to_string(),
-            Err(_) => "Failed to decode stderr output".to_string(),
-        };
-        error!("Suricata status command failed: {}", stderr);
-        return Err(format!("Command failed with status: {} ({})", output.status, stderr));
-    }
-    
-    match std::str::from_utf8(&output.stdout) {
-        Ok(uptime) => Ok(uptime.to_string()),
-        Err(e) => {
-            error!("Failed to decode Suricata output: {}", e);
-            Err("Failed to decode command output".to_string())
-        }
-    }
+pub async fn get_suricata_status(State(state): State<AppState>) -> Result<String, String> {
+    state.controller.suricatasc("uptime").await
 }
 
 // 수리카타 규칙 리로드
-pub async fn reload_suricata_rules() -> Result<(), String> {
-    // 컨테이너 환경에서는 다음과 같이 구현할 수 있음
-    let output = match Command::new("docker")
-        .args(&["exec", "suricata", "suricatasc", "-c", "reload-rules"])
-        .output()
-        .await {
-            Ok(output) => output,
-            Err(e) => {
-                error!("Failed to execute reload command: {}", e);
-                return Err(format!("Failed to execute reload command: {}", e));
-            }
-        };
-    
-    if !output.status.success() {
-        let error = match std::str::from_utf8(&output.stderr) {
-            Ok(s) => s.to_string(),
-            Err(_) => "Failed to decode stderr output".to_string(),
-        };
-        error!("Failed to reload Suricata rules: {}", error);
-        return Err(format!("Failed to reload rules: {}", error));
+pub async fn reload_suricata_rules(State(state): State<AppState>) -> Result<(), String> {
+    if let Err(e) = state.controller.suricatasc("reload-rules").await {
+        error!("Failed to reload Suricata rules: {}", e);
+        state.metrics.rule_reload(false);
+        return Err(format!("Failed to reload rules: {}", e));
     }
-    
+
+    state.metrics.rule_reload(true);
     info!("Successfully reloaded Suricata rules");
     Ok(())
 }
 
 // 수리카타 규칙 통계 확인
-pub async fn get_suricata_rule_statistics() -> Result<String, String> {
-    // 컨테이너 환경에서는 다음과 같이 구현할 수 있음
-    let output = match Command::new("docker")
-        .args(&["exec", "suricata", "suricatasc", "-c", "ruleset-stats"])
-        .output()
-        .await {
-            Ok(output) => output,
-            Err(e) => {
-                error!("Failed to execute statistics command: {}", e);
-                return Err(format!("Failed to execute command: {}", e));
-            }
-        };
-    
-    if !output.status.success() {
-        let stderr = match std::str::from_utf8(&output.stderr) {
-            Ok(s) => s.to_string(),
-            Err(_) => "Failed to decode stderr output".to_string(),
-        };
-        error!("Rule statistics command failed: {}", stderr);
-        return Err(format!("Command failed with status: {} ({})", output.status, stderr));
-    }
-    
-    match std::str::from_utf8(&output.stdout) {
-        Ok(stats) => Ok(stats.to_string()),
-        Err(e) => {
-            error!("Failed to decode statistics output: {}", e);
-            Err("Failed to decode command output".to_string())
+pub async fn get_suricata_rule_statistics(State(state): State<AppState>) -> Result<String, String> {
+    state.controller.suricatasc("ruleset-stats").await
+}
+
+// 인터페이스 통계 확인 (지정하지 않으면 엔진이 캡처 중인 모든 인터페이스)
+pub async fn get_interface_statistics(
+    State(state): State<AppState>,
+    Query(query): Query<InterfaceStatsQuery>,
+) -> Result<Json<Value>, String> {
+    let interfaces = match query.interface {
+        Some(interface) => vec![interface],
+        None => {
+            let output = state.controller.suricatasc("iface-list").await?;
+            let response: Value = serde_json::from_str(&output)
+                .map_err(|e| format!("Invalid iface-list output: {}", e))?;
+            response.pointer("/message/ifaces")
+                .and_then(Value::as_array)
+                .map(|ifaces| ifaces.iter().filter_map(Value::as_str).map(str::to_string).collect())
+                .unwrap_or_default()
+        }
+    };
+
+    let mut stats = Map::new();
+    for interface in interfaces {
+        // 특수 문자 검증 (명령어 주입 방지)
+        if !is_valid_interface_name(&interface) {
+            return Err(format!("Invalid interface name: {}", interface));
         }
+
+        let output = state.controller.suricatasc(&format!("iface-stat {}", interface)).await?;
+        let value = serde_json::from_str::<Value>(&output)
+            .map(|response| response.get("message").cloned().unwrap_or(response))
+            .unwrap_or(Value::String(output));
+        stats.insert(interface, value);
     }
+
+    Ok(Json(Value::Object(stats)))
 }
 
-// 인터페이스 통계 확인
-pub async fn get_interface_statistics() -> Result<String, String> {
-    dotenvy::dotenv().ok();
-    let interface = std::env::var("NETWORK_INTERFACE").unwrap_or_else(|_| "eth0".to_string());
-    
-    // 특수 문자 검증 (명령어 주입 방지)
-    if interface.contains(|c: char| !c.is_alphanumeric() && c != '_' && c != '-') {
-        return Err("Invalid interface name".to_string());
+// 카운터 시계열 조회 (증가율 및 다운샘플링 포함)
+pub async fn get_suricata_counters(
+    State(state): State<AppState>,
+    Query(query): Query<CounterQuery>,
+) -> (StatusCode, Json<ApiResponse<CounterSeriesList>>) {
+    if query.step == Some(0) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse {
+                success: false,
+                message: Some("step must be greater than 0".to_string()),
+                data: None,
+            })
+        );
     }
-    
-    let output = match Command::new("docker")
-        .args(&["exec", "suricata", "suricatasc", "-c", &format!("iface-stat {}", interface)])
-        .output()
-        .await {
-            Ok(output) => output,
-            Err(e) => {
-                error!("Failed to execute interface stats command: {}", e);
-                return Err(format!("Failed to execute command: {}", e));
-            }
-        };
-    
-    if !output.status.success() {
-        let stderr = match std::str::from_utf8(&output.stderr) {
-            Ok(s) => s.to_string(),
-            Err(_) => "Failed to decode stderr output".to_string(),
-        };
-        error!("Interface stats command failed: {}", stderr);
-        return Err(format!("Command failed with status: {} ({})", output.status, stderr));
-    }
-    
-    match std::str::from_utf8(&output.stdout) {
-        Ok(stats) => Ok(stats.to_string()),
-        Err(e) => {
-            error!("Failed to decode interface stats output: {}", e);
-            Err("Failed to decode command output".to_string())
-        }
-    }
-}
\ No newline at end of file
+
+    let series = state.counters.query(&query).await;
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: None,
+            data: Some(series),
+        })
+    )
+}
@@ -0,0 +1,120 @@
+use axum::{
+    extract::{Json, Path as PathExtractor, State},
+    http::StatusCode,
+};
+use tracing::{error, info};
+
+use crate::models::config::{VarEntry, VarGroup, VarRequest, VarsSection};
+use crate::models::rule::ApiResponse;
+use crate::services::config::{group_vars, ConfigError};
+use crate::state::AppState;
+
+fn error_response<T>(e: ConfigError) -> (StatusCode, Json<ApiResponse<T>>) {
+    let status = match e {
+        ConfigError::Invalid(_) => StatusCode::BAD_REQUEST,
+        ConfigError::NotFound(_) => StatusCode::NOT_FOUND,
+        ConfigError::Conflict(_) => StatusCode::CONFLICT,
+        ConfigError::TestFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        ConfigError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    error!("Variable operation failed: {}", e.message());
+    (
+        status,
+        Json(ApiResponse {
+            success: false,
+            message: Some(e.message().to_string()),
+            data: None,
+        })
+    )
+}
+
+// 주소/포트 그룹 변수 전체 조회
+pub async fn get_vars(State(state): State<AppState>) -> (StatusCode, Json<ApiResponse<VarsSection>>) {
+    match state.config.vars() {
+        Ok(vars) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(vars),
+            })
+        ),
+        Err(e) => error_response(e),
+    }
+}
+
+// 변수 하나 조회
+pub async fn get_var(
+    State(state): State<AppState>,
+    PathExtractor((group, name)): PathExtractor<(VarGroup, String)>,
+) -> (StatusCode, Json<ApiResponse<VarEntry>>) {
+    let vars = match state.config.vars() {
+        Ok(vars) => vars,
+        Err(e) => return error_response(e),
+    };
+
+    match group_vars(&vars, group).get(&name) {
+        Some(value) => (
+            StatusCode::OK,
+            Json(ApiResponse {
+                success: true,
+                message: None,
+                data: Some(VarEntry {
+                    group,
+                    name,
+                    value: value.clone(),
+                }),
+            })
+        ),
+        None => error_response(ConfigError::NotFound(format!(
+            "Variable ${} not found in {}",
+            name,
+            group.key()
+        ))),
+    }
+}
+
+// 변수 생성 또는 수정
+pub async fn put_var(
+    State(state): State<AppState>,
+    PathExtractor((group, name)): PathExtractor<(VarGroup, String)>,
+    Json(payload): Json<VarRequest>,
+) -> (StatusCode, Json<ApiResponse<VarEntry>>) {
+    if let Err(e) = state.config.set_var(group, &name, &payload.value).await {
+        return error_response(e);
+    }
+    info!("Variable ${} in {} set to {}", name, group.key(), payload.value);
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: Some("Variable saved, reload rules or restart Suricata to take effect".to_string()),
+            data: Some(VarEntry {
+                group,
+                name,
+                value: payload.value.trim().to_string(),
+            }),
+        })
+    )
+}
+
+// 변수 삭제
+pub async fn delete_var(
+    State(state): State<AppState>,
+    PathExtractor((group, name)): PathExtractor<(VarGroup, String)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    if let Err(e) = state.config.delete_var(group, &name).await {
+        return error_response(e);
+    }
+    info!("Variable ${} removed from {}", name, group.key());
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse {
+            success: true,
+            message: Some("Variable deleted".to_string()),
+            data: None,
+        })
+    )
+}
@@ -3,15 +3,27 @@ use axum::{
 };
 use routes::routes;
 use tracing::{info, Level};
-use tracing_subscriber;
 
 mod handlers;
+mod middleware;
 mod models;
 mod routes;
 mod cors;
+mod services;
+mod state;
 mod utils;
 
 use crate::cors::cors::create_cors;
+use crate::middleware::metrics::track_http_metrics;
+use crate::services::alert_store::spawn_alert_store_pruner;
+use crate::services::counters::spawn_counter_poller;
+use crate::services::forwarder::spawn_forwarder;
+use crate::services::listener::spawn_eve_listeners;
+use crate::services::policy::spawn_policy_watcher;
+use crate::services::sink::spawn_sinks;
+use crate::services::supervisor::spawn_supervisor;
+use crate::services::tailer::spawn_eve_tailer;
+use crate::state::AppState;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
@@ -19,12 +31,32 @@ async fn main() -> Result<(), Error> {
         .with_max_level(Level::DEBUG)
         .init();
 
+    let state = match AppState::from_env() {
+        Ok(state) => state,
+        Err(e) => {
+            eprintln!("Failed to initialize: {}", e);
+            return Err(Error::new(e));
+        }
+    };
+    spawn_alert_store_pruner(state.alerts.clone());
+    spawn_counter_poller(state.counters.clone(), state.controller.clone());
+    spawn_supervisor(state.supervisor.clone());
+    spawn_forwarder(state.forwarder.clone());
+    spawn_policy_watcher(state.policy.clone());
+    spawn_sinks(state.sinks.clone());
+    spawn_eve_tailer(state.tailer.clone());
+    spawn_eve_listeners(state.listener.clone());
+
     let app: Router = Router::new()
         .route("/", get(root))
         .merge(routes())
+        .layer(
+            axum::middleware::from_fn_with_state(state.clone(), track_http_metrics)
+        )
         .layer(
             create_cors()
-        );
+        )
+        .with_state(state);
 
     let listener: tokio::net::TcpListener = match tokio::net::TcpListener::bind("0.0.0.0:3000").await{
         Ok(listener)=> listener,
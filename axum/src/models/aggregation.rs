@@ -0,0 +1,17 @@
+use serde::Serialize;
+
+// 집계 구간 동안 묶인 경보 요약 (alert_rollup 이벤트의 rollup 필드)
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertRollup {
+    // 구간 안의 전체 경보 수 (즉시 전달된 첫 경보 포함)
+    pub count: u64,
+    // 첫 경보 이후 묶여서 따로 전달되지 않은 경보 수
+    pub suppressed: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<String>,
+    pub window_secs: u64,
+    // 집계 키로 쓴 필드
+    pub key: Vec<String>,
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+// /alerts/stats 조회 조건
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertStatsQuery {
+    // 시작/끝 시각 (RFC 3339 또는 유닉스 초), 기본은 최근 1시간
+    pub from: Option<String>,
+    pub to: Option<String>,
+    // 1m, 1h, 1d (없으면 구간 길이에 맞춰 선택)
+    pub bucket: Option<String>,
+    // 상위 항목 개수
+    pub top: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TopCount<K> {
+    pub key: K,
+    pub count: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignatureCount {
+    pub signature_id: u64,
+    pub signature: String,
+    pub count: u64,
+}
+
+// 시간 구간별 경보 수 (start는 유닉스 초)
+#[derive(Debug, Serialize)]
+pub struct TimelinePoint {
+    pub start: u64,
+    pub count: u64,
+}
+
+// 구간 경보 통계
+// 구간은 버킷 경계에 맞춰지고, 버킷마다 값 종류 수에 한도가 있어 한도를 넘은 값은 other로 셈
+#[derive(Debug, Serialize)]
+pub struct AlertStatsReport {
+    pub from: u64,
+    pub to: u64,
+    pub bucket: String,
+    pub total: u64,
+    pub by_severity: BTreeMap<u8, u64>,
+    pub by_category: Vec<TopCount<String>>,
+    pub top_signatures: Vec<SignatureCount>,
+    pub top_src_ips: Vec<TopCount<String>>,
+    pub top_dest_ips: Vec<TopCount<String>>,
+    pub top_dest_ports: Vec<TopCount<u16>>,
+    // 한도를 넘어 개별로 세지 못한 경보 수 (항목별)
+    pub other: BTreeMap<String, u64>,
+    pub timeline: Vec<TimelinePoint>,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// /alerts 조회 조건 (모두 선택, 지정한 조건은 AND)
+#[derive(Debug, Default, Deserialize)]
+pub struct AlertQuery {
+    // 시작/끝 시각 (RFC 3339 또는 유닉스 초), 끝은 포함하지 않음
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub event_type: Option<String>,
+    pub signature_id: Option<u64>,
+    // 시그니처 이름 일부 (대소문자 구분 없음)
+    pub signature: Option<String>,
+    pub severity: Option<u8>,
+    // 출발지 또는 목적지
+    pub ip: Option<String>,
+    pub src_ip: Option<String>,
+    pub dest_ip: Option<String>,
+    pub proto: Option<String>,
+    pub action: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+// 저장된 이벤트 (보강/점수 포함 원본)
+#[derive(Debug, Serialize)]
+pub struct StoredAlert {
+    pub id: i64,
+    pub event: Value,
+}
+
+// 조회 결과 페이지 (최신순)
+#[derive(Debug, Serialize)]
+pub struct AlertPage {
+    // 조건에 맞는 전체 개수
+    pub total: u64,
+    pub offset: usize,
+    pub limit: usize,
+    pub alerts: Vec<StoredAlert>,
+}
@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+// 실시간 캡처 요청
+#[derive(Debug, Deserialize)]
+pub struct CaptureRequest {
+    pub interface: String,
+    // BPF 필터 (예: "host 10.0.0.5 and tcp port 443")
+    #[serde(default)]
+    pub filter: String,
+    pub duration_secs: Option<u64>,
+    pub packet_count: Option<u64>,
+}
+
+// pcap-log 링 버퍼에서 특정 플로우 패킷 추출 요청
+#[derive(Debug, Deserialize)]
+pub struct FlowCaptureRequest {
+    pub src_ip: String,
+    pub dest_ip: String,
+    pub src_port: Option<u16>,
+    pub dest_port: Option<u16>,
+    pub proto: Option<String>,
+    // 조회 구간 (유닉스 시각, 기본값: 최근 1시간)
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    pub packet_count: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureStatus {
+    Running,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureSource {
+    Live,
+    PcapLog,
+}
+
+// 캡처 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct CaptureInfo {
+    pub id: String,
+    pub source: CaptureSource,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<String>,
+    pub filter: String,
+    pub status: CaptureStatus,
+    pub created_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 캡처 목록
+#[derive(Debug, Serialize)]
+pub struct CaptureList {
+    pub captures: Vec<CaptureInfo>,
+    pub count: usize,
+}
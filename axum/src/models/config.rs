@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// 설정 변경 항목 (value가 null이면 키 삭제)
+#[derive(Debug, Deserialize)]
+pub struct ConfigPatch {
+    pub path: String,
+    #[serde(default)]
+    pub value: Value,
+}
+
+// 설정 변경 요청
+#[derive(Debug, Deserialize)]
+pub struct ConfigPatchRequest {
+    pub changes: Vec<ConfigPatch>,
+    // 적용 후 reload-rules 실행 여부 (vars 변경 시 유용)
+    #[serde(default)]
+    pub reload_rules: bool,
+}
+
+// vars 섹션
+#[derive(Debug, Serialize)]
+pub struct VarsSection {
+    pub address_groups: BTreeMap<String, String>,
+    pub port_groups: BTreeMap<String, String>,
+}
+
+// af-packet 인터페이스 설정
+#[derive(Debug, Serialize)]
+pub struct AfPacketInterface {
+    pub interface: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cluster_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub defrag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_iface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bpf_filter: Option<String>,
+}
+
+// eve-log 출력 설정
+#[derive(Debug, Serialize)]
+pub struct EveLogSection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filetype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    pub types: Vec<String>,
+}
+
+// 노출하는 suricata.yaml 섹션 모음
+#[derive(Debug, Serialize)]
+pub struct SuricataConfigView {
+    pub vars: VarsSection,
+    pub af_packet: Vec<AfPacketInterface>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eve_log: Option<EveLogSection>,
+    // 프로토콜별 enabled 값
+    pub app_layer: BTreeMap<String, String>,
+}
+
+// 변수 그룹 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VarGroup {
+    AddressGroups,
+    PortGroups,
+}
+
+impl VarGroup {
+    pub fn key(&self) -> &'static str {
+        match self {
+            VarGroup::AddressGroups => "address-groups",
+            VarGroup::PortGroups => "port-groups",
+        }
+    }
+}
+
+// 변수 생성/수정 요청
+#[derive(Debug, Deserialize)]
+pub struct VarRequest {
+    pub value: String,
+}
+
+// 변수 하나
+#[derive(Debug, Serialize)]
+pub struct VarEntry {
+    pub group: VarGroup,
+    pub name: String,
+    pub value: String,
+}
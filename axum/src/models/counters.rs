@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+// 카운터 조회 파라미터
+#[derive(Debug, Deserialize)]
+pub struct CounterQuery {
+    // 쉼표로 구분된 카운터 이름 (예: capture.kernel_packets,capture.kernel_drops)
+    pub names: Option<String>,
+    // 카운터 이름 접두사 (예: decoder.)
+    pub prefix: Option<String>,
+    // 조회 구간 (초, 기본값: 전체 보존 구간)
+    pub window: Option<u64>,
+    // 다운샘플링 간격 (초)
+    pub step: Option<u64>,
+}
+
+// 시계열의 한 지점
+#[derive(Debug, Serialize)]
+pub struct CounterPoint {
+    pub timestamp: u64,
+    pub value: u64,
+    // 직전 지점 대비 초당 증가량
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+}
+
+// 카운터 하나의 시계열
+#[derive(Debug, Serialize)]
+pub struct CounterSeries {
+    pub name: String,
+    pub points: Vec<CounterPoint>,
+}
+
+// 카운터 시계열 목록
+#[derive(Debug, Serialize)]
+pub struct CounterSeriesList {
+    pub source: String,
+    pub interval: u64,
+    pub retention: u64,
+    pub series: Vec<CounterSeries>,
+    pub count: usize,
+}
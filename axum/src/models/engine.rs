@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+// 엔진 동작 모드
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineMode {
+    // 탐지 후 경보만 발생
+    Ids,
+    // af-packet copy-mode=ips 인라인 차단
+    Ips,
+}
+
+// 현재 모드 정보
+#[derive(Debug, Serialize)]
+pub struct EngineModeView {
+    pub mode: EngineMode,
+    // copy-mode=ips로 묶인 인터페이스 쌍
+    pub ips_pairs: Vec<[String; 2]>,
+}
+
+// 모드 전환 요청
+#[derive(Debug, Deserialize)]
+pub struct EngineModeRequest {
+    pub mode: EngineMode,
+    // IPS 전환 시 서로 연결할 인터페이스 쌍
+    #[serde(default)]
+    pub pairs: Vec<[String; 2]>,
+}
+
+// 재시작 절차의 단계별 결과
+#[derive(Debug, Serialize)]
+pub struct EngineStep {
+    pub step: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// 재시작/모드 전환 결과
+#[derive(Debug, Serialize)]
+pub struct EngineReport {
+    pub action: String,
+    pub success: bool,
+    pub rolled_back: bool,
+    pub steps: Vec<EngineStep>,
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+// 자산 목록 항목 (CIDR 대역별 내부 자산 정보)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetEntry {
+    pub cidr: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+// 자산 목록 파일 형식
+#[derive(Debug, Default, Deserialize)]
+pub struct AssetInventory {
+    #[serde(default)]
+    pub assets: Vec<AssetEntry>,
+}
+
+// IP 하나에 대한 보강 정보 (EVE 이벤트의 enrichment.src_ip / enrichment.dest_ip)
+#[derive(Debug, Default, Serialize)]
+pub struct IpEnrichment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub longitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asn: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub as_org: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset: Option<AssetEntry>,
+    // 역방향 DNS (캐시에 있을 때만)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+}
+
+impl IpEnrichment {
+    pub fn is_empty(&self) -> bool {
+        self.country_code.is_none()
+            && self.city.is_none()
+            && self.asn.is_none()
+            && self.asset.is_none()
+            && self.hostname.is_none()
+    }
+}
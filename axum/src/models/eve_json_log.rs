@@ -1,11 +1,582 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 use std::collections::HashMap;
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct EveJsonLog {
-    #[serde(flatten)]  // 모든 필드를 동적으로 확장
-    pub extra_fields: HashMap<String, Value>,
-    #[serde(default)]  // 없으면 기본값
+// 모든 EVE 이벤트에 공통으로 들어가는 필드
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EveCommon {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flow_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pcap_cnt: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub in_iface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vlan: Option<Vec<u16>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub src_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dest_ip: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dest_port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proto: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_proto: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub community_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub action: String,
+    pub gid: u32,
+    pub signature_id: u64,
+    pub rev: u32,
+    #[serde(default)]
+    pub signature: String,
+    // classtype이 없으면 빈 문자열
+    #[serde(default)]
+    pub category: String,
+    pub severity: u8,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Flow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkts_toserver: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkts_toclient: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_toserver: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes_toclient: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub age: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alerted: Option<bool>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+// 버전 2(질의/응답 한 건씩)와 3(queries/answers 배열) 형식 모두 수용
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Dns {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<u32>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rrname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rrtype: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rcode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queries: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub answers: Option<Vec<Value>>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Http {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_user_agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tls {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sni: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuerdn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notbefore: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notafter: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gaps: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stored: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md5: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_id: Option<u64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Anomaly {
+    // decode, stream, applayer
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DroppedPacket {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub len: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+// 카운터 섹션(capture, decoder, flow 등)은 extra에 그대로 보관
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime: Option<u64>,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub alert: Alert,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub flow: Flow,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DnsEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub dns: Dns,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub http: Http,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub tls: Tls,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileInfoEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub fileinfo: FileInfo,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnomalyEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub anomaly: Anomaly,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DropEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub drop: DroppedPacket,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    pub stats: Stats,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+// 따로 정의하지 않은 event_type (smtp, ssh, netflow 등)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OtherEvent {
+    #[serde(flatten)]
+    pub common: EveCommon,
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+// event_type으로 구분되는 Suricata EVE 이벤트
+#[derive(Debug, Clone)]
+pub enum EveEvent {
+    Alert(AlertEvent),
+    Flow(FlowEvent),
+    Dns(DnsEvent),
+    Http(HttpEvent),
+    Tls(TlsEvent),
+    FileInfo(FileInfoEvent),
+    Anomaly(AnomalyEvent),
+    Drop(DropEvent),
+    Stats(StatsEvent),
+    Other(String, OtherEvent),
+    // event_type이 없는 이벤트 (받은 그대로 전달)
+    Untyped(OtherEvent),
+}
+
+impl EveEvent {
+    pub fn event_type(&self) -> &str {
+        match self {
+            EveEvent::Alert(_) => "alert",
+            EveEvent::Flow(_) => "flow",
+            EveEvent::Dns(_) => "dns",
+            EveEvent::Http(_) => "http",
+            EveEvent::Tls(_) => "tls",
+            EveEvent::FileInfo(_) => "fileinfo",
+            EveEvent::Anomaly(_) => "anomaly",
+            EveEvent::Drop(_) => "drop",
+            EveEvent::Stats(_) => "stats",
+            EveEvent::Other(event_type, _) => event_type,
+            EveEvent::Untyped(_) => "unknown",
+        }
+    }
+
+    pub fn common(&self) -> &EveCommon {
+        match self {
+            EveEvent::Alert(event) => &event.common,
+            EveEvent::Flow(event) => &event.common,
+            EveEvent::Dns(event) => &event.common,
+            EveEvent::Http(event) => &event.common,
+            EveEvent::Tls(event) => &event.common,
+            EveEvent::FileInfo(event) => &event.common,
+            EveEvent::Anomaly(event) => &event.common,
+            EveEvent::Drop(event) => &event.common,
+            EveEvent::Stats(event) => &event.common,
+            EveEvent::Other(_, event) | EveEvent::Untyped(event) => &event.common,
+        }
+    }
+
+    // 최상위의 정의되지 않은 필드 (보강 정보 등)
+    pub fn extra(&self) -> &HashMap<String, Value> {
+        match self {
+            EveEvent::Alert(event) => &event.extra,
+            EveEvent::Flow(event) => &event.extra,
+            EveEvent::Dns(event) => &event.extra,
+            EveEvent::Http(event) => &event.extra,
+            EveEvent::Tls(event) => &event.extra,
+            EveEvent::FileInfo(event) => &event.extra,
+            EveEvent::Anomaly(event) => &event.extra,
+            EveEvent::Drop(event) => &event.extra,
+            EveEvent::Stats(event) => &event.extra,
+            EveEvent::Other(_, event) | EveEvent::Untyped(event) => &event.extra,
+        }
+    }
+
+    pub fn extra_mut(&mut self) -> &mut HashMap<String, Value> {
+        match self {
+            EveEvent::Alert(event) => &mut event.extra,
+            EveEvent::Flow(event) => &mut event.extra,
+            EveEvent::Dns(event) => &mut event.extra,
+            EveEvent::Http(event) => &mut event.extra,
+            EveEvent::Tls(event) => &mut event.extra,
+            EveEvent::FileInfo(event) => &mut event.extra,
+            EveEvent::Anomaly(event) => &mut event.extra,
+            EveEvent::Drop(event) => &mut event.extra,
+            EveEvent::Stats(event) => &mut event.extra,
+            EveEvent::Other(_, event) | EveEvent::Untyped(event) => &mut event.extra,
+        }
+    }
+
+    pub fn alert(&self) -> Option<&Alert> {
+        match self {
+            EveEvent::Alert(event) => Some(&event.alert),
+            _ => None,
+        }
+    }
+
+    // 원래 JSON 형태로 변환 (event_type 포함)
+    pub fn to_value(&self) -> Result<Value, serde_json::Error> {
+        let mut value = match self {
+            EveEvent::Alert(event) => serde_json::to_value(event),
+            EveEvent::Flow(event) => serde_json::to_value(event),
+            EveEvent::Dns(event) => serde_json::to_value(event),
+            EveEvent::Http(event) => serde_json::to_value(event),
+            EveEvent::Tls(event) => serde_json::to_value(event),
+            EveEvent::FileInfo(event) => serde_json::to_value(event),
+            EveEvent::Anomaly(event) => serde_json::to_value(event),
+            EveEvent::Drop(event) => serde_json::to_value(event),
+            EveEvent::Stats(event) => serde_json::to_value(event),
+            EveEvent::Other(_, event) | EveEvent::Untyped(event) => serde_json::to_value(event),
+        }?;
+        if let EveEvent::Untyped(_) = self {
+            return Ok(value);
+        }
+        if let Some(fields) = value.as_object_mut() {
+            fields.insert("event_type".to_string(), Value::String(self.event_type().to_string()));
+        }
+        Ok(value)
+    }
+}
+
+impl TryFrom<Value> for EveEvent {
+    type Error = serde_json::Error;
+
+    fn try_from(mut value: Value) -> Result<Self, Self::Error> {
+        let Some(event_type) = value.as_object_mut()
+            .ok_or_else(|| serde_json::Error::custom("EVE event must be a JSON object"))?
+            .remove("event_type") else {
+            return Ok(EveEvent::Untyped(serde_json::from_value(value)?));
+        };
+        let Value::String(event_type) = event_type else {
+            return Err(serde_json::Error::custom("`event_type` must be a string"));
+        };
+
+        Ok(match event_type.as_str() {
+            "alert" => EveEvent::Alert(serde_json::from_value(value)?),
+            "flow" => EveEvent::Flow(serde_json::from_value(value)?),
+            "dns" => EveEvent::Dns(serde_json::from_value(value)?),
+            "http" => EveEvent::Http(serde_json::from_value(value)?),
+            "tls" => EveEvent::Tls(serde_json::from_value(value)?),
+            "fileinfo" => EveEvent::FileInfo(serde_json::from_value(value)?),
+            "anomaly" => EveEvent::Anomaly(serde_json::from_value(value)?),
+            "drop" => EveEvent::Drop(serde_json::from_value(value)?),
+            "stats" => EveEvent::Stats(serde_json::from_value(value)?),
+            _ => EveEvent::Other(event_type, serde_json::from_value(value)?),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for EveEvent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        EveEvent::try_from(Value::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for EveEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value()
+            .map_err(serde::ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EveItemStatus {
+    // 형식 오류 (재전송해도 실패)
+    Rejected,
+    // 스풀 기록 실패 (재전송하면 성공할 수 있음)
+    Failed,
+}
+
+// 항목별 오류 (index는 요청 배열 안의 위치)
+#[derive(Debug, Serialize)]
+pub struct EveItemError {
+    pub index: usize,
+    pub status: EveItemStatus,
+    pub error: String,
+}
+
+// 수신 결과
+#[derive(Debug, Serialize)]
+pub struct EveIngestResult {
+    // 스풀에 기록된 이벤트 수
+    pub accepted: usize,
+    // 정책에 따라 버린 이벤트 수 (오류 아님)
+    pub filtered: usize,
+    // 같은 경보로 묶여 롤업에만 집계된 이벤트 수
+    pub aggregated: usize,
+    pub rejected: usize,
+    pub failed: usize,
+    pub queue_depth: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<EveItemError>,
+}
+
+// 테스트용 EVE 예시 (tests/fixtures/eve.json, event_type별 한 줄)
+#[cfg(test)]
+pub mod test_support {
+    use serde_json::Value;
+
+    use super::EveEvent;
+
+    pub const SAMPLES: &str = include_str!("../../tests/fixtures/eve.json");
+
+    // event_type이 같은 첫 예시
+    pub fn sample(event_type: &str) -> Value {
+        SAMPLES.lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .find(|value| value["event_type"] == event_type)
+            .unwrap_or_else(|| panic!("no {} sample", event_type))
+    }
+
+    pub fn sample_event(event_type: &str) -> EveEvent {
+        EveEvent::try_from(sample(event_type)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::sample;
+    use super::*;
+
+    // 파싱 후 다시 JSON으로 바꾸면 원래 줄과 같아야 함
+    fn round_trip(event_type: &str) -> EveEvent {
+        let value = sample(event_type);
+        let event = EveEvent::try_from(value.clone()).unwrap();
+        assert_eq!(event.event_type(), event_type);
+        assert_eq!(event.to_value().unwrap(), value);
+        event
+    }
+
+    #[test]
+    fn alert_round_trip() {
+        let event = round_trip("alert");
+        let alert = event.alert().unwrap();
+        assert_eq!(alert.signature_id, 2100498);
+        assert_eq!(alert.severity, 2);
+        assert_eq!(event.common().dest_port, Some(80));
+    }
+
+    #[test]
+    fn flow_round_trip() {
+        assert!(matches!(round_trip("flow"), EveEvent::Flow(_)));
+    }
+
+    #[test]
+    fn dns_round_trip() {
+        assert!(matches!(round_trip("dns"), EveEvent::Dns(_)));
+    }
+
+    #[test]
+    fn http_round_trip() {
+        assert!(matches!(round_trip("http"), EveEvent::Http(_)));
+    }
+
+    #[test]
+    fn tls_round_trip() {
+        assert!(matches!(round_trip("tls"), EveEvent::Tls(_)));
+    }
+
+    #[test]
+    fn fileinfo_round_trip() {
+        assert!(matches!(round_trip("fileinfo"), EveEvent::FileInfo(_)));
+    }
+
+    #[test]
+    fn anomaly_round_trip() {
+        assert!(matches!(round_trip("anomaly"), EveEvent::Anomaly(_)));
+    }
+
+    #[test]
+    fn unknown_type_round_trip() {
+        assert!(matches!(round_trip("ssh"), EveEvent::Other(..)));
+    }
+
+    #[test]
+    fn missing_event_type_is_kept_as_is() {
+        let value = serde_json::json!({"src_ip": "10.0.0.1", "custom": {"a": 1}});
+        let event = EveEvent::try_from(value.clone()).unwrap();
+        assert_eq!(event.event_type(), "unknown");
+        assert_eq!(event.to_value().unwrap(), value);
+    }
+
+    #[test]
+    fn tags_are_kept_when_present() {
+        let value = serde_json::json!({"event_type": "flow", "src_ip": "10.0.0.1", "tags": ["a"], "flow": {}});
+        let event = EveEvent::try_from(value.clone()).unwrap();
+        assert_eq!(event.to_value().unwrap(), value);
+    }
+
+    #[test]
+    fn invalid_events_are_rejected() {
+        assert!(EveEvent::try_from(serde_json::json!([1])).is_err());
+        assert!(EveEvent::try_from(serde_json::json!({"event_type": 1})).is_err());
+        assert!(EveEvent::try_from(serde_json::json!({"event_type": "alert", "alert": {}})).is_err());
+    }
+}
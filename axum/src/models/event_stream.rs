@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+// /events/stream 서버 측 필터 (지정한 조건은 AND)
+#[derive(Debug, Default, Deserialize)]
+pub struct EventStreamQuery {
+    // 쉼표로 구분한 event_type 목록
+    pub event_type: Option<String>,
+    // 쉼표로 구분한 경보 severity 목록
+    pub severity: Option<String>,
+    // 출발지 또는 목적지 주소/CIDR
+    pub ip: Option<String>,
+    // 숫자면 signature_id, 아니면 시그니처 이름 일부 (대소문자 구분 없음)
+    pub signature: Option<String>,
+}
+
+// 느린 구독자에게 보내지 못하고 버린 이벤트 수 알림
+#[derive(Debug, Serialize)]
+pub struct StreamDropped {
+    pub dropped: u64,
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+
+// Suricata 엔진 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EngineState {
+    // 아직 점검 전
+    Unknown,
+    Healthy,
+    // 패킷 카운터가 증가하지 않음
+    Stalled,
+    // 유닉스 소켓 응답 없음
+    Unreachable,
+}
+
+// 감시 중 발생한 사건
+#[derive(Debug, Clone, Serialize)]
+pub struct Incident {
+    pub kind: String,
+    pub message: String,
+    pub timestamp: u64,
+}
+
+// 감시 태스크가 관리하는 엔진 상태 상세
+#[derive(Debug, Clone, Serialize)]
+pub struct EngineHealth {
+    pub state: EngineState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_check: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub engine_uptime: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel_packets: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_packet_change: Option<u64>,
+    pub consecutive_failures: u32,
+    pub stalled_polls: u32,
+    pub restarts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_restart: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_incident: Option<Incident>,
+}
+
+// 에이전트 자체 생존 여부
+#[derive(Debug, Serialize)]
+pub struct Liveness {
+    pub alive: bool,
+    pub uptime: u64,
+}
+
+// 요청 처리 준비 여부 (Suricata 정상 동작 포함)
+#[derive(Debug, Serialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub engine: EngineHealth,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub liveness: Liveness,
+    pub readiness: Readiness,
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::config::AfPacketInterface;
+
+// 호스트 네트워크 인터페이스
+#[derive(Debug, Serialize)]
+pub struct HostInterface {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mac: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u32>,
+    // 현재 캡처 모드에서 Suricata가 감시 중인지 여부
+    pub monitored: bool,
+}
+
+// 캡처 방식별 설정된 인터페이스
+#[derive(Debug, Serialize)]
+pub struct CaptureInterfaces {
+    pub af_packet: Vec<AfPacketInterface>,
+    pub af_xdp: Vec<String>,
+    pub pcap: Vec<String>,
+}
+
+// 인터페이스 현황
+#[derive(Debug, Serialize)]
+pub struct InterfaceOverview {
+    // 엔진이 사용하는 캡처 방식 (af-packet, af-xdp, pcap)
+    pub mode: String,
+    pub host: Vec<HostInterface>,
+    pub capture: CaptureInterfaces,
+}
+
+// af-packet 캡처 인터페이스 설정 요청 항목
+#[derive(Debug, Deserialize)]
+pub struct CaptureInterfaceRequest {
+    pub interface: String,
+    // ips 또는 tap (copy_iface 필수)
+    pub copy_mode: Option<String>,
+    pub copy_iface: Option<String>,
+    pub threads: Option<String>,
+    pub cluster_id: Option<u32>,
+}
+
+fn default_restart() -> bool {
+    true
+}
+
+// af-packet 인터페이스 목록 교체 요청
+#[derive(Debug, Deserialize)]
+pub struct UpdateInterfacesRequest {
+    pub interfaces: Vec<CaptureInterfaceRequest>,
+    // 적용 후 엔진 재시작 여부
+    #[serde(default = "default_restart")]
+    pub restart: bool,
+}
+
+// 인터페이스 통계 조회 파라미터
+#[derive(Debug, Deserialize)]
+pub struct InterfaceStatsQuery {
+    pub interface: Option<String>,
+}
@@ -1,2 +1,19 @@
+pub mod aggregation;
+pub mod alert_stats;
+pub mod alert_store;
+pub mod capture;
+pub mod config;
+pub mod counters;
+pub mod engine;
+pub mod enrichment;
 pub mod eve_json_log;
-pub mod rule;
\ No newline at end of file
+pub mod event_stream;
+pub mod health;
+pub mod interface;
+pub mod pcap_job;
+pub mod policy;
+pub mod rule;
+pub mod scoring;
+pub mod sink;
+pub mod spool;
+pub mod tailer;
@@ -0,0 +1,70 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// pcap 업로드 파라미터
+#[derive(Debug, Deserialize)]
+pub struct PcapJobRequest {
+    // 원본 파일 이름 (표시용)
+    pub name: Option<String>,
+    // 분석 결과 이벤트를 중앙 서버로 전달할지 여부
+    #[serde(default)]
+    pub forward: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PcapJobStatus {
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+// 분석 작업 정보
+#[derive(Debug, Clone, Serialize)]
+pub struct PcapJobInfo {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub status: PcapJobStatus,
+    pub forward: bool,
+    pub size: u64,
+    pub created_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    // event_type별 이벤트 수
+    pub events: BTreeMap<String, u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forwarded: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+// 작업 목록
+#[derive(Debug, Serialize)]
+pub struct PcapJobList {
+    pub jobs: Vec<PcapJobInfo>,
+    pub count: usize,
+}
+
+// 결과 이벤트 조회 파라미터
+#[derive(Debug, Deserialize)]
+pub struct PcapJobEventsQuery {
+    pub event_type: Option<String>,
+    #[serde(default)]
+    pub offset: usize,
+    pub limit: Option<usize>,
+}
+
+// 결과 이벤트 페이지
+#[derive(Debug, Serialize)]
+pub struct PcapJobEvents {
+    pub job_id: String,
+    pub total: usize,
+    pub offset: usize,
+    pub events: Vec<Value>,
+}
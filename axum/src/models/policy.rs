@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyAction {
+    #[default]
+    Keep,
+    Drop,
+    // rate 비율만큼 무작위로 유지
+    Sample,
+}
+
+// 조건은 모두 만족해야 일치 (비어 있는 조건은 무시)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub name: String,
+    pub action: PolicyAction,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
+    // 이 값보다 덜 심각한 경고 (Suricata severity 숫자가 더 큼)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity_below: Option<u8>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signature_ids: Vec<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub src_cidrs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dest_cidrs: Vec<String>,
+    // sample 동작에서 유지할 비율 (0.0 ~ 1.0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<f64>,
+}
+
+// 수신 EVE 이벤트 필터링 정책 (위에서부터 처음 일치하는 규칙 적용)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PolicyConfig {
+    // 어느 규칙에도 일치하지 않을 때
+    #[serde(default)]
+    pub default_action: PolicyAction,
+    #[serde(default)]
+    pub rules: Vec<PolicyRule>,
+}
+
+// 규칙별 처리 건수 (default는 규칙 미일치)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PolicyRuleCounter {
+    pub rule: String,
+    pub kept: u64,
+    pub dropped: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PolicyStatus {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub loaded_at: Option<u64>,
+    pub policy: PolicyConfig,
+    pub counters: Vec<PolicyRuleCounter>,
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct RuleRequest {
     pub rule_content: String,
     pub rule_type: Option<String>, 
@@ -0,0 +1,69 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+// 경보 점수 가중치 (점수는 0~100으로 제한)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringWeights {
+    // Suricata severity(1이 가장 높음)별 점수
+    pub severity: BTreeMap<u8, f64>,
+    // 경보 category(classtype 설명)별 점수
+    pub classtypes: HashMap<String, f64>,
+    // 목적지가 중요 자산일 때 더하는 점수
+    pub critical_asset: f64,
+    // 중요 자산으로 보는 자산 목록 태그
+    pub critical_tags: Vec<String>,
+    // drop 규칙으로 차단된 경보(action: blocked)일 때 더하는 점수
+    pub blocked: f64,
+    // 처음 보는 출발지/목적지 쌍일 때 더하는 점수
+    pub new_pair: f64,
+    // 같은 쌍이 10배 늘 때마다 빼는 점수 (최대 3단계)
+    pub repeat_penalty: f64,
+    // 같은 쌍의 횟수를 세는 구간
+    pub pair_window_secs: u64,
+    // 이 점수 이상이면 우선 전송
+    pub priority_threshold: f64,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        ScoringWeights {
+            severity: BTreeMap::from([(1, 40.0), (2, 25.0), (3, 10.0)]),
+            classtypes: HashMap::from([
+                ("A Network Trojan was detected".to_string(), 20.0),
+                ("Attempted Administrator Privilege Gain".to_string(), 20.0),
+                ("Successful Administrator Privilege Gain".to_string(), 25.0),
+                ("Executable code was detected".to_string(), 15.0),
+                ("Web Application Attack".to_string(), 10.0),
+                ("Potentially Bad Traffic".to_string(), 5.0),
+            ]),
+            critical_asset: 25.0,
+            critical_tags: vec!["critical".to_string()],
+            blocked: 20.0,
+            new_pair: 10.0,
+            repeat_penalty: 5.0,
+            pair_window_secs: 3600,
+            priority_threshold: 60.0,
+        }
+    }
+}
+
+// 항목별 점수
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScoreFactors {
+    pub severity: f64,
+    pub classtype: f64,
+    pub critical_asset: f64,
+    pub blocked: f64,
+    pub pair: f64,
+}
+
+// 경보 이벤트의 score 필드
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertScore {
+    pub value: f64,
+    // 우선 전송 대상인지
+    pub priority: bool,
+    pub factors: ScoreFactors,
+}
@@ -0,0 +1,273 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::spool::SpoolStatus;
+
+// 전달 대상 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkTransport {
+    // syslog (address)
+    #[default]
+    Udp,
+    Tcp,
+    Tls,
+    // EVE JSON 배열을 POST (url)
+    Http,
+    // 이벤트마다 EVE JSON 객체 하나를 POST (url)
+    Webhook,
+    // Kafka REST 프록시로 토픽에 기록 (url, topic)
+    Kafka,
+    // EVE JSON 줄을 로컬 파일에 추가 (path)
+    File,
+}
+
+impl SinkTransport {
+    pub fn is_syslog(&self) -> bool {
+        matches!(self, SinkTransport::Udp | SinkTransport::Tcp | SinkTransport::Tls)
+    }
+}
+
+// syslog MSG 부분 형식 (syslog 대상만 해당, 나머지는 항상 EVE JSON)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SinkFormat {
+    // EVE JSON 그대로
+    Json,
+    #[default]
+    Cef,
+    Leef,
+}
+
+// TCP/TLS 메시지 구분 방식 (RFC 6587)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    // "길이 메시지"
+    #[default]
+    OctetCounting,
+    // 메시지 끝에 개행
+    NonTransparent,
+}
+
+// RFC 5424 헤더 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyslogOptions {
+    // 0 ~ 23 (기본 16 = local0)
+    pub facility: u8,
+    pub app_name: String,
+    // 없으면 시스템 호스트 이름
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    pub framing: SyslogFraming,
+    // 메시지 최대 크기, 넘으면 잘라서 보냄 (없으면 UDP는 데이터그램 한도, TCP/TLS는 제한 없음)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_message_bytes: Option<usize>,
+}
+
+impl Default for SyslogOptions {
+    fn default() -> Self {
+        SyslogOptions {
+            facility: 16,
+            app_name: "suricata".to_string(),
+            hostname: None,
+            framing: SyslogFraming::default(),
+            max_message_bytes: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsOptions {
+    // 서버 인증서 검증용 CA (없으면 공개 루트 인증서)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<String>,
+    // 클라이언트 인증서/키 (상호 인증이 필요할 때)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cert_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<String>,
+    // 인증서 검증에 쓸 이름 (없으면 address의 호스트)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+}
+
+// CEF/LEEF 헤더의 장비 정보
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeviceOptions {
+    pub vendor: String,
+    pub product: String,
+    pub version: String,
+}
+
+impl Default for DeviceOptions {
+    fn default() -> Self {
+        DeviceOptions {
+            vendor: "OISF".to_string(),
+            product: "Suricata".to_string(),
+            version: "7.0".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    pub base_ms: u64,
+    pub max_secs: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy { base_ms: 500, max_secs: 60 }
+    }
+}
+
+// 싱크별 디스크 큐 한도
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueueOptions {
+    pub max_mb: u64,
+    pub max_age_hours: u64,
+    pub batch_size: usize,
+}
+
+impl Default for QueueOptions {
+    fn default() -> Self {
+        QueueOptions { max_mb: 256, max_age_hours: 72, batch_size: 200 }
+    }
+}
+
+// 파일 대상 교체 설정
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FileOptions {
+    // 이 크기를 넘으면 path.1, path.2 ... 로 밀어냄
+    pub max_mb: u64,
+    // 남겨 둘 이전 파일 수
+    pub keep: usize,
+}
+
+impl Default for FileOptions {
+    fn default() -> Self {
+        FileOptions { max_mb: 100, keep: 5 }
+    }
+}
+
+// 라우팅 조건 (지정한 조건은 모두 만족해야 일치)
+// event_types를 비워 두면 syslog 대상은 경보만, 나머지는 모든 이벤트
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SinkRoute {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub event_types: Vec<String>,
+    // 이 값 이하 severity의 경보만 (1이 가장 심각)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_severity: Option<u8>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub signature_ids: Vec<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub src_cidrs: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dest_cidrs: Vec<String>,
+    // 경보 점수 하한
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_score: Option<f64>,
+    // EVE 필드 경로 (점으로 구분) → 허용 값 목록
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, Vec<String>>,
+}
+
+// 중앙 API 외 추가 전달 대상 (syslog 수집기, SIEM, HTTP, webhook, Kafka, 파일)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SinkConfig {
+    pub name: String,
+    #[serde(default, alias = "type")]
+    pub transport: SinkTransport,
+    // syslog 대상 host:port
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub address: String,
+    // http, webhook, kafka 대상 URL (kafka는 REST 프록시 주소)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    // Kafka 레코드 키로 쓸 EVE 필드 경로 (예: flow_id)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    // HTTP 요청에 더할 헤더 (인증 토큰 등)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    // file 대상 경로
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub file: FileOptions,
+    #[serde(default)]
+    pub format: SinkFormat,
+    #[serde(default)]
+    pub route: SinkRoute,
+    // 이전 형식 (route.event_types로 옮겨 적용)
+    #[serde(default, skip_serializing)]
+    pub event_types: Option<Vec<String>>,
+    #[serde(default)]
+    pub syslog: SyslogOptions,
+    #[serde(default)]
+    pub tls: TlsOptions,
+    #[serde(default)]
+    pub device: DeviceOptions,
+    // CEF/LEEF 확장 필드 → EVE 필드 경로 (점으로 구분, =로 시작하면 상수)
+    // 비워 두면 형식별 기본 매핑
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub mapping: BTreeMap<String, String>,
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    #[serde(default)]
+    pub queue: QueueOptions,
+}
+
+// 중앙 API(CENTRAL_API_SERVER_URL)로 보낼 이벤트 (기본은 모두)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CentralOutput {
+    #[serde(default)]
+    pub route: SinkRoute,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SinksFile {
+    #[serde(default)]
+    pub central: CentralOutput,
+    #[serde(default)]
+    pub sinks: Vec<SinkConfig>,
+}
+
+// 싱크별 전달 상태
+#[derive(Debug, Serialize)]
+pub struct SinkStatus {
+    pub name: String,
+    pub transport: SinkTransport,
+    // address, url 또는 path
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<SinkFormat>,
+    pub route: SinkRoute,
+    pub queue: SpoolStatus,
+    // 재시도 중이 아니면 정상
+    pub healthy: bool,
+    pub connected: bool,
+    pub retrying: bool,
+    pub retry_attempts: u32,
+    // 라우팅 조건에 맞아 큐에 들어간 이벤트 수
+    pub routed_events: u64,
+    pub sent_events: u64,
+    // 재시도해도 받아들여지지 않아 버린 이벤트 수 (HTTP 4xx)
+    pub rejected_events: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sent_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+// 디스크 스풀 상태
+#[derive(Debug, Serialize)]
+pub struct SpoolStatus {
+    // 아직 전달 확인되지 않은 이벤트 수
+    pub pending_events: u64,
+    pub pending_bytes: u64,
+    pub segments: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_segment_age_secs: Option<u64>,
+    // 크기/보관 기간 한도로 버려진 이벤트 수 (에이전트 시작 이후)
+    pub dropped_events: u64,
+}
+
+// 전달 상태 (스풀 + 재시도)
+#[derive(Debug, Serialize)]
+pub struct ForwardStatus {
+    pub spool: SpoolStatus,
+    // 우선 전송 경보 스풀
+    pub priority_spool: SpoolStatus,
+    // 중앙 API 전송 실패로 재시도 중인지
+    pub retrying: bool,
+    // 밀린 이벤트를 순서대로 재전송 중인지
+    pub replaying: bool,
+    pub retry_attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
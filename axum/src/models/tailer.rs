@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+// eve.json 읽기 위치 (재시작 후 이어 읽기용)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct TailPosition {
+    pub inode: u64,
+    // 마지막으로 전달한 줄 다음 바이트
+    pub offset: u64,
+}
+
+// 내장 eve.json tailer 상태
+#[derive(Debug, Serialize)]
+pub struct TailStatus {
+    pub enabled: bool,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position: Option<TailPosition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_size: Option<u64>,
+    // 아직 읽지 않은 바이트 수
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag_bytes: Option<u64>,
+    pub lines_read: u64,
+    // JSON으로 파싱하지 못한 줄 수
+    pub invalid_lines: u64,
+    pub rotations: u64,
+    pub truncations: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
@@ -0,0 +1,13 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::alert::{get_alert_stats, get_alerts};
+use crate::state::AppState;
+
+pub fn router_alert() -> Router<AppState> {
+    Router::new()
+    .route("/alerts", get(get_alerts))
+    .route("/alerts/stats", get(get_alert_stats))
+}
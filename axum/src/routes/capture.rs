@@ -0,0 +1,28 @@
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::handlers::capture::{
+    delete_capture,
+    download_capture,
+    get_capture,
+    get_captures,
+    start_capture,
+    start_flow_capture
+};
+use crate::state::AppState;
+
+pub fn router_capture() -> Router<AppState> {
+    Router::new()
+        .nest(
+            "/captures",
+            Router::new()
+                .route("/", get(get_captures))
+                .route("/", post(start_capture))
+                .route("/flow", post(start_flow_capture))
+                .route("/{id}", get(get_capture))
+                .route("/{id}", delete(delete_capture))
+                .route("/{id}/pcap", get(download_capture))
+        )
+}
@@ -1,11 +1,15 @@
 use axum::{
-    routing::post,
+    routing::{get, post},
     Router,
 };
 
-use crate::handlers::eve_json_log::send_eve_json_log;
+use crate::handlers::eve_json_log::{get_sink_status, get_spool_status, get_tail_status, send_eve_json_log};
+use crate::state::AppState;
 
-pub fn router_eve_json_log() -> Router {
+pub fn router_eve_json_log() -> Router<AppState> {
     Router::new()
     .route("/eve_json_log", post(send_eve_json_log))
+    .route("/eve_json_log/sinks", get(get_sink_status))
+    .route("/eve_json_log/spool", get(get_spool_status))
+    .route("/eve_json_log/tail", get(get_tail_status))
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::event_stream::stream_events;
+use crate::state::AppState;
+
+pub fn router_event_stream() -> Router<AppState> {
+    Router::new()
+    .route("/events/stream", get(stream_events))
+}
@@ -0,0 +1,18 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::health::{get_health, get_liveness, get_readiness};
+use crate::state::AppState;
+
+pub fn router_health() -> Router<AppState> {
+    Router::new()
+        .nest(
+            "/health",
+            Router::new()
+                .route("/", get(get_health))
+                .route("/live", get(get_liveness))
+                .route("/ready", get(get_readiness))
+        )
+}
@@ -0,0 +1,12 @@
+use axum::{
+    routing::get,
+    Router,
+};
+
+use crate::handlers::metrics::get_metrics;
+use crate::state::AppState;
+
+pub fn router_metrics() -> Router<AppState> {
+    Router::new()
+    .route("/metrics", get(get_metrics))
+}
@@ -1,12 +1,28 @@
 use axum::Router;
 
+use crate::state::AppState;
+
+pub mod alert;
+pub mod capture;
 pub mod eve_json_log;
+pub mod event_stream;
+pub mod health;
+pub mod metrics;
+pub mod pcap_job;
+pub mod policy;
 pub mod rule;
 pub mod suricata;
 
-pub fn routes() -> Router {
+pub fn routes() -> Router<AppState> {
     Router::new()
+    .merge(alert::router_alert())
+    .merge(capture::router_capture())
     .merge(eve_json_log::router_eve_json_log())
+    .merge(event_stream::router_event_stream())
+    .merge(health::router_health())
+    .merge(metrics::router_metrics())
+    .merge(pcap_job::router_pcap_job())
+    .merge(policy::router_policy())
     .merge(rule::router_rule())
     .merge(suricata::router_suricata())
 }
\ No newline at end of file
@@ -0,0 +1,28 @@
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{delete, get, post},
+    Router,
+};
+
+use crate::handlers::pcap_job::{
+    create_pcap_job,
+    delete_pcap_job,
+    get_pcap_job,
+    get_pcap_job_events,
+    get_pcap_jobs
+};
+use crate::state::AppState;
+
+pub fn router_pcap_job() -> Router<AppState> {
+    Router::new()
+        .nest(
+            "/pcap_jobs",
+            Router::new()
+                .route("/", get(get_pcap_jobs))
+                // 업로드 크기는 핸들러에서 PCAP_JOB_MAX_UPLOAD_BYTES로 제한
+                .route("/", post(create_pcap_job).layer(DefaultBodyLimit::disable()))
+                .route("/{id}", get(get_pcap_job))
+                .route("/{id}", delete(delete_pcap_job))
+                .route("/{id}/events", get(get_pcap_job_events))
+        )
+}
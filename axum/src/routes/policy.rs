@@ -0,0 +1,18 @@
+use axum::{
+    routing::{get, post, put},
+    Router,
+};
+
+use crate::handlers::policy::{get_policy, reload_policy, update_policy};
+use crate::state::AppState;
+
+pub fn router_policy() -> Router<AppState> {
+    Router::new()
+        .nest(
+            "/policy",
+            Router::new()
+                .route("/", get(get_policy))
+                .route("/", put(update_policy))
+                .route("/reload", post(reload_policy))
+        )
+}
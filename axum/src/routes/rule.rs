@@ -4,8 +4,9 @@ use axum::{
 };
 
 use crate::handlers::rule::{create_rule, delete_rule, get_rule, get_rules};
+use crate::state::AppState;
 
-pub fn router_rule() -> Router {
+pub fn router_rule() -> Router<AppState> {
     Router::new()
         .nest(
             "/rule", 
@@ -1,16 +1,26 @@
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
+use crate::handlers::config::{
+    get_suricata_config,
+    patch_suricata_config,
+    rollback_suricata_config
+};
+use crate::handlers::engine::{get_engine_mode, put_engine_mode, restart_engine};
+use crate::handlers::interface::{get_capture_interfaces, put_capture_interfaces};
 use crate::handlers::suricata::{
     get_interface_statistics, 
+    get_suricata_counters,
     get_suricata_rule_statistics, 
     get_suricata_status, 
     reload_suricata_rules
 };
+use crate::handlers::vars::{delete_var, get_var, get_vars, put_var};
+use crate::state::AppState;
 
-pub fn router_suricata() -> Router {
+pub fn router_suricata() -> Router<AppState> {
     Router::new()
         .nest(
             "/suricata", 
@@ -19,5 +29,18 @@ pub fn router_suricata() -> Router {
                 .route("/statistics", get(get_suricata_rule_statistics))
                 .route("/interface", get(get_interface_statistics))
                 .route("/rules/reload", post(reload_suricata_rules))
+                .route("/counters", get(get_suricata_counters))
+                .route("/config", get(get_suricata_config))
+                .route("/config", patch(patch_suricata_config))
+                .route("/config/rollback", post(rollback_suricata_config))
+                .route("/engine/restart", post(restart_engine))
+                .route("/engine/mode", get(get_engine_mode))
+                .route("/engine/mode", put(put_engine_mode))
+                .route("/interfaces", get(get_capture_interfaces))
+                .route("/interfaces", put(put_capture_interfaces))
+                .route("/vars", get(get_vars))
+                .route("/vars/{group}/{name}", get(get_var))
+                .route("/vars/{group}/{name}", put(put_var))
+                .route("/vars/{group}/{name}", delete(delete_var))
         )
 }
\ No newline at end of file
@@ -0,0 +1,372 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::models::aggregation::AlertRollup;
+use crate::models::eve_json_log::{EveCommon, EveEvent, OtherEvent};
+use crate::services::enrichment::ENRICHMENT_KEY;
+use crate::services::metrics::Metrics;
+use crate::utils::config::env_or;
+
+// 롤업 이벤트의 event_type
+const ROLLUP_EVENT_TYPE: &str = "alert_rollup";
+
+// 집계 키로 쓸 수 있는 필드
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyField {
+    SignatureId,
+    SrcIp,
+    DestIp,
+    SrcPort,
+    DestPort,
+    Proto,
+    AppProto,
+    InIface,
+}
+
+impl KeyField {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "signature_id" => Some(KeyField::SignatureId),
+            "src_ip" => Some(KeyField::SrcIp),
+            "dest_ip" => Some(KeyField::DestIp),
+            "src_port" => Some(KeyField::SrcPort),
+            "dest_port" => Some(KeyField::DestPort),
+            "proto" => Some(KeyField::Proto),
+            "app_proto" => Some(KeyField::AppProto),
+            "in_iface" => Some(KeyField::InIface),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            KeyField::SignatureId => "signature_id",
+            KeyField::SrcIp => "src_ip",
+            KeyField::DestIp => "dest_ip",
+            KeyField::SrcPort => "src_port",
+            KeyField::DestPort => "dest_port",
+            KeyField::Proto => "proto",
+            KeyField::AppProto => "app_proto",
+            KeyField::InIface => "in_iface",
+        }
+    }
+
+    // 값이 없으면 빈 문자열
+    fn value(self, event: &EveEvent) -> String {
+        let common = event.common();
+        match self {
+            KeyField::SignatureId => event.alert().map(|alert| alert.signature_id.to_string()),
+            KeyField::SrcIp => common.src_ip.clone(),
+            KeyField::DestIp => common.dest_ip.clone(),
+            KeyField::SrcPort => common.src_port.map(|port| port.to_string()),
+            KeyField::DestPort => common.dest_port.map(|port| port.to_string()),
+            KeyField::Proto => common.proto.clone(),
+            KeyField::AppProto => common.app_proto.clone(),
+            KeyField::InIface => common.in_iface.clone(),
+        }
+        .unwrap_or_default()
+    }
+}
+
+// 키별 집계 구간
+struct AlertWindow {
+    opened: Instant,
+    count: u64,
+    first_seen: Option<String>,
+    last_seen: Option<String>,
+    // 첫 경보에서 키 필드만 뽑아 둔 롤업 이벤트 틀
+    template: OtherEvent,
+}
+
+#[derive(Default)]
+struct Windows {
+    open: HashMap<String, AlertWindow>,
+    // 새 경보가 들어오면서 끝난 구간의 롤업 (다음 flush 때 내보냄)
+    ready: Vec<EveEvent>,
+}
+
+// 같은 키(기본: signature_id + src_ip + dest_ip)의 경보를 구간 단위로 묶음
+// 구간의 첫 경보는 바로 전달하고, 나머지는 구간이 끝날 때 alert_rollup 이벤트 하나로 전달
+// 경보가 아닌 이벤트는 그대로 통과
+pub struct AlertAggregator {
+    metrics: Arc<Metrics>,
+    fields: Vec<KeyField>,
+    window: Duration,
+    max_keys: usize,
+    windows: Mutex<Windows>,
+}
+
+impl AlertAggregator {
+    pub fn from_env(metrics: Arc<Metrics>) -> Self {
+        let mut fields: Vec<KeyField> = Vec::new();
+        for name in env_or("ALERT_AGGREGATE_KEY", "signature_id,src_ip,dest_ip".to_string()).split(',') {
+            let name = name.trim();
+            if name.is_empty() {
+                continue;
+            }
+            match KeyField::parse(name) {
+                Some(field) if !fields.contains(&field) => fields.push(field),
+                Some(_) => {}
+                None => warn!("Ignoring unknown alert aggregation key field '{}'", name),
+            }
+        }
+
+        let aggregator = AlertAggregator {
+            metrics,
+            fields,
+            window: Duration::from_secs(env_or("ALERT_AGGREGATE_WINDOW_SECS", 60u64)),
+            max_keys: env_or("ALERT_AGGREGATE_MAX_KEYS", 10_000usize).max(1),
+            windows: Mutex::new(Windows::default()),
+        };
+        if aggregator.is_enabled() {
+            info!(
+                "Aggregating alerts by {} over {}s windows",
+                aggregator.key_names().join("+"),
+                aggregator.window.as_secs()
+            );
+        } else {
+            info!("Alert aggregation is disabled");
+        }
+        aggregator
+    }
+
+    // 구간이 0초이거나 키 필드가 없으면 비활성
+    pub fn is_enabled(&self) -> bool {
+        !self.window.is_zero() && !self.fields.is_empty()
+    }
+
+    // 이벤트를 바로 전달할지 판단 (false면 롤업에만 집계)
+    pub fn observe(&self, event: &EveEvent) -> bool {
+        if !self.is_enabled() || event.alert().is_none() {
+            return true;
+        }
+        let key = self.fields.iter()
+            .map(|field| field.value(event))
+            .collect::<Vec<_>>()
+            .join("|");
+        let timestamp = event.common().timestamp.clone();
+        let now = Instant::now();
+
+        let mut windows = self.lock();
+        let expired = match windows.open.get_mut(&key) {
+            Some(window) if now.duration_since(window.opened) < self.window => {
+                window.count += 1;
+                if timestamp.is_some() {
+                    window.last_seen = timestamp;
+                }
+                self.metrics.alert_aggregation("suppressed");
+                return false;
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            if let Some(rollup) = windows.open.remove(&key).and_then(|window| self.rollup(window)) {
+                windows.ready.push(rollup);
+            }
+        } else if windows.open.len() >= self.max_keys {
+            // 키가 너무 많으면 묶지 않고 그대로 전달
+            self.metrics.alert_aggregation("untracked");
+            return true;
+        }
+
+        windows.open.insert(key, AlertWindow {
+            opened: now,
+            count: 1,
+            first_seen: timestamp.clone(),
+            last_seen: timestamp,
+            template: self.template(event),
+        });
+        self.metrics.alert_aggregation("forwarded");
+        true
+    }
+
+    // 끝난 구간의 롤업 이벤트 (첫 경보만 있었던 구간은 제외)
+    pub fn flush(&self) -> Vec<EveEvent> {
+        let now = Instant::now();
+        let mut windows = self.lock();
+        let mut rollups = std::mem::take(&mut windows.ready);
+        let expired: Vec<AlertWindow> = windows.open
+            .extract_if(|_, window| now.duration_since(window.opened) >= self.window)
+            .map(|(_, window)| window)
+            .collect();
+        drop(windows);
+
+        rollups.extend(expired.into_iter().filter_map(|window| self.rollup(window)));
+        rollups
+    }
+
+    fn key_names(&self) -> Vec<String> {
+        self.fields.iter().map(|field| field.name().to_string()).collect()
+    }
+
+    // 키에 포함된 필드만 남긴 롤업 틀 (키가 아닌 필드는 경보마다 다를 수 있음)
+    fn template(&self, event: &EveEvent) -> OtherEvent {
+        let source = event.common();
+        let mut common = EveCommon::default();
+        let mut extra = HashMap::new();
+        let mut addresses = Vec::new();
+        for field in &self.fields {
+            match field {
+                KeyField::SignatureId => {
+                    if let Some(alert) = event.alert()
+                        && let Ok(value) = serde_json::to_value(alert) {
+                        extra.insert("alert".to_string(), value);
+                    }
+                }
+                KeyField::SrcIp => {
+                    common.src_ip = source.src_ip.clone();
+                    addresses.push("src_ip");
+                }
+                KeyField::DestIp => {
+                    common.dest_ip = source.dest_ip.clone();
+                    addresses.push("dest_ip");
+                }
+                KeyField::SrcPort => common.src_port = source.src_port,
+                KeyField::DestPort => common.dest_port = source.dest_port,
+                KeyField::Proto => common.proto = source.proto.clone(),
+                KeyField::AppProto => common.app_proto = source.app_proto.clone(),
+                KeyField::InIface => common.in_iface = source.in_iface.clone(),
+            }
+        }
+
+        // 키에 포함된 주소의 보강 정보만 유지
+        if let Some(Value::Object(enrichment)) = event.extra().get(ENRICHMENT_KEY) {
+            let enrichment: serde_json::Map<String, Value> = enrichment.iter()
+                .filter(|(address, _)| addresses.contains(&address.as_str()))
+                .map(|(address, info)| (address.clone(), info.clone()))
+                .collect();
+            if !enrichment.is_empty() {
+                extra.insert(ENRICHMENT_KEY.to_string(), Value::Object(enrichment));
+            }
+        }
+        OtherEvent { common, extra }
+    }
+
+    fn rollup(&self, window: AlertWindow) -> Option<EveEvent> {
+        if window.count < 2 {
+            return None;
+        }
+        let summary = AlertRollup {
+            count: window.count,
+            suppressed: window.count - 1,
+            first_seen: window.first_seen,
+            last_seen: window.last_seen.clone(),
+            window_secs: self.window.as_secs(),
+            key: self.key_names(),
+        };
+        let mut event = window.template;
+        event.common.timestamp = window.last_seen;
+        event.extra.insert("rollup".to_string(), serde_json::to_value(summary).ok()?);
+        self.metrics.alert_aggregation("rollup");
+        Some(EveEvent::Other(ROLLUP_EVENT_TYPE.to_string(), event))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Windows> {
+        self.windows.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample;
+
+    fn alert(src_ip: &str, src_port: u16, timestamp: &str) -> EveEvent {
+        let mut value = sample("alert");
+        value["src_ip"] = src_ip.into();
+        value["src_port"] = src_port.into();
+        value["timestamp"] = timestamp.into();
+        EveEvent::try_from(value).unwrap()
+    }
+
+    fn aggregator(fields: &[KeyField], max_keys: usize) -> AlertAggregator {
+        AlertAggregator {
+            metrics: Arc::new(Metrics::new()),
+            fields: fields.to_vec(),
+            window: Duration::from_secs(60),
+            max_keys,
+            windows: Mutex::new(Windows::default()),
+        }
+    }
+
+    // 열린 구간을 모두 끝난 것으로 만듦
+    fn expire(aggregator: &AlertAggregator) {
+        for window in aggregator.lock().open.values_mut() {
+            window.opened = window.opened.checked_sub(aggregator.window).unwrap();
+        }
+    }
+
+    const KEY: [KeyField; 3] = [KeyField::SignatureId, KeyField::SrcIp, KeyField::DestIp];
+
+    #[test]
+    fn repeated_alerts_are_rolled_up() {
+        let aggregator = aggregator(&KEY, 100);
+        assert!(aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:00.000000+0000")));
+        assert!(!aggregator.observe(&alert("10.0.0.5", 1001, "2024-05-14T10:00:10.000000+0000")));
+        assert!(!aggregator.observe(&alert("10.0.0.5", 1002, "2024-05-14T10:00:20.000000+0000")));
+        // 다른 키는 따로 묶임
+        assert!(aggregator.observe(&alert("10.0.0.6", 1000, "2024-05-14T10:00:30.000000+0000")));
+        // 경보가 아닌 이벤트는 그대로 통과
+        assert!(aggregator.observe(&EveEvent::try_from(sample("flow")).unwrap()));
+        assert!(aggregator.flush().is_empty());
+
+        expire(&aggregator);
+        let rollups = aggregator.flush();
+        assert_eq!(rollups.len(), 1);
+        let rollup = serde_json::to_value(&rollups[0]).unwrap();
+        assert_eq!(rollup["event_type"], ROLLUP_EVENT_TYPE);
+        assert_eq!(rollup["timestamp"], "2024-05-14T10:00:20.000000+0000");
+        assert_eq!(rollup["src_ip"], "10.0.0.5");
+        assert_eq!(rollup["alert"]["signature_id"], 2100498);
+        // 키가 아닌 필드는 남기지 않음
+        assert!(rollup.get("src_port").is_none());
+        assert_eq!(rollup["rollup"]["count"], 3);
+        assert_eq!(rollup["rollup"]["suppressed"], 2);
+        assert_eq!(rollup["rollup"]["first_seen"], "2024-05-14T10:00:00.000000+0000");
+        assert_eq!(rollup["rollup"]["key"], serde_json::json!(["signature_id", "src_ip", "dest_ip"]));
+        assert!(aggregator.lock().open.is_empty());
+    }
+
+    #[test]
+    fn alert_after_window_ends_opens_new_window() {
+        let aggregator = aggregator(&KEY, 100);
+        assert!(aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:00.000000+0000")));
+        assert!(!aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:10.000000+0000")));
+        expire(&aggregator);
+
+        // 끝난 구간의 롤업은 다음 flush로 미루고 새 구간의 첫 경보는 바로 전달
+        assert!(aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:01:30.000000+0000")));
+        let rollups = aggregator.flush();
+        assert_eq!(rollups.len(), 1);
+        assert_eq!(serde_json::to_value(&rollups[0]).unwrap()["rollup"]["count"], 2);
+        assert_eq!(aggregator.lock().open.len(), 1);
+    }
+
+    #[test]
+    fn too_many_keys_are_forwarded_untracked() {
+        let aggregator = aggregator(&[KeyField::SrcIp], 1);
+        assert!(aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:00.000000+0000")));
+        assert!(aggregator.observe(&alert("10.0.0.6", 1000, "2024-05-14T10:00:00.000000+0000")));
+        assert!(aggregator.observe(&alert("10.0.0.6", 1000, "2024-05-14T10:00:01.000000+0000")));
+        assert!(!aggregator.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:02.000000+0000")));
+    }
+
+    #[test]
+    fn disabled_without_window_or_key() {
+        let mut disabled = aggregator(&[], 100);
+        assert!(!disabled.is_enabled());
+        disabled.fields = KEY.to_vec();
+        disabled.window = Duration::ZERO;
+        assert!(!disabled.is_enabled());
+        for _ in 0..3 {
+            assert!(disabled.observe(&alert("10.0.0.5", 1000, "2024-05-14T10:00:00.000000+0000")));
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::Hash,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::models::alert_stats::{AlertStatsQuery, AlertStatsReport, SignatureCount, TimelinePoint, TopCount};
+use crate::models::eve_json_log::EveEvent;
+use crate::utils::config::env_or;
+use crate::utils::time::{now_secs, parse_query_time_ms, parse_timestamp_ms};
+
+// 기본/최대 상위 항목 개수
+const DEFAULT_TOP: usize = 10;
+const MAX_TOP: usize = 100;
+// 시그니처 이름 기록 최대 개수
+const MAX_SIGNATURE_NAMES: usize = 200_000;
+// 센서 시계 오차로 허용하는 미래 시각 (초), 이보다 앞선 경보는 버림
+const MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+// 버킷 단위와 보존 기간 (초)
+struct Granularity {
+    name: &'static str,
+    width: u64,
+    retention: u64,
+}
+
+const GRANULARITIES: [Granularity; 3] = [
+    Granularity { name: "1m", width: 60, retention: 6 * 3600 },
+    Granularity { name: "1h", width: 3600, retention: 7 * 86_400 },
+    Granularity { name: "1d", width: 86_400, retention: 90 * 86_400 },
+];
+
+#[derive(Debug)]
+pub enum AlertStatsError {
+    // 잘못된 조회 조건
+    Invalid(String),
+}
+
+impl AlertStatsError {
+    pub fn message(&self) -> &str {
+        match self {
+            AlertStatsError::Invalid(message) => message,
+        }
+    }
+}
+
+// 값별 개수 (한도를 넘은 새 값은 other로 셈)
+struct Counter<K> {
+    counts: HashMap<K, u64>,
+    other: u64,
+}
+
+impl<K> Default for Counter<K> {
+    fn default() -> Self {
+        Counter { counts: HashMap::new(), other: 0 }
+    }
+}
+
+impl<K: Eq + Hash + Clone> Counter<K> {
+    fn add(&mut self, key: K, max_keys: usize) {
+        if let Some(count) = self.counts.get_mut(&key) {
+            *count += 1;
+        } else if self.counts.len() < max_keys {
+            self.counts.insert(key, 1);
+        } else {
+            self.other += 1;
+        }
+    }
+
+    fn merge_into(&self, total: &mut HashMap<K, u64>, other: &mut u64) {
+        for (key, count) in &self.counts {
+            *total.entry(key.clone()).or_default() += count;
+        }
+        *other += self.other;
+    }
+}
+
+#[derive(Default)]
+struct Bucket {
+    alerts: u64,
+    severity: BTreeMap<u8, u64>,
+    categories: Counter<String>,
+    signatures: Counter<u64>,
+    src_ips: Counter<String>,
+    dest_ips: Counter<String>,
+    dest_ports: Counter<u16>,
+}
+
+#[derive(Default)]
+struct Rings {
+    // GRANULARITIES 순서, 키는 버킷 시작 시각
+    buckets: [BTreeMap<u64, Bucket>; 3],
+    signature_names: HashMap<u64, String>,
+}
+
+// 전달 경로를 지나는 경보를 분/시/일 버킷에 바로 누적해 두는 통계
+// 조회 시에는 요청 구간의 버킷만 합치므로 저장된 이벤트를 다시 읽지 않음
+pub struct AlertStats {
+    rings: Mutex<Rings>,
+    max_keys: usize,
+}
+
+impl AlertStats {
+    pub fn from_env() -> Self {
+        AlertStats {
+            rings: Mutex::new(Rings::default()),
+            max_keys: env_or("ALERT_STATS_MAX_KEYS", 200usize).max(1),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Rings> {
+        self.rings.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 경보 하나를 각 단위 버킷에 더함 (보존 기간보다 오래된 경보는 해당 단위에서 제외)
+    pub fn record(&self, event: &EveEvent) {
+        self.record_at(event, now_secs());
+    }
+
+    // 허용 오차 안의 미래 시각은 현재 시각으로 당기고, 더 먼 미래 경보는 버킷을 만들지 않도록 버림
+    fn record_at(&self, event: &EveEvent, now: u64) {
+        let Some(alert) = event.alert() else {
+            return;
+        };
+        let common = event.common();
+        let ts = common.timestamp.as_deref()
+            .and_then(parse_timestamp_ms)
+            .map(|ms| (ms.max(0) / 1000) as u64)
+            .unwrap_or(now);
+        if ts > now.saturating_add(MAX_CLOCK_SKEW_SECS) {
+            return;
+        }
+        let ts = ts.min(now);
+
+        let mut rings = self.lock();
+        if !rings.signature_names.contains_key(&alert.signature_id)
+            && rings.signature_names.len() < MAX_SIGNATURE_NAMES {
+            rings.signature_names.insert(alert.signature_id, alert.signature.clone());
+        }
+        for (granularity, ring) in GRANULARITIES.iter().zip(rings.buckets.iter_mut()) {
+            let oldest = now.saturating_sub(granularity.retention);
+            while let Some(entry) = ring.first_entry() && *entry.key() + granularity.width <= oldest {
+                entry.remove();
+            }
+            if ts + granularity.width <= oldest {
+                continue;
+            }
+
+            let bucket = ring.entry(ts - ts % granularity.width).or_default();
+            bucket.alerts += 1;
+            *bucket.severity.entry(alert.severity).or_default() += 1;
+            bucket.categories.add(alert.category.clone(), self.max_keys);
+            bucket.signatures.add(alert.signature_id, self.max_keys);
+            if let Some(src_ip) = &common.src_ip {
+                bucket.src_ips.add(src_ip.clone(), self.max_keys);
+            }
+            if let Some(dest_ip) = &common.dest_ip {
+                bucket.dest_ips.add(dest_ip.clone(), self.max_keys);
+            }
+            if let Some(dest_port) = common.dest_port {
+                bucket.dest_ports.add(dest_port, self.max_keys);
+            }
+        }
+    }
+
+    // 요청 구간의 버킷을 합쳐 통계 생성
+    pub fn report(&self, query: &AlertStatsQuery) -> Result<AlertStatsReport, AlertStatsError> {
+        let now = now_secs();
+        let parse = |value: &str| {
+            parse_query_time_ms(value)
+                .map(|ms| (ms.max(0) / 1000) as u64)
+                .ok_or_else(|| AlertStatsError::Invalid(format!("Invalid time '{}'", value)))
+        };
+        let to = query.to.as_deref().map(parse).transpose()?.unwrap_or(now);
+        let from = query.from.as_deref().map(parse).transpose()?.unwrap_or(to.saturating_sub(3600));
+        if from >= to {
+            return Err(AlertStatsError::Invalid("'from' must be before 'to'".to_string()));
+        }
+
+        // 지정하지 않으면 시작 시각을 보존하는 가장 작은 단위
+        let index = match query.bucket.as_deref() {
+            Some(name) => GRANULARITIES.iter().position(|granularity| granularity.name == name)
+                .ok_or_else(|| AlertStatsError::Invalid(format!("Unknown bucket '{}', expected 1m, 1h or 1d", name)))?,
+            None => GRANULARITIES.iter()
+                .position(|granularity| from + granularity.retention >= now)
+                .unwrap_or(GRANULARITIES.len() - 1),
+        };
+        let granularity = &GRANULARITIES[index];
+        let width = granularity.width;
+
+        // 버킷 경계에 맞추고 보존 기간과 현재 시각 안으로 제한
+        let oldest = now.saturating_sub(granularity.retention);
+        let start = from.max(oldest - oldest % width);
+        let start = start - start % width;
+        let end = to.min(now + 1).div_ceil(width) * width;
+        // 구간이 보존 기간보다 오래되었거나 미래면 빈 결과
+        let end = end.max(start);
+
+        let top = query.top.unwrap_or(DEFAULT_TOP).clamp(1, MAX_TOP);
+        let rings = self.lock();
+        let ring = &rings.buckets[index];
+
+        let mut total = 0;
+        let mut by_severity = BTreeMap::new();
+        let mut categories = HashMap::new();
+        let mut signatures = HashMap::new();
+        let mut src_ips = HashMap::new();
+        let mut dest_ips = HashMap::new();
+        let mut dest_ports = HashMap::new();
+        let mut others = [0u64; 5];
+
+        for (_, bucket) in ring.range(start..end) {
+            total += bucket.alerts;
+            for (severity, count) in &bucket.severity {
+                *by_severity.entry(*severity).or_default() += count;
+            }
+            bucket.categories.merge_into(&mut categories, &mut others[0]);
+            bucket.signatures.merge_into(&mut signatures, &mut others[1]);
+            bucket.src_ips.merge_into(&mut src_ips, &mut others[2]);
+            bucket.dest_ips.merge_into(&mut dest_ips, &mut others[3]);
+            bucket.dest_ports.merge_into(&mut dest_ports, &mut others[4]);
+        }
+        let other = ["categories", "signatures", "src_ips", "dest_ips", "dest_ports"]
+            .into_iter()
+            .zip(others)
+            .filter(|(_, count)| *count > 0)
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+
+        let timeline = (start..end)
+            .step_by(width as usize)
+            .map(|start| TimelinePoint {
+                start,
+                count: ring.get(&start).map(|bucket| bucket.alerts).unwrap_or(0),
+            })
+            .collect();
+        let top_signatures = top_counts(signatures, top)
+            .into_iter()
+            .map(|TopCount { key, count }| SignatureCount {
+                signature_id: key,
+                signature: rings.signature_names.get(&key).cloned().unwrap_or_default(),
+                count,
+            })
+            .collect();
+
+        Ok(AlertStatsReport {
+            from: start,
+            to: end,
+            bucket: granularity.name.to_string(),
+            total,
+            by_severity,
+            by_category: top_counts(categories, top),
+            top_signatures,
+            top_src_ips: top_counts(src_ips, top),
+            top_dest_ips: top_counts(dest_ips, top),
+            top_dest_ports: top_counts(dest_ports, top),
+            other,
+            timeline,
+        })
+    }
+}
+
+// 개수 내림차순 상위 n개 (같으면 값 오름차순)
+fn top_counts<K: Ord>(counts: HashMap<K, u64>, n: usize) -> Vec<TopCount<K>> {
+    let mut counts: Vec<TopCount<K>> = counts.into_iter()
+        .map(|(key, count)| TopCount { key, count })
+        .collect();
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    counts.truncate(n);
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alert(timestamp: Option<&str>) -> EveEvent {
+        let mut value = serde_json::json!({
+            "event_type": "alert",
+            "src_ip": "10.0.0.1",
+            "dest_ip": "10.0.0.2",
+            "dest_port": 443,
+            "alert": {"action": "allowed", "gid": 1, "signature_id": 2000, "rev": 1, "signature": "TEST", "category": "Cat", "severity": 2}
+        });
+        if let Some(timestamp) = timestamp {
+            value["timestamp"] = timestamp.into();
+        }
+        EveEvent::try_from(value).unwrap()
+    }
+
+    // 수신 시각으로 기록되는 경보 하나 (timestamp 없음)
+    fn stats_with_alert() -> AlertStats {
+        let stats = AlertStats::from_env();
+        stats.record(&alert(None));
+        stats
+    }
+
+    fn query(from: u64, to: u64) -> AlertStatsQuery {
+        AlertStatsQuery {
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+            ..AlertStatsQuery::default()
+        }
+    }
+
+    #[test]
+    fn range_older_than_retention_is_empty() {
+        let stats = stats_with_alert();
+        let report = stats.report(&query(0, 1)).unwrap();
+        assert_eq!(report.bucket, "1d");
+        assert_eq!(report.total, 0);
+        assert!(report.timeline.is_empty());
+    }
+
+    #[test]
+    fn range_in_the_future_is_empty() {
+        let stats = stats_with_alert();
+        let now = now_secs();
+        let report = stats.report(&query(now + 7200, now + 10_800)).unwrap();
+        assert_eq!(report.total, 0);
+        assert!(report.timeline.is_empty());
+    }
+
+    #[test]
+    fn recorded_alert_is_counted() {
+        let stats = stats_with_alert();
+        let now = now_secs();
+        let report = stats.report(&query(now - 60, now + 1)).unwrap();
+        assert_eq!(report.total, 1);
+        assert_eq!(report.by_severity.get(&2), Some(&1));
+        assert_eq!(report.top_signatures[0].signature, "TEST");
+        assert_eq!(report.top_dest_ports[0].key, 443);
+    }
+
+    #[test]
+    fn inverted_range_is_rejected() {
+        let stats = stats_with_alert();
+        assert!(stats.report(&query(200, 100)).is_err());
+    }
+
+    #[test]
+    fn future_timestamps_are_clamped_or_dropped() {
+        // 2024-05-14T10:12:33Z
+        let ts = 1_715_681_553;
+        let stats = AlertStats::from_env();
+
+        // 허용 오차 안: 현재 시각 버킷에 기록
+        let now = ts - 60;
+        stats.record_at(&alert(Some("2024-05-14T10:12:33Z")), now);
+        {
+            let rings = stats.lock();
+            let minutes = &rings.buckets[0];
+            assert_eq!(minutes.len(), 1);
+            assert_eq!(minutes.get(&(now - now % 60)).map(|bucket| bucket.alerts), Some(1));
+        }
+
+        // 허용 오차 밖: 어느 단위에도 버킷을 만들지 않음
+        stats.record_at(&alert(Some("2024-05-14T10:12:33Z")), ts - MAX_CLOCK_SKEW_SECS - 1);
+        stats.record_at(&alert(Some("9999-12-31T23:59:59Z")), now);
+        let rings = stats.lock();
+        for ring in &rings.buckets {
+            assert_eq!(ring.len(), 1);
+            assert_eq!(ring.values().map(|bucket| bucket.alerts).sum::<u64>(), 1);
+        }
+    }
+}
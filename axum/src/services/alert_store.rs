@@ -0,0 +1,611 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::Path,
+    sync::{
+        mpsc::{sync_channel, Receiver, SyncSender, TrySendError},
+        Arc, Mutex, MutexGuard,
+    },
+    time::Duration,
+};
+
+use rusqlite::{params, params_from_iter, types::Value as SqlValue, Connection};
+use serde_json::Value;
+use tracing::{error, info, warn};
+
+use crate::models::alert_store::{AlertPage, AlertQuery, StoredAlert};
+use crate::models::eve_json_log::EveEvent;
+use crate::utils::config::env_or;
+use crate::utils::time::{now_secs, parse_query_time_ms, parse_timestamp_ms};
+
+// 조회 한 페이지 기본/최대 개수
+const DEFAULT_PAGE_SIZE: usize = 100;
+const MAX_PAGE_SIZE: usize = 1000;
+// 보존 기간/크기 정리 주기
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+// 크기 초과 시 한 번에 지우는 최소 행 수
+const PRUNE_CHUNK_ROWS: i64 = 1000;
+// 저장 스레드가 밀렸을 때 쌓아 둘 최대 배치 수와 한 트랜잭션에 묶는 최대 행 수
+const WRITE_QUEUE_BATCHES: usize = 1024;
+const WRITE_BATCH_ROWS: usize = 10_000;
+// 정리와 저장이 동시에 쓰려고 할 때 기다리는 시간
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        ts INTEGER NOT NULL,
+        event_type TEXT NOT NULL,
+        signature_id INTEGER,
+        signature TEXT,
+        severity INTEGER,
+        category TEXT,
+        action TEXT,
+        src_ip TEXT,
+        src_port INTEGER,
+        dest_ip TEXT,
+        dest_port INTEGER,
+        proto TEXT,
+        app_proto TEXT,
+        event TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS events_ts ON events (ts);
+    CREATE INDEX IF NOT EXISTS events_signature_id ON events (signature_id, ts);
+    CREATE INDEX IF NOT EXISTS events_src_ip ON events (src_ip, ts);
+    CREATE INDEX IF NOT EXISTS events_dest_ip ON events (dest_ip, ts);
+";
+
+#[derive(Debug)]
+pub enum AlertStoreError {
+    // 저장소를 사용하지 않음
+    Disabled(String),
+    // 잘못된 조회 조건
+    Invalid(String),
+    // SQLite 오류
+    Database(String),
+}
+
+impl AlertStoreError {
+    pub fn message(&self) -> &str {
+        match self {
+            AlertStoreError::Disabled(message)
+            | AlertStoreError::Invalid(message)
+            | AlertStoreError::Database(message) => message,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for AlertStoreError {
+    fn from(e: rusqlite::Error) -> Self {
+        AlertStoreError::Database(format!("Alert store query failed: {}", e))
+    }
+}
+
+// 저장할 한 행 (전달 경로에서 이벤트를 변환해 둠)
+pub struct AlertRow {
+    ts: i64,
+    event_type: String,
+    signature_id: Option<i64>,
+    signature: Option<String>,
+    severity: Option<i64>,
+    category: Option<String>,
+    action: Option<String>,
+    src_ip: Option<String>,
+    src_port: Option<i64>,
+    dest_ip: Option<String>,
+    dest_port: Option<i64>,
+    proto: Option<String>,
+    app_proto: Option<String>,
+    event: String,
+}
+
+// 중앙 연결이 끊겨도 센서에서 바로 확인할 수 있도록 최근 경보와 선택한 이벤트를 SQLite에 보관
+// 보존 기간과 데이터 크기를 넘으면 오래된 것부터 삭제
+// 저장은 자체 연결을 가진 전용 스레드가 맡아 수신 경로가 조회/정리와 같은 잠금을 기다리지 않음
+pub struct AlertStore {
+    // 조회와 정리용 연결
+    connection: Option<Mutex<Connection>>,
+    writer: Option<SyncSender<Vec<AlertRow>>>,
+    event_types: HashSet<String>,
+    retention: Duration,
+    max_bytes: u64,
+}
+
+impl AlertStore {
+    pub fn from_env() -> Self {
+        let event_types = env_or("ALERT_STORE_EVENT_TYPES", "alert,anomaly,drop".to_string())
+            .split(',')
+            .map(|event_type| event_type.trim().to_string())
+            .filter(|event_type| !event_type.is_empty())
+            .collect();
+        AlertStore::new(
+            &env_or("ALERT_STORE_PATH", "/logs/alerts.db".to_string()),
+            event_types,
+            Duration::from_secs(env_or("ALERT_STORE_RETENTION_HOURS", 168u64) * 3600),
+            env_or("ALERT_STORE_MAX_MB", 256u64) * 1024 * 1024,
+        )
+    }
+
+    pub fn new(path: &str, event_types: HashSet<String>, retention: Duration, max_bytes: u64) -> Self {
+        let opened = if path.is_empty() {
+            info!("ALERT_STORE_PATH is empty, local alert store disabled");
+            None
+        } else {
+            match open(Path::new(path)).and_then(|connection| Ok((connection, spawn_writer(Path::new(path))?))) {
+                Ok(opened) => {
+                    info!("Storing recent alerts in {}", path);
+                    Some(opened)
+                }
+                Err(e) => {
+                    error!("Local alert store disabled, failed to open {}: {}", path, e);
+                    None
+                }
+            }
+        };
+        let (connection, writer) = opened.map_or((None, None), |(connection, writer)| {
+            (Some(Mutex::new(connection)), Some(writer))
+        });
+
+        AlertStore {
+            connection,
+            writer,
+            event_types,
+            retention,
+            max_bytes,
+        }
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, AlertStoreError> {
+        let connection = self.connection.as_ref()
+            .ok_or_else(|| AlertStoreError::Disabled("Local alert store is disabled".to_string()))?;
+        Ok(connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    }
+
+    // 보관 대상이면 저장할 행으로 변환
+    pub fn row(&self, event: &EveEvent) -> Option<AlertRow> {
+        if self.connection.is_none() || !self.event_types.contains(event.event_type()) {
+            return None;
+        }
+        let common = event.common();
+        let alert = event.alert();
+        let event_json = serde_json::to_string(event).ok()?;
+        Some(AlertRow {
+            ts: common.timestamp.as_deref()
+                .and_then(parse_timestamp_ms)
+                .unwrap_or_else(|| now_secs() as i64 * 1000),
+            event_type: event.event_type().to_string(),
+            signature_id: alert.map(|alert| alert.signature_id as i64),
+            signature: alert.map(|alert| alert.signature.clone()),
+            severity: alert.map(|alert| alert.severity as i64),
+            category: alert.map(|alert| alert.category.clone()),
+            action: alert.map(|alert| alert.action.clone()),
+            src_ip: common.src_ip.clone(),
+            src_port: common.src_port.map(i64::from),
+            dest_ip: common.dest_ip.clone(),
+            dest_port: common.dest_port.map(i64::from),
+            proto: common.proto.clone(),
+            app_proto: common.app_proto.clone(),
+            event: event_json,
+        })
+    }
+
+    // 저장 스레드로 넘기고 바로 반환 (밀려서 큐가 가득 차면 버림, 전달은 계속되므로 기록만 남김)
+    pub fn insert(&self, rows: Vec<AlertRow>) {
+        let Some(writer) = &self.writer else {
+            return;
+        };
+        if rows.is_empty() {
+            return;
+        }
+        match writer.try_send(rows) {
+            Ok(()) => {}
+            Err(TrySendError::Full(rows)) => {
+                warn!("Local alert store is falling behind, dropped {} events", rows.len());
+            }
+            Err(TrySendError::Disconnected(rows)) => {
+                warn!("Local alert store writer has stopped, dropped {} events", rows.len());
+            }
+        }
+    }
+
+    // 조건에 맞는 이벤트를 최신순으로 조회
+    pub fn query(&self, query: &AlertQuery) -> Result<AlertPage, AlertStoreError> {
+        let (filter, values) = build_filter(query)?;
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let connection = self.lock()?;
+
+        let total: i64 = connection.query_row(
+            &format!("SELECT COUNT(*) FROM events{}", filter),
+            params_from_iter(values.iter()),
+            |row| row.get(0),
+        )?;
+
+        let mut statement = connection.prepare(&format!(
+            "SELECT id, event FROM events{} ORDER BY ts DESC, id DESC LIMIT {} OFFSET {}",
+            filter, limit, query.offset,
+        ))?;
+        let alerts = statement
+            .query_map(params_from_iter(values.iter()), |row| {
+                let event: String = row.get(1)?;
+                Ok(StoredAlert {
+                    id: row.get(0)?,
+                    event: serde_json::from_str(&event).unwrap_or(Value::String(event)),
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(AlertPage {
+            total: total as u64,
+            offset: query.offset,
+            limit,
+            alerts,
+        })
+    }
+
+    // 보존 기간이 지난 행을 지우고, 데이터 크기가 한도를 넘으면 오래된 행부터 삭제
+    fn prune(&self) -> Result<(), AlertStoreError> {
+        let connection = self.lock()?;
+        let cutoff = (now_secs().saturating_sub(self.retention.as_secs()) * 1000) as i64;
+        let mut removed = connection.execute("DELETE FROM events WHERE ts < ?1", params![cutoff])?;
+
+        // 지운 행의 페이지는 재사용되므로 사용 중인 페이지 크기로 판단
+        loop {
+            let used: i64 = connection.query_row(
+                "SELECT (page_count - freelist_count) * page_size
+                 FROM pragma_page_count(), pragma_freelist_count(), pragma_page_size()",
+                [],
+                |row| row.get(0),
+            )?;
+            if used as u64 <= self.max_bytes {
+                break;
+            }
+            let rows: i64 = connection.query_row("SELECT COUNT(*) FROM events", [], |row| row.get(0))?;
+            if rows == 0 {
+                break;
+            }
+            removed += connection.execute(
+                "DELETE FROM events WHERE id IN (SELECT id FROM events ORDER BY ts, id LIMIT ?1)",
+                params![(rows / 10).max(PRUNE_CHUNK_ROWS)],
+            )?;
+        }
+        if removed > 0 {
+            info!("Pruned {} events from the local alert store", removed);
+        }
+        Ok(())
+    }
+}
+
+fn open(path: &Path) -> Result<Connection, String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let connection = Connection::open(path).map_err(|e| e.to_string())?;
+    connection
+        .execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")
+        .and_then(|_| connection.execute_batch(SCHEMA))
+        .and_then(|_| connection.busy_timeout(BUSY_TIMEOUT))
+        .map_err(|e| e.to_string())?;
+    Ok(connection)
+}
+
+// 자체 연결로 큐에 들어온 행을 저장하는 스레드 시작
+fn spawn_writer(path: &Path) -> Result<SyncSender<Vec<AlertRow>>, String> {
+    let mut connection = open(path)?;
+    let (sender, receiver) = sync_channel(WRITE_QUEUE_BATCHES);
+    std::thread::Builder::new()
+        .name("alert-store-writer".to_string())
+        .spawn(move || write_rows(&mut connection, receiver))
+        .map_err(|e| format!("Failed to start alert store writer: {}", e))?;
+    Ok(sender)
+}
+
+// 밀린 배치는 한 트랜잭션으로 묶어 저장
+fn write_rows(connection: &mut Connection, receiver: Receiver<Vec<AlertRow>>) {
+    while let Ok(mut rows) = receiver.recv() {
+        while rows.len() < WRITE_BATCH_ROWS
+            && let Ok(more) = receiver.try_recv() {
+            rows.extend(more);
+        }
+        if let Err(e) = insert_rows(connection, &rows) {
+            warn!("Failed to store {} events locally: {}", rows.len(), e);
+        }
+    }
+}
+
+fn insert_rows(connection: &mut Connection, rows: &[AlertRow]) -> rusqlite::Result<()> {
+    let transaction = connection.transaction()?;
+    {
+        let mut statement = transaction.prepare_cached(
+            "INSERT INTO events (ts, event_type, signature_id, signature, severity, category, action,
+                src_ip, src_port, dest_ip, dest_port, proto, app_proto, event)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        )?;
+        for row in rows {
+            statement.execute(params![
+                row.ts, row.event_type, row.signature_id, row.signature, row.severity, row.category,
+                row.action, row.src_ip, row.src_port, row.dest_ip, row.dest_port, row.proto,
+                row.app_proto, row.event,
+            ])?;
+        }
+    }
+    transaction.commit()
+}
+
+// 조회 조건을 WHERE 절과 바인딩 값으로 변환
+fn build_filter(query: &AlertQuery) -> Result<(String, Vec<SqlValue>), AlertStoreError> {
+    let mut clauses = Vec::new();
+    let mut values = Vec::new();
+    let mut add = |clause: &str, value: SqlValue| {
+        values.push(value);
+        clauses.push(clause.replace('?', &format!("?{}", values.len())));
+    };
+
+    if let Some(from) = &query.from {
+        add("ts >= ?", SqlValue::Integer(parse_time(from)?));
+    }
+    if let Some(to) = &query.to {
+        add("ts < ?", SqlValue::Integer(parse_time(to)?));
+    }
+    if let Some(event_type) = &query.event_type {
+        add("event_type = ?", SqlValue::Text(event_type.clone()));
+    }
+    if let Some(signature_id) = query.signature_id {
+        add("signature_id = ?", SqlValue::Integer(signature_id as i64));
+    }
+    if let Some(signature) = &query.signature {
+        let pattern = signature.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+        add("signature LIKE ? ESCAPE '\\'", SqlValue::Text(format!("%{}%", pattern)));
+    }
+    if let Some(severity) = query.severity {
+        add("severity = ?", SqlValue::Integer(severity as i64));
+    }
+    if let Some(ip) = &query.ip {
+        add("(src_ip = ? OR dest_ip = ?)", SqlValue::Text(ip.clone()));
+    }
+    if let Some(src_ip) = &query.src_ip {
+        add("src_ip = ?", SqlValue::Text(src_ip.clone()));
+    }
+    if let Some(dest_ip) = &query.dest_ip {
+        add("dest_ip = ?", SqlValue::Text(dest_ip.clone()));
+    }
+    if let Some(proto) = &query.proto {
+        add("proto = ? COLLATE NOCASE", SqlValue::Text(proto.clone()));
+    }
+    if let Some(action) = &query.action {
+        add("action = ?", SqlValue::Text(action.clone()));
+    }
+
+    let filter = if clauses.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", clauses.join(" AND "))
+    };
+    Ok((filter, values))
+}
+
+fn parse_time(value: &str) -> Result<i64, AlertStoreError> {
+    parse_query_time_ms(value)
+        .ok_or_else(|| AlertStoreError::Invalid(format!("Invalid time '{}'", value)))
+}
+
+// 주기적으로 보존 기간/크기 한도 적용
+pub fn spawn_alert_store_pruner(store: Arc<AlertStore>) {
+    if store.connection.is_none() {
+        return;
+    }
+    tokio::spawn(async move {
+        loop {
+            let pruning = Arc::clone(&store);
+            match tokio::task::spawn_blocking(move || pruning.prune()).await {
+                Ok(Err(e)) => warn!("Failed to prune local alert store: {}", e.message()),
+                Err(e) => warn!("Alert store pruning task failed: {}", e),
+                Ok(Ok(())) => {}
+            }
+            tokio::time::sleep(PRUNE_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::models::eve_json_log::test_support::{sample, sample_event};
+    use crate::utils::random::random_u64;
+
+    // 2024-05-14T10:12:33Z
+    const BASE_MS: i64 = 1_715_681_553_000;
+    const MINUTE_MS: i64 = 60_000;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("alert-store-test-{}-{}", std::process::id(), random_u64()))
+    }
+
+    fn store(dir: &Path, retention: Duration, max_bytes: u64) -> AlertStore {
+        let event_types = ["alert".to_string(), "drop".to_string()].into_iter().collect();
+        AlertStore::new(dir.join("alerts.db").to_str().unwrap(), event_types, retention, max_bytes)
+    }
+
+    // 예시 이벤트를 고쳐 저장할 행으로 (flow_id로 결과를 구분)
+    fn row(store: &AlertStore, event_type: &str, flow_id: u64, ts: i64, edit: impl FnOnce(&mut Value)) -> AlertRow {
+        let mut value = sample(event_type);
+        value["flow_id"] = flow_id.into();
+        edit(&mut value);
+        let mut row = store.row(&EveEvent::try_from(value).unwrap()).unwrap();
+        row.ts = ts;
+        row
+    }
+
+    fn wait_for_rows(store: &AlertStore, query: &AlertQuery, total: usize) -> AlertPage {
+        for _ in 0..100 {
+            let page = store.query(query).unwrap();
+            if page.total as usize >= total {
+                return page;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        panic!("writer did not store {} rows", total);
+    }
+
+    fn flow_ids(page: &AlertPage) -> Vec<u64> {
+        page.alerts.iter().map(|alert| alert.event["flow_id"].as_u64().unwrap()).collect()
+    }
+
+    fn used_bytes(store: &AlertStore) -> u64 {
+        store.lock().unwrap().query_row(
+            "SELECT (page_count - freelist_count) * page_size
+             FROM pragma_page_count(), pragma_freelist_count(), pragma_page_size()",
+            [],
+            |row| row.get::<_, i64>(0),
+        ).unwrap() as u64
+    }
+
+    #[test]
+    fn inserts_are_written_by_the_writer_thread() {
+        let dir = temp_dir();
+        let store = store(&dir, Duration::from_secs(3600), 1 << 20);
+
+        let alert = sample_event("alert");
+        let flow = sample_event("flow");
+        assert!(store.row(&flow).is_none());
+        store.insert(vec![store.row(&alert).unwrap()]);
+        store.insert(vec![store.row(&alert).unwrap(), store.row(&alert).unwrap()]);
+
+        let page = wait_for_rows(&store, &AlertQuery { signature_id: Some(2100498), ..Default::default() }, 3);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.alerts[0].event["src_ip"], "10.0.0.5");
+
+        let overflow = AlertQuery { from: Some(i64::MAX.to_string()), ..Default::default() };
+        assert!(matches!(store.query(&overflow), Err(AlertStoreError::Invalid(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn queries_filter_by_time_severity_signature_and_address() {
+        let dir = temp_dir();
+        let store = store(&dir, Duration::from_secs(3600), 1 << 20);
+        let alert = |flow_id: u64, severity: u8, signature_id: u64, signature: &str, src_ip: &str, dest_ip: &str| {
+            row(&store, "alert", flow_id, BASE_MS + flow_id as i64 * MINUTE_MS, |value| {
+                value["alert"]["severity"] = severity.into();
+                value["alert"]["signature_id"] = signature_id.into();
+                value["alert"]["signature"] = signature.into();
+                value["src_ip"] = src_ip.into();
+                value["dest_ip"] = dest_ip.into();
+            })
+        };
+        let mut udp = alert(1, 2, 2000, "ET MALWARE 100% Bad_Bot", "10.0.0.6", "10.0.0.5");
+        udp.proto = Some("UDP".to_string());
+        store.insert(vec![
+            alert(0, 1, 1000, "ET SCAN Nmap", "10.0.0.5", "192.0.2.1"),
+            udp,
+            alert(2, 2, 1000, "ET SCAN Nmap", "10.0.0.7", "192.0.2.1"),
+            alert(3, 3, 3000, "GPL ICMP ping", "192.0.2.1", "10.0.0.7"),
+            // 10.0.0.66 -> 10.0.0.5
+            row(&store, "drop", 4, BASE_MS + 4 * MINUTE_MS, |_| {}),
+        ]);
+        wait_for_rows(&store, &AlertQuery::default(), 5);
+
+        let secs = |minute: i64| Some(((BASE_MS + minute * MINUTE_MS) / 1000).to_string());
+        let cases = [
+            (AlertQuery::default(), vec![4, 3, 2, 1, 0]),
+            // 끝 시각은 포함하지 않음
+            (AlertQuery { from: secs(1), to: secs(3), ..Default::default() }, vec![2, 1]),
+            (AlertQuery { from: Some("2024-05-14T19:15:33+09:00".to_string()), ..Default::default() }, vec![4, 3]),
+            (AlertQuery { event_type: Some("drop".to_string()), ..Default::default() }, vec![4]),
+            (AlertQuery { severity: Some(2), ..Default::default() }, vec![2, 1]),
+            (AlertQuery { signature_id: Some(1000), ..Default::default() }, vec![2, 0]),
+            (AlertQuery { signature: Some("nmap".to_string()), ..Default::default() }, vec![2, 0]),
+            // %와 _는 와일드카드가 아님
+            (AlertQuery { signature: Some("100%".to_string()), ..Default::default() }, vec![1]),
+            (AlertQuery { signature: Some("d_b".to_string()), ..Default::default() }, vec![1]),
+            (AlertQuery { signature: Some("t_s".to_string()), ..Default::default() }, vec![]),
+            (AlertQuery { src_ip: Some("10.0.0.5".to_string()), ..Default::default() }, vec![0]),
+            (AlertQuery { dest_ip: Some("10.0.0.5".to_string()), ..Default::default() }, vec![4, 1]),
+            (AlertQuery { ip: Some("10.0.0.7".to_string()), ..Default::default() }, vec![3, 2]),
+            (AlertQuery { proto: Some("udp".to_string()), ..Default::default() }, vec![1]),
+            (AlertQuery { severity: Some(2), src_ip: Some("10.0.0.7".to_string()), ..Default::default() }, vec![2]),
+        ];
+        for (query, expected) in cases {
+            let page = store.query(&query).unwrap();
+            assert_eq!(flow_ids(&page), expected, "{:?}", query);
+            assert_eq!(page.total, expected.len() as u64, "{:?}", query);
+        }
+
+        let invalid = AlertQuery { to: Some("yesterday".to_string()), ..Default::default() };
+        assert!(matches!(store.query(&invalid), Err(AlertStoreError::Invalid(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn pages_are_newest_first_with_a_clamped_limit() {
+        let dir = temp_dir();
+        let store = store(&dir, Duration::from_secs(3600), 1 << 20);
+        // 같은 시각이면 나중에 저장한 것이 먼저
+        let rows = (0..25u64)
+            .map(|flow_id| row(&store, "alert", flow_id, BASE_MS + (flow_id as i64 / 2) * MINUTE_MS, |_| {}))
+            .collect();
+        store.insert(rows);
+        wait_for_rows(&store, &AlertQuery::default(), 25);
+
+        let page = |offset: usize, limit: Option<usize>| store.query(&AlertQuery { offset, limit, ..Default::default() }).unwrap();
+        let first = page(0, Some(10));
+        assert_eq!((first.total, first.offset, first.limit), (25, 0, 10));
+        assert_eq!(flow_ids(&first), (15..25).rev().collect::<Vec<_>>());
+        assert_eq!(flow_ids(&page(10, Some(10))), (5..15).rev().collect::<Vec<_>>());
+        let last = page(20, Some(10));
+        assert_eq!(flow_ids(&last), (0..5).rev().collect::<Vec<_>>());
+        assert_eq!(last.total, 25);
+        assert!(page(30, Some(10)).alerts.is_empty());
+
+        assert_eq!(page(0, None).limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(page(0, Some(0)).alerts.len(), 1);
+        assert_eq!(page(0, Some(MAX_PAGE_SIZE + 1)).limit, MAX_PAGE_SIZE);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_removes_events_past_the_retention() {
+        let dir = temp_dir();
+        let store = store(&dir, Duration::from_secs(3600), 1 << 30);
+        let now = now_secs() as i64 * 1000;
+        store.insert(vec![
+            row(&store, "alert", 0, now - 120 * MINUTE_MS, |_| {}),
+            row(&store, "alert", 1, now - 61 * MINUTE_MS, |_| {}),
+            row(&store, "alert", 2, now - 30 * MINUTE_MS, |_| {}),
+            row(&store, "alert", 3, now, |_| {}),
+        ]);
+        wait_for_rows(&store, &AlertQuery::default(), 4);
+
+        store.prune().unwrap();
+        assert_eq!(flow_ids(&store.query(&AlertQuery::default()).unwrap()), vec![3, 2]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prune_removes_the_oldest_events_over_the_size_limit() {
+        let dir = temp_dir();
+        let mut store = store(&dir, Duration::from_secs(3600), 1 << 30);
+        let now = now_secs() as i64 * 1000;
+        let rows = (0..5000u64)
+            .map(|flow_id| row(&store, "alert", flow_id, now - 5000 + flow_id as i64, |_| {}))
+            .collect();
+        store.insert(rows);
+        wait_for_rows(&store, &AlertQuery::default(), 5000);
+
+        // 한도 안이면 그대로
+        store.prune().unwrap();
+        assert_eq!(store.query(&AlertQuery::default()).unwrap().total, 5000);
+
+        store.max_bytes = used_bytes(&store) / 2;
+        store.prune().unwrap();
+        assert!(used_bytes(&store) <= store.max_bytes);
+        let page = store.query(&AlertQuery { limit: Some(MAX_PAGE_SIZE), ..Default::default() }).unwrap();
+        assert!(page.total > 0 && page.total < 5000, "{} rows left", page.total);
+        assert_eq!(flow_ids(&page)[0], 4999);
+        let oldest = store.query(&AlertQuery { offset: page.total as usize - 1, ..Default::default() }).unwrap();
+        assert_eq!(flow_ids(&oldest), vec![5000 - page.total]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,673 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use serde_json::Value;
+use tokio::sync::{OwnedSemaphorePermit, RwLock, Semaphore};
+use tracing::{error, info, warn};
+
+use crate::models::capture::{
+    CaptureInfo, CaptureRequest, CaptureSource, CaptureStatus, FlowCaptureRequest,
+};
+use crate::services::config::{af_packet_interfaces, scalar_string, ConfigManager};
+use crate::services::controller::SuricataController;
+use crate::utils::config::env_or;
+use crate::utils::time::now_secs;
+
+// BPF 필터 최대 길이
+const MAX_FILTER_LEN: usize = 512;
+// pcap 파일/레코드 헤더 크기
+const PCAP_HEADER_LEN: usize = 24;
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+
+// 캡처 실패 사유
+#[derive(Debug)]
+pub enum CaptureError {
+    // 잘못된 요청 (인터페이스, 필터, 범위)
+    Invalid(String),
+    // 대상 없음
+    NotFound(String),
+    // 동시 캡처 한도 초과
+    Busy(String),
+    // 파일 입출력 실패
+    Io(String),
+}
+
+impl CaptureError {
+    pub fn message(&self) -> &str {
+        match self {
+            CaptureError::Invalid(message)
+            | CaptureError::NotFound(message)
+            | CaptureError::Busy(message)
+            | CaptureError::Io(message) => message,
+        }
+    }
+}
+
+// 캡처 실행 및 보관 관리
+// 저장 디렉터리는 suricata 컨테이너와 같은 경로로 공유되어야 함 (suricata-log 볼륨)
+pub struct CaptureManager {
+    controller: Arc<dyn SuricataController>,
+    dir: PathBuf,
+    captures: RwLock<VecDeque<CaptureInfo>>,
+    permits: Arc<Semaphore>,
+    sequence: AtomicU64,
+    max_duration_secs: u64,
+    max_packets: u64,
+    snaplen: u64,
+    max_bytes: u64,
+    max_stored: usize,
+}
+
+impl CaptureManager {
+    pub fn from_env(controller: Arc<dyn SuricataController>) -> Self {
+        let log_dir = env_or("SURICATA_LOG_DIR", "/var/log/suricata".to_string());
+        CaptureManager {
+            controller,
+            dir: PathBuf::from(env_or("CAPTURE_DIR", format!("{}/captures", log_dir))),
+            captures: RwLock::new(VecDeque::new()),
+            permits: Arc::new(Semaphore::new(env_or("CAPTURE_MAX_CONCURRENT", 2usize).max(1))),
+            sequence: AtomicU64::new(0),
+            max_duration_secs: env_or("CAPTURE_MAX_DURATION_SECS", 60u64).max(1),
+            max_packets: env_or("CAPTURE_MAX_PACKETS", 10000u64).max(1),
+            snaplen: env_or("CAPTURE_SNAPLEN", 1518u64).clamp(64, 65535),
+            max_bytes: env_or("CAPTURE_MAX_BYTES", 20 * 1024 * 1024u64),
+            max_stored: env_or("CAPTURE_MAX_STORED", 20usize).max(1),
+        }
+    }
+
+    pub async fn list(&self) -> Vec<CaptureInfo> {
+        self.captures.read().await.iter().rev().cloned().collect()
+    }
+
+    pub async fn get(&self, id: &str) -> Result<CaptureInfo, CaptureError> {
+        self.captures.read().await
+            .iter()
+            .find(|capture| capture.id == id)
+            .cloned()
+            .ok_or_else(|| CaptureError::NotFound(format!("Capture {} not found", id)))
+    }
+
+    // 완료된 캡처의 pcap 파일 경로
+    pub async fn pcap_path(&self, id: &str) -> Result<PathBuf, CaptureError> {
+        let capture = self.get(id).await?;
+        if capture.status != CaptureStatus::Completed {
+            return Err(CaptureError::Invalid(format!("Capture {} is not completed", id)));
+        }
+        Ok(self.file_path(id))
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<(), CaptureError> {
+        let mut captures = self.captures.write().await;
+        let index = captures.iter()
+            .position(|capture| capture.id == id)
+            .ok_or_else(|| CaptureError::NotFound(format!("Capture {} not found", id)))?;
+        if captures[index].status == CaptureStatus::Running {
+            return Err(CaptureError::Invalid(format!("Capture {} is still running", id)));
+        }
+        captures.remove(index);
+        self.remove_file(id);
+        Ok(())
+    }
+
+    // 인터페이스에서 실시간 캡처 시작
+    pub async fn start_live(
+        self: &Arc<Self>,
+        config: &ConfigManager,
+        request: CaptureRequest,
+    ) -> Result<CaptureInfo, CaptureError> {
+        let monitored: Vec<String> = config.read_value()
+            .map(|value| af_packet_interfaces(&value))
+            .map_err(|e| CaptureError::Io(e.message().to_string()))?
+            .into_iter()
+            .map(|entry| entry.interface)
+            .filter(|name| name != "default")
+            .collect();
+        if !monitored.contains(&request.interface) {
+            return Err(CaptureError::Invalid(format!(
+                "Interface {} is not a monitored capture interface",
+                request.interface
+            )));
+        }
+
+        let filter = request.filter.trim().to_string();
+        validate_filter(&filter)?;
+        let duration = request.duration_secs.unwrap_or(10);
+        if duration == 0 || duration > self.max_duration_secs {
+            return Err(CaptureError::Invalid(format!(
+                "duration_secs must be between 1 and {}",
+                self.max_duration_secs
+            )));
+        }
+        let packets = self.packet_limit(request.packet_count)?;
+        self.compile_filter(&request.interface, &filter).await?;
+
+        let permit = self.acquire()?;
+        let info = self.register(CaptureSource::Live, Some(request.interface.clone()), filter.clone()).await?;
+
+        let manager = Arc::clone(self);
+        let id = info.id.clone();
+        tokio::spawn(async move {
+            let result = manager.run_live(&id, &request.interface, &filter, duration, packets).await;
+            manager.finish(&id, result).await;
+            drop(permit);
+        });
+        Ok(info)
+    }
+
+    // pcap-log 링 버퍼에서 플로우에 해당하는 패킷 추출
+    pub async fn start_flow(
+        self: &Arc<Self>,
+        config: &ConfigManager,
+        request: FlowCaptureRequest,
+    ) -> Result<CaptureInfo, CaptureError> {
+        let value = config.read_value().map_err(|e| CaptureError::Io(e.message().to_string()))?;
+        let (ring_dir, prefix) = pcap_log_location(&value)?;
+
+        let filter = flow_filter(&request)?;
+        let until = request.until.unwrap_or_else(now_secs);
+        let since = request.since.unwrap_or_else(|| until.saturating_sub(3600));
+        if since > until {
+            return Err(CaptureError::Invalid("since must not be later than until".to_string()));
+        }
+        let packets = self.packet_limit(request.packet_count)?;
+
+        let files = ring_files(&ring_dir, &prefix, since, until)?;
+        if files.is_empty() {
+            return Err(CaptureError::NotFound("No pcap-log files cover the requested time range".to_string()));
+        }
+
+        let permit = self.acquire()?;
+        let info = self.register(CaptureSource::PcapLog, None, filter.clone()).await?;
+
+        let manager = Arc::clone(self);
+        let id = info.id.clone();
+        tokio::spawn(async move {
+            let result = manager.run_flow(&id, &files, &filter, packets).await;
+            manager.finish(&id, result).await;
+            drop(permit);
+        });
+        Ok(info)
+    }
+
+    fn file_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.pcap", id))
+    }
+
+    fn remove_file(&self, id: &str) {
+        let path = self.file_path(id);
+        if let Err(e) = fs::remove_file(&path)
+            && e.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to remove capture {}: {}", path.display(), e);
+        }
+    }
+
+    // 요청 패킷 수를 한도와 최대 크기 안으로 제한
+    fn packet_limit(&self, requested: Option<u64>) -> Result<u64, CaptureError> {
+        let by_size = (self.max_bytes / (self.snaplen + PCAP_RECORD_HEADER_LEN as u64)).max(1);
+        let limit = self.max_packets.min(by_size);
+        let packets = requested.unwrap_or(limit);
+        if packets == 0 || packets > limit {
+            return Err(CaptureError::Invalid(format!("packet_count must be between 1 and {}", limit)));
+        }
+        Ok(packets)
+    }
+
+    fn acquire(&self) -> Result<OwnedSemaphorePermit, CaptureError> {
+        Arc::clone(&self.permits)
+            .try_acquire_owned()
+            .map_err(|_| CaptureError::Busy("Too many captures in progress, try again later".to_string()))
+    }
+
+    // 새 캡처 등록 (보관 한도를 넘으면 오래된 완료 캡처부터 삭제)
+    async fn register(
+        &self,
+        source: CaptureSource,
+        interface: Option<String>,
+        filter: String,
+    ) -> Result<CaptureInfo, CaptureError> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| CaptureError::Io(format!("Failed to create {}: {}", self.dir.display(), e)))?;
+
+        let created_at = now_secs();
+        let info = CaptureInfo {
+            id: format!("{}-{}", created_at, self.sequence.fetch_add(1, Ordering::Relaxed)),
+            source,
+            interface,
+            filter,
+            status: CaptureStatus::Running,
+            created_at,
+            finished_at: None,
+            size: None,
+            error: None,
+        };
+
+        let mut captures = self.captures.write().await;
+        while captures.len() >= self.max_stored {
+            let Some(index) = captures.iter().position(|capture| capture.status != CaptureStatus::Running) else {
+                break;
+            };
+            if let Some(evicted) = captures.remove(index) {
+                self.remove_file(&evicted.id);
+            }
+        }
+        captures.push_back(info.clone());
+        Ok(info)
+    }
+
+    async fn finish(&self, id: &str, result: Result<(), String>) {
+        let size = fs::metadata(self.file_path(id)).map(|metadata| metadata.len()).ok();
+        let mut captures = self.captures.write().await;
+        let Some(capture) = captures.iter_mut().find(|capture| capture.id == id) else {
+            return;
+        };
+        capture.finished_at = Some(now_secs());
+        match result {
+            Ok(()) => {
+                info!("Capture {} completed ({} bytes)", id, size.unwrap_or(0));
+                capture.status = CaptureStatus::Completed;
+                capture.size = size;
+            }
+            Err(e) => {
+                error!("Capture {} failed: {}", id, e);
+                capture.status = CaptureStatus::Failed;
+                capture.error = Some(e);
+                self.remove_file(id);
+            }
+        }
+    }
+
+    async fn run_live(
+        &self,
+        id: &str,
+        interface: &str,
+        filter: &str,
+        duration: u64,
+        packets: u64,
+    ) -> Result<(), String> {
+        let output = self.file_path(id).to_string_lossy().to_string();
+        let duration = duration.to_string();
+        let packets = packets.to_string();
+        let snaplen = self.snaplen.to_string();
+
+        let mut args = vec![
+            "timeout", "-s", "INT", duration.as_str(),
+            "tcpdump", "-n", "-i", interface, "-c", packets.as_str(), "-s", snaplen.as_str(), "-w", output.as_str(),
+        ];
+        if !filter.is_empty() {
+            args.push(filter);
+        }
+
+        let result = self.controller.exec(&args).await?;
+        // timeout으로 종료되면 124
+        match result.code {
+            Some(0) | Some(124) => Ok(()),
+            _ => Err(result.stderr.trim().to_string()),
+        }
+    }
+
+    // tcpdump -d로 필터 컴파일 확인
+    async fn compile_filter(&self, interface: &str, filter: &str) -> Result<(), CaptureError> {
+        if filter.is_empty() {
+            return Ok(());
+        }
+        let output = self.controller.exec(&["tcpdump", "-d", "-i", interface, filter])
+            .await
+            .map_err(CaptureError::Io)?;
+        if !output.success() {
+            return Err(CaptureError::Invalid(format!("Invalid BPF filter: {}", output.stderr.trim())));
+        }
+        Ok(())
+    }
+
+    // 링 파일마다 필터를 적용한 뒤 하나의 pcap으로 병합
+    async fn run_flow(&self, id: &str, files: &[PathBuf], filter: &str, packets: u64) -> Result<(), String> {
+        let mut parts = Vec::new();
+        let mut result = Ok(());
+        for (index, file) in files.iter().enumerate() {
+            let part = self.dir.join(format!("{}.part{}.pcap", id, index));
+            let input = file.to_string_lossy().to_string();
+            let output = part.to_string_lossy().to_string();
+            let count = packets.to_string();
+            let args = [
+                "tcpdump", "-n", "-r", input.as_str(), "-c", count.as_str(), "-w", output.as_str(), filter,
+            ];
+            match self.controller.exec(&args).await {
+                Ok(output) if output.success() => parts.push(part),
+                Ok(output) => {
+                    result = Err(output.stderr.trim().to_string());
+                    parts.push(part);
+                    break;
+                }
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+
+        if result.is_ok() {
+            result = merge_pcaps(&parts, &self.file_path(id), packets, self.max_bytes);
+        }
+        for part in &parts {
+            let _ = fs::remove_file(part);
+        }
+        result
+    }
+}
+
+// 필터 문자열 검증 (옵션 주입과 셸 메타문자 차단)
+fn validate_filter(filter: &str) -> Result<(), CaptureError> {
+    if filter.len() > MAX_FILTER_LEN {
+        return Err(CaptureError::Invalid(format!("filter must be at most {} characters", MAX_FILTER_LEN)));
+    }
+    if let Some(c) = filter.chars().find(|c| {
+        !(c.is_ascii_alphanumeric() || " .:/()!&|<>=[]_-+*".contains(*c))
+    }) {
+        return Err(CaptureError::Invalid(format!("filter contains invalid character: {:?}", c)));
+    }
+    if filter.split_whitespace().any(|token| token.starts_with('-')) {
+        return Err(CaptureError::Invalid("filter tokens must not start with '-'".to_string()));
+    }
+    Ok(())
+}
+
+// 5-tuple로 양방향 BPF 필터 생성
+fn flow_filter(request: &FlowCaptureRequest) -> Result<String, CaptureError> {
+    let src: IpAddr = request.src_ip.parse()
+        .map_err(|_| CaptureError::Invalid(format!("Invalid src_ip: {}", request.src_ip)))?;
+    let dest: IpAddr = request.dest_ip.parse()
+        .map_err(|_| CaptureError::Invalid(format!("Invalid dest_ip: {}", request.dest_ip)))?;
+
+    let mut terms = Vec::new();
+    if let Some(proto) = &request.proto {
+        let proto = proto.to_ascii_lowercase();
+        if !matches!(proto.as_str(), "tcp" | "udp" | "icmp" | "icmp6" | "sctp") {
+            return Err(CaptureError::Invalid(format!("Unsupported proto: {}", proto)));
+        }
+        terms.push(proto);
+    }
+    terms.push(format!("host {}", src));
+    terms.push(format!("host {}", dest));
+    for port in [request.src_port, request.dest_port].into_iter().flatten() {
+        terms.push(format!("port {}", port));
+    }
+    Ok(terms.join(" and "))
+}
+
+// pcap-log 출력 위치 (디렉터리, 파일 접두사)
+fn pcap_log_location(config: &Value) -> Result<(PathBuf, String), CaptureError> {
+    let pcap_log = config.get("outputs")
+        .and_then(Value::as_array)
+        .and_then(|outputs| outputs.iter().find_map(|output| output.get("pcap-log")))
+        .ok_or_else(|| CaptureError::NotFound("pcap-log output is not configured".to_string()))?;
+
+    let field = |key: &str| pcap_log.get(key).and_then(scalar_string);
+    if !matches!(field("enabled").as_deref(), Some("yes" | "true")) {
+        return Err(CaptureError::Invalid("pcap-log output is not enabled".to_string()));
+    }
+    if field("mode").is_some_and(|mode| mode != "normal") {
+        return Err(CaptureError::Invalid("Only pcap-log mode normal is supported".to_string()));
+    }
+
+    let dir = field("dir")
+        .or_else(|| config.get("default-log-dir").and_then(scalar_string))
+        .unwrap_or_else(|| env_or("SURICATA_LOG_DIR", "/var/log/suricata".to_string()));
+    let prefix = field("filename").unwrap_or_else(|| "log.pcap".to_string());
+    Ok((PathBuf::from(dir), prefix))
+}
+
+// 요청 구간과 겹치는 링 파일 (파일명 접미사가 시작 시각)
+fn ring_files(dir: &Path, prefix: &str, since: u64, until: u64) -> Result<Vec<PathBuf>, CaptureError> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| CaptureError::Io(format!("Failed to read {}: {}", dir.display(), e)))?;
+
+    let mut files: Vec<(u64, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let started = name.strip_prefix(prefix)?
+                .strip_prefix('.')?
+                .split('.')
+                .next()?
+                .parse()
+                .ok()?;
+            Some((started, entry.path()))
+        })
+        .collect();
+    files.sort();
+
+    let ends: Vec<u64> = files.iter().skip(1).map(|(started, _)| *started).chain([u64::MAX]).collect();
+    Ok(files.into_iter()
+        .zip(ends)
+        .filter(|((started, _), end)| *started <= until && *end >= since)
+        .map(|((_, path), _)| path)
+        .collect())
+}
+
+// pcap 파일 병합 (첫 파일 헤더 유지, 패킷 수와 크기 한도 적용)
+fn merge_pcaps(parts: &[PathBuf], output: &Path, max_packets: u64, max_bytes: u64) -> Result<(), String> {
+    let mut merged: Vec<u8> = Vec::new();
+    let mut packets = 0u64;
+
+    'parts: for part in parts {
+        let data = fs::read(part).map_err(|e| format!("Failed to read {}: {}", part.display(), e))?;
+        if data.len() < PCAP_HEADER_LEN {
+            continue;
+        }
+        let little_endian = match data[..4] {
+            [0xd4, 0xc3, 0xb2, 0xa1] | [0x4d, 0x3c, 0xb2, 0xa1] => true,
+            [0xa1, 0xb2, 0xc3, 0xd4] | [0xa1, 0xb2, 0x3c, 0x4d] => false,
+            _ => return Err(format!("{} is not a pcap file", part.display())),
+        };
+        if merged.is_empty() {
+            merged.extend_from_slice(&data[..PCAP_HEADER_LEN]);
+        }
+
+        let mut offset = PCAP_HEADER_LEN;
+        while offset + PCAP_RECORD_HEADER_LEN <= data.len() {
+            let len_bytes = [data[offset + 8], data[offset + 9], data[offset + 10], data[offset + 11]];
+            let captured = if little_endian {
+                u32::from_le_bytes(len_bytes)
+            } else {
+                u32::from_be_bytes(len_bytes)
+            } as usize;
+            let end = offset + PCAP_RECORD_HEADER_LEN + captured;
+            if end > data.len() {
+                break;
+            }
+            if packets >= max_packets || (merged.len() + end - offset) as u64 > max_bytes {
+                break 'parts;
+            }
+            merged.extend_from_slice(&data[offset..end]);
+            packets += 1;
+            offset = end;
+        }
+    }
+
+    if packets == 0 {
+        return Err("No packets matched the flow".to_string());
+    }
+    fs::write(output, merged).map_err(|e| format!("Failed to write {}: {}", output.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::random_u64;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("capture-test-{}-{}", std::process::id(), random_u64()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // 레코드마다 captured 길이만큼 같은 바이트로 채운 pcap
+    fn pcap(little_endian: bool, records: &[(u8, u32)]) -> Vec<u8> {
+        let u32_bytes = |value: u32| if little_endian { value.to_le_bytes() } else { value.to_be_bytes() };
+        let mut data = u32_bytes(0xa1b2c3d4).to_vec();
+        data.extend_from_slice(&[0; PCAP_HEADER_LEN - 4]);
+        for (fill, captured) in records {
+            data.extend_from_slice(&[0; 8]);
+            data.extend_from_slice(&u32_bytes(*captured));
+            data.extend_from_slice(&u32_bytes(*captured));
+            data.extend(std::iter::repeat_n(*fill, *captured as usize));
+        }
+        data
+    }
+
+    fn write_parts(dir: &Path, parts: &[Vec<u8>]) -> Vec<PathBuf> {
+        parts.iter().enumerate().map(|(index, data)| {
+            let path = dir.join(format!("part{}.pcap", index));
+            fs::write(&path, data).unwrap();
+            path
+        }).collect()
+    }
+
+    // 병합 결과의 레코드별 채움 바이트
+    fn merged_records(data: &[u8]) -> Vec<u8> {
+        let mut fills = Vec::new();
+        let mut offset = PCAP_HEADER_LEN;
+        while offset < data.len() {
+            let captured = u32::from_le_bytes(data[offset + 8..offset + 12].try_into().unwrap()) as usize;
+            fills.push(data[offset + PCAP_RECORD_HEADER_LEN]);
+            offset += PCAP_RECORD_HEADER_LEN + captured;
+        }
+        assert_eq!(offset, data.len());
+        fills
+    }
+
+    fn flow(src_port: Option<u16>, dest_port: Option<u16>, proto: Option<&str>) -> FlowCaptureRequest {
+        FlowCaptureRequest {
+            src_ip: "10.0.0.1".to_string(),
+            dest_ip: "2001:db8::2".to_string(),
+            src_port,
+            dest_port,
+            proto: proto.map(str::to_string),
+            since: None,
+            until: None,
+            packet_count: None,
+        }
+    }
+
+    #[test]
+    fn filters_are_validated() {
+        let valid = [
+            "",
+            "tcp port 443",
+            "host 10.0.0.1 and (port 80 or port 8080)",
+            "not port 22 && len > 100",
+            "ip6 and net fe80::/10",
+            "tcp[13] & 2 != 0",
+        ];
+        for filter in valid {
+            assert!(validate_filter(filter).is_ok(), "{}", filter);
+        }
+
+        let long = "port 80 or ".repeat(MAX_FILTER_LEN / 11 + 1);
+        let invalid = [
+            "-i eth1",
+            "port 80 --version",
+            "port 80; rm -rf /",
+            "port $PORT",
+            "port `id`",
+            "host 'a'",
+            "host \"a\"",
+            "port 80\nport 81",
+            "port 80 \\",
+            "port {80}",
+            long.as_str(),
+        ];
+        for filter in invalid {
+            assert!(matches!(validate_filter(filter), Err(CaptureError::Invalid(_))), "{}", filter);
+        }
+        assert!(validate_filter(&"a".repeat(MAX_FILTER_LEN)).is_ok());
+    }
+
+    #[test]
+    fn flow_filters_match_both_directions() {
+        let cases = [
+            (flow(None, None, None), "host 10.0.0.1 and host 2001:db8::2"),
+            (flow(Some(51000), Some(443), Some("TCP")), "tcp and host 10.0.0.1 and host 2001:db8::2 and port 51000 and port 443"),
+            (flow(None, Some(53), Some("udp")), "udp and host 10.0.0.1 and host 2001:db8::2 and port 53"),
+        ];
+        for (request, expected) in cases {
+            let filter = flow_filter(&request).unwrap();
+            assert_eq!(filter, expected);
+            assert!(validate_filter(&filter).is_ok());
+        }
+
+        assert!(flow_filter(&flow(None, None, Some("gre"))).is_err());
+        let mut request = flow(None, None, None);
+        request.src_ip = "10.0.0.1 or port 22".to_string();
+        assert!(flow_filter(&request).is_err());
+    }
+
+    #[test]
+    fn merge_stops_at_packet_limit() {
+        let dir = temp_dir();
+        let parts = write_parts(&dir, &[
+            pcap(true, &[(1, 10), (2, 10)]),
+            pcap(true, &[(3, 10), (4, 10)]),
+        ]);
+        let output = dir.join("merged.pcap");
+
+        merge_pcaps(&parts, &output, 3, u64::MAX).unwrap();
+        let merged = fs::read(&output).unwrap();
+        // 헤더는 첫 파일 것 하나만
+        assert_eq!(merged[..PCAP_HEADER_LEN], fs::read(&parts[0]).unwrap()[..PCAP_HEADER_LEN]);
+        assert_eq!(merged_records(&merged), vec![1, 2, 3]);
+
+        merge_pcaps(&parts, &output, 100, u64::MAX).unwrap();
+        assert_eq!(merged_records(&fs::read(&output).unwrap()), vec![1, 2, 3, 4]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_stops_at_byte_limit() {
+        let dir = temp_dir();
+        let parts = write_parts(&dir, &[pcap(true, &[(1, 100), (2, 100), (3, 100)])]);
+        let output = dir.join("merged.pcap");
+
+        // 헤더 + 레코드 두 개까지만 들어가는 크기
+        let limit = (PCAP_HEADER_LEN + 2 * (PCAP_RECORD_HEADER_LEN + 100)) as u64;
+        merge_pcaps(&parts, &output, 100, limit + 50).unwrap();
+        let merged = fs::read(&output).unwrap();
+        assert_eq!(merged.len() as u64, limit);
+        assert_eq!(merged_records(&merged), vec![1, 2]);
+
+        // 첫 패킷도 들어가지 않으면 실패
+        assert!(merge_pcaps(&parts, &output, 100, 50).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn merge_skips_truncated_records_and_rejects_other_files() {
+        let dir = temp_dir();
+        let mut truncated = pcap(false, &[(1, 10), (2, 10)]);
+        truncated.truncate(truncated.len() - 5);
+        let parts = write_parts(&dir, &[truncated, vec![0; 10], pcap(false, &[(3, 10)])]);
+        let output = dir.join("merged.pcap");
+
+        merge_pcaps(&parts, &output, 100, u64::MAX).unwrap();
+        let merged = fs::read(&output).unwrap();
+        assert_eq!(merged.len(), PCAP_HEADER_LEN + 2 * (PCAP_RECORD_HEADER_LEN + 10));
+        assert_eq!(merged[PCAP_HEADER_LEN + PCAP_RECORD_HEADER_LEN], 1);
+        assert_eq!(merged[merged.len() - 1], 3);
+
+        let empty = write_parts(&dir, &[pcap(true, &[])]);
+        assert_eq!(merge_pcaps(&empty, &output, 100, u64::MAX).unwrap_err(), "No packets matched the flow");
+
+        let text = dir.join("notes.txt");
+        fs::write(&text, "this is not a pcap file at all").unwrap();
+        assert!(merge_pcaps(&[text], &output, 100, u64::MAX).unwrap_err().contains("is not a pcap file"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
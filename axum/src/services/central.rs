@@ -0,0 +1,125 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use reqwest::{header::CONTENT_TYPE, StatusCode};
+use tracing::{error, warn};
+
+use crate::services::metrics::Metrics;
+
+// 중앙 API로 EVE 배치를 보내는 HTTP 전송기 (스풀, 재시도 순서는 Forwarder가 관리)
+pub struct CentralApi {
+    client: reqwest::Client,
+    url: String,
+    metrics: Arc<Metrics>,
+}
+
+impl CentralApi {
+    pub fn new(url: String, timeout: Duration, metrics: Arc<Metrics>) -> Self {
+        CentralApi {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .unwrap_or_default(),
+            url,
+            metrics,
+        }
+    }
+
+    // 배치 하나 전송 (배치 크기가 1이면 기존처럼 단일 객체로 전송)
+    // 배치가 재시도해도 소용없는 4xx로 거부되면 이벤트마다 따로 보내 거부된 이벤트만 버림
+    pub async fn send_batch(&self, lines: &[String]) -> Result<(), String> {
+        let body = if lines.len() == 1 {
+            lines[0].clone()
+        } else {
+            format!("[{}]", lines.join(","))
+        };
+        match self.post(body).await {
+            Ok(None) => {
+                self.metrics.forward_batch("success");
+                for line in lines {
+                    self.metrics.eve_forwarded(&event_type(line));
+                }
+                Ok(())
+            }
+            Ok(Some(status)) if lines.len() > 1 => {
+                warn!("Central API rejected a batch of {} events ({}), sending them one by one", lines.len(), status);
+                let mut rejected = 0;
+                for line in lines {
+                    match self.post(line.clone()).await {
+                        Ok(None) => self.metrics.eve_forwarded(&event_type(line)),
+                        Ok(Some(_)) => {
+                            rejected += 1;
+                            self.metrics.eve_failed(&event_type(line));
+                        }
+                        // 앞서 받아들여진 이벤트는 재시도 때 다시 전송될 수 있음
+                        Err(e) => {
+                            self.metrics.forward_batch("failure");
+                            return Err(e);
+                        }
+                    }
+                }
+                if rejected > 0 {
+                    warn!("Dropped {} of {} events rejected by central API", rejected, lines.len());
+                }
+                self.metrics.forward_batch(if rejected == lines.len() { "rejected" } else { "success" });
+                Ok(())
+            }
+            Ok(Some(status)) => {
+                self.metrics.forward_batch("rejected");
+                for line in lines {
+                    self.metrics.eve_failed(&event_type(line));
+                }
+                warn!("Dropped batch of {} events rejected by central API ({})", lines.len(), status);
+                Ok(())
+            }
+            Err(e) => {
+                self.metrics.forward_batch("failure");
+                Err(e)
+            }
+        }
+    }
+
+    // 요청 하나 전송, 재시도해도 받아들여지지 않을 응답이면 그 상태 코드
+    async fn post(&self, body: String) -> Result<Option<StatusCode>, String> {
+        let started = Instant::now();
+        let result = self.client.post(&self.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .await;
+        let status = match result {
+            Ok(response) => response.status(),
+            Err(e) => {
+                error!("외부 API 호출 실패: {}", e);
+                self.metrics.central_api_request("error", started.elapsed().as_secs_f64());
+                return Err(e.to_string());
+            }
+        };
+        if status.is_success() {
+            self.metrics.central_api_request("success", started.elapsed().as_secs_f64());
+            return Ok(None);
+        }
+        warn!("외부 API 오류 응답: {}", status);
+        self.metrics.central_api_request("http_error", started.elapsed().as_secs_f64());
+        if status.is_client_error()
+            && status != StatusCode::REQUEST_TIMEOUT
+            && status != StatusCode::TOO_MANY_REQUESTS {
+            Ok(Some(status))
+        } else {
+            Err(format!("Central API responded with {}", status))
+        }
+    }
+}
+
+fn event_type(line: &str) -> String {
+    #[derive(serde::Deserialize)]
+    struct EventType {
+        event_type: Option<String>,
+    }
+    serde_json::from_str::<EventType>(line)
+        .ok()
+        .and_then(|event| event.event_type)
+        .unwrap_or_else(|| "unknown".to_string())
+}
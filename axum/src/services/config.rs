@@ -0,0 +1,408 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde_json::Value;
+use tokio::sync::{Mutex, MutexGuard};
+use tracing::{info, warn};
+
+use crate::models::config::{
+    AfPacketInterface, ConfigPatch, EveLogSection, SuricataConfigView, VarGroup, VarsSection,
+};
+use crate::services::controller::SuricataController;
+use crate::utils::config::env_or;
+use crate::utils::suricata::{
+    get_env, references_var, validate_address_group, validate_port_group,
+};
+use crate::utils::yaml::{quote_scalar, remove_key, set_value};
+
+// API로 변경을 허용하는 경로 접두사
+const PATCHABLE_PREFIXES: &[&str] = &[
+    "vars.address-groups.",
+    "vars.port-groups.",
+    "af-packet[",
+    "app-layer.protocols.",
+    "outputs.eve-log.",
+    "stats.",
+];
+
+// 설정 변경 실패 사유
+#[derive(Debug)]
+pub enum ConfigError {
+    // 잘못된 요청 (경로, 값)
+    Invalid(String),
+    // 대상 없음
+    NotFound(String),
+    // 다른 설정이나 규칙이 참조 중
+    Conflict(String),
+    // suricata -T 검증 실패
+    TestFailed(String),
+    // 파일 입출력 실패
+    Io(String),
+}
+
+impl ConfigError {
+    pub fn message(&self) -> &str {
+        match self {
+            ConfigError::Invalid(message)
+            | ConfigError::NotFound(message)
+            | ConfigError::Conflict(message)
+            | ConfigError::TestFailed(message)
+            | ConfigError::Io(message) => message,
+        }
+    }
+}
+
+// 편집부터 재시작, 복구까지 다른 변경이 끼어들지 않도록 잡아 두는 설정 잠금
+pub struct ConfigLock<'a> {
+    _guard: MutexGuard<'a, ()>,
+}
+
+// suricata.yaml 읽기/검증/적용 관리
+pub struct ConfigManager {
+    // 에이전트에서 보이는 경로
+    path: PathBuf,
+    // suricata 컨테이너에서 보이는 경로
+    engine_path: String,
+    // 동시 수정 방지
+    lock: Mutex<()>,
+    controller: Arc<dyn SuricataController>,
+}
+
+impl ConfigManager {
+    pub fn from_env(controller: Arc<dyn SuricataController>) -> Self {
+        let path = env_or("SURICATA_CONFIG_FILE", "/etc/suricata/suricata.yaml".to_string());
+        let engine_path = env_or("SURICATA_ENGINE_CONFIG_FILE", path.clone());
+        ConfigManager::new(PathBuf::from(path), engine_path, controller)
+    }
+
+    pub fn new(path: PathBuf, engine_path: String, controller: Arc<dyn SuricataController>) -> Self {
+        ConfigManager {
+            path,
+            engine_path,
+            lock: Mutex::new(()),
+            controller,
+        }
+    }
+
+    // suricata 컨테이너에서 보이는 설정 파일 경로
+    pub fn engine_path(&self) -> &str {
+        &self.engine_path
+    }
+
+    pub fn read_text(&self) -> Result<String, ConfigError> {
+        fs::read_to_string(&self.path)
+            .map_err(|e| ConfigError::Io(format!("Failed to read {}: {}", self.path.display(), e)))
+    }
+
+    pub fn read_value(&self) -> Result<Value, ConfigError> {
+        parse_config(&self.read_text()?)
+    }
+
+    pub fn view(&self) -> Result<SuricataConfigView, ConfigError> {
+        Ok(build_view(&self.read_value()?))
+    }
+
+    pub fn vars(&self) -> Result<VarsSection, ConfigError> {
+        Ok(self.view()?.vars)
+    }
+
+    // 변경 목록 적용 후 검증을 통과하면 원본 교체
+    pub async fn patch(&self, changes: &[ConfigPatch]) -> Result<(), ConfigError> {
+        let vars = self.vars()?;
+        for change in changes {
+            if !PATCHABLE_PREFIXES.iter().any(|prefix| change.path.starts_with(prefix)) {
+                return Err(ConfigError::Invalid(format!(
+                    "Path is not patchable: {}. Allowed prefixes: {}",
+                    change.path,
+                    PATCHABLE_PREFIXES.join(", ")
+                )));
+            }
+
+            // 변수 값은 CIDR/포트 문법 검증
+            let var = [VarGroup::AddressGroups, VarGroup::PortGroups]
+                .into_iter()
+                .find_map(|group| {
+                    change.path
+                        .strip_prefix(&format!("vars.{}.", group.key()))
+                        .map(|name| (group, name))
+                });
+            if let Some((group, name)) = var
+                && let Some(value) = scalar_string(&change.value) {
+                validate_var(&vars, group, name, &value)?;
+            }
+        }
+
+        self.update(|text| {
+            let mut text = text.to_string();
+            for change in changes {
+                text = match &change.value {
+                    Value::Null => remove_key(&text, &change.path),
+                    value => set_value(&text, &change.path, &yaml_scalar(value)?),
+                }
+                .map_err(ConfigError::Invalid)?;
+            }
+            Ok(text)
+        })
+        .await
+    }
+
+    pub async fn lock(&self) -> ConfigLock<'_> {
+        ConfigLock { _guard: self.lock.lock().await }
+    }
+
+    // 편집 함수를 적용한 후보 파일을 검증하고, 통과하면 백업 후 교체
+    pub async fn update<F>(&self, edit: F) -> Result<(), ConfigError>
+    where
+        F: FnOnce(&str) -> Result<String, ConfigError>,
+    {
+        let lock = self.lock().await;
+        self.update_locked(&lock, edit).await.map(|_| ())
+    }
+
+    // 잠금을 잡은 상태에서 편집 적용 (바뀌었으면 편집 전 내용 반환)
+    pub async fn update_locked<F>(&self, _lock: &ConfigLock<'_>, edit: F) -> Result<Option<String>, ConfigError>
+    where
+        F: FnOnce(&str) -> Result<String, ConfigError>,
+    {
+        let original = self.read_text()?;
+        let updated = edit(&original)?;
+        if updated == original {
+            return Ok(None);
+        }
+        // 편집 결과가 YAML로 읽히는지 먼저 확인
+        parse_config(&updated)?;
+
+        let candidate = sibling(&self.path, "candidate");
+        fs::write(&candidate, &updated)
+            .map_err(|e| ConfigError::Io(format!("Failed to write candidate config: {}", e)))?;
+
+        let engine_candidate = format!("{}.candidate", self.engine_path);
+        if let Err(e) = self.controller.test_config(&engine_candidate).await {
+            // 실패 시 원본은 그대로 두고 후보만 제거
+            if let Err(e) = fs::remove_file(&candidate) {
+                warn!("Failed to remove candidate config: {}", e);
+            }
+            return Err(ConfigError::TestFailed(e));
+        }
+
+        fs::copy(&self.path, sibling(&self.path, "bak"))
+            .map_err(|e| ConfigError::Io(format!("Failed to back up config: {}", e)))?;
+        fs::rename(&candidate, &self.path)
+            .map_err(|e| ConfigError::Io(format!("Failed to replace config: {}", e)))?;
+
+        info!("Applied suricata.yaml changes to {}", self.path.display());
+        Ok(Some(original))
+    }
+
+    // 변수 생성 또는 수정
+    pub async fn set_var(&self, group: VarGroup, name: &str, value: &str) -> Result<(), ConfigError> {
+        validate_var(&self.vars()?, group, name, value)?;
+
+        let path = format!("vars.{}.{}", group.key(), name);
+        self.update(|text| {
+            set_value(text, &path, &quote_scalar(value.trim())).map_err(ConfigError::Invalid)
+        })
+        .await
+    }
+
+    // 변수 삭제 (다른 변수나 규칙이 참조 중이면 거부)
+    pub async fn delete_var(&self, group: VarGroup, name: &str) -> Result<(), ConfigError> {
+        let vars = self.vars()?;
+        let group_vars = group_vars(&vars, group);
+        if !group_vars.contains_key(name) {
+            return Err(ConfigError::NotFound(format!("Variable ${} not found in {}", name, group.key())));
+        }
+
+        if let Some((other, _)) = group_vars.iter().find(|(other, value)| {
+            other.as_str() != name && references_var(value, name)
+        }) {
+            return Err(ConfigError::Conflict(format!("Variable ${} is referenced by ${}", name, other)));
+        }
+        if let Some(file) = rule_file_referencing(name) {
+            return Err(ConfigError::Conflict(format!("Variable ${} is referenced by rules in {}", name, file)));
+        }
+
+        let path = format!("vars.{}.{}", group.key(), name);
+        self.update(|text| remove_key(text, &path).map_err(ConfigError::Invalid)).await
+    }
+
+    // 직전 백업으로 되돌림
+    pub async fn rollback(&self) -> Result<(), ConfigError> {
+        let backup = sibling(&self.path, "bak");
+        let previous = fs::read_to_string(&backup)
+            .map_err(|e| ConfigError::Io(format!("No backup to roll back to: {}", e)))?;
+        self.update(|_| Ok(previous)).await
+    }
+}
+
+pub fn group_vars(vars: &VarsSection, group: VarGroup) -> &BTreeMap<String, String> {
+    match group {
+        VarGroup::AddressGroups => &vars.address_groups,
+        VarGroup::PortGroups => &vars.port_groups,
+    }
+}
+
+// 변수 이름과 값 검증 (자기 자신 참조는 허용하지 않음)
+fn validate_var(vars: &VarsSection, group: VarGroup, name: &str, value: &str) -> Result<(), ConfigError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ConfigError::Invalid(format!(
+            "Invalid variable name: {}. Use letters, digits and underscores only",
+            name
+        )));
+    }
+
+    let defined: Vec<String> = group_vars(vars, group)
+        .keys()
+        .filter(|var| var.as_str() != name)
+        .cloned()
+        .collect();
+    match group {
+        VarGroup::AddressGroups => validate_address_group(value, &defined),
+        VarGroup::PortGroups => validate_port_group(value, &defined),
+    }
+    .map_err(|e| ConfigError::Invalid(format!("Invalid value for ${}: {}", name, e)))
+}
+
+// 규칙 디렉토리에서 변수를 참조하는 파일 검색
+fn rule_file_referencing(name: &str) -> Option<String> {
+    let (rules_dir, _) = get_env();
+    fs::read_dir(&rules_dir).ok()?
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|ext| ext.to_str()) == Some("rules"))
+        .find(|entry| {
+            fs::read_to_string(entry.path())
+                .map(|content| {
+                    content.lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                        .any(|line| references_var(line.split('(').next().unwrap_or_default(), name))
+                })
+                .unwrap_or(false)
+        })
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+}
+
+// %YAML 헤더를 포함한 suricata.yaml을 JSON 값으로 변환
+pub fn parse_config(text: &str) -> Result<Value, ConfigError> {
+    serde_yaml::from_str::<Value>(text)
+        .map_err(|e| ConfigError::Invalid(format!("Invalid YAML: {}", e)))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", suffix));
+    PathBuf::from(name)
+}
+
+// JSON 값을 suricata.yaml 스칼라 표기로 변환
+fn yaml_scalar(value: &Value) -> Result<String, ConfigError> {
+    match value {
+        Value::String(s) => Ok(quote_scalar(s)),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(true) => Ok("yes".to_string()),
+        Value::Bool(false) => Ok("no".to_string()),
+        _ => Err(ConfigError::Invalid("Only scalar values can be set".to_string())),
+    }
+}
+
+// 스칼라 값을 문자열로 표현
+pub fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn string_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    value
+        .and_then(Value::as_object)
+        .map(|map| {
+            map.iter()
+                .filter_map(|(key, value)| scalar_string(value).map(|value| (key.clone(), value)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn af_packet_interfaces(config: &Value) -> Vec<AfPacketInterface> {
+    let field = |entry: &Value, key: &str| entry.get(key).and_then(scalar_string);
+    config.get("af-packet")
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries.iter()
+                .filter_map(|entry| {
+                    Some(AfPacketInterface {
+                        interface: field(entry, "interface")?,
+                        threads: field(entry, "threads"),
+                        cluster_id: field(entry, "cluster-id"),
+                        cluster_type: field(entry, "cluster-type"),
+                        defrag: field(entry, "defrag"),
+                        copy_mode: field(entry, "copy-mode"),
+                        copy_iface: field(entry, "copy-iface"),
+                        bpf_filter: field(entry, "bpf-filter"),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn eve_log_section(config: &Value) -> Option<EveLogSection> {
+    let eve = config.get("outputs")?
+        .as_array()?
+        .iter()
+        .find_map(|output| output.get("eve-log"))?;
+
+    // types 항목은 "- flow" 또는 "- alert: {...}" 형태
+    let types = eve.get("types")
+        .and_then(Value::as_array)
+        .map(|types| {
+            types.iter()
+                .filter_map(|entry| match entry {
+                    Value::String(name) => Some(name.clone()),
+                    Value::Object(map) => map.keys().next().cloned(),
+                    _ => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(EveLogSection {
+        enabled: eve.get("enabled").and_then(scalar_string),
+        filetype: eve.get("filetype").and_then(scalar_string),
+        filename: eve.get("filename").and_then(scalar_string),
+        types,
+    })
+}
+
+fn build_view(config: &Value) -> SuricataConfigView {
+    let vars = config.get("vars");
+    let app_layer = config.pointer("/app-layer/protocols")
+        .and_then(Value::as_object)
+        .map(|protocols| {
+            protocols.iter()
+                .filter_map(|(proto, settings)| {
+                    settings.get("enabled")
+                        .and_then(scalar_string)
+                        .map(|enabled| (proto.clone(), enabled))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    SuricataConfigView {
+        vars: VarsSection {
+            address_groups: string_map(vars.and_then(|vars| vars.get("address-groups"))),
+            port_groups: string_map(vars.and_then(|vars| vars.get("port-groups"))),
+        },
+        af_packet: af_packet_interfaces(config),
+        eve_log: eve_log_section(config),
+        app_layer,
+    }
+}
@@ -0,0 +1,231 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use async_trait::async_trait;
+use serde_json::json;
+use tokio::process::Command;
+use tracing::{error, info, warn};
+
+use crate::utils::config::env_or;
+
+// 명령 실행 결과
+#[derive(Debug)]
+pub struct CommandOutput {
+    // 정상 종료가 아니면 None (시그널 등)
+    pub code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+// Suricata 제어 백엔드 (컨테이너 런타임, 호스트 프로세스, 테스트용 가짜)
+#[async_trait]
+pub trait SuricataController: Send + Sync {
+    // suricatasc 명령 실행 후 원본 출력 반환
+    async fn suricatasc(&self, command: &str) -> Result<String, String>;
+    // suricata -T로 설정 파일 검증
+    async fn test_config(&self, path: &str) -> Result<(), String>;
+    async fn restart(&self) -> Result<(), String>;
+    // Suricata와 같은 환경에서 임의 명령 실행 (tcpdump, suricata -r 등)
+    async fn exec(&self, args: &[&str]) -> Result<CommandOutput, String>;
+}
+
+async fn run_command(program: &str, args: &[&str]) -> Result<CommandOutput, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| {
+            error!("Failed to execute {}: {}", program, e);
+            format!("Failed to execute command: {}", e)
+        })?;
+
+    Ok(CommandOutput {
+        code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+    })
+}
+
+fn suricatasc_output(output: CommandOutput, command: &str) -> Result<String, String> {
+    if !output.success() {
+        error!("suricatasc command '{}' failed: {}", command, output.stderr);
+        return Err(format!("Command failed with status: {:?} ({})", output.code, output.stderr));
+    }
+    Ok(output.stdout)
+}
+
+fn config_test_output(output: CommandOutput) -> Result<(), String> {
+    if !output.success() {
+        let detail = format!("{}{}", output.stderr, output.stdout);
+        error!("Suricata config test failed: {}", detail);
+        return Err(format!("Config test failed: {}", detail.trim()));
+    }
+    Ok(())
+}
+
+// docker/podman/nerdctl처럼 exec, restart 하위 명령을 가진 컨테이너 CLI
+pub struct ContainerController {
+    binary: String,
+    container: String,
+}
+
+impl ContainerController {
+    pub fn new(binary: &str, container: String) -> Self {
+        ContainerController {
+            binary: binary.to_string(),
+            container,
+        }
+    }
+
+    async fn exec_in_container(&self, args: &[&str]) -> Result<CommandOutput, String> {
+        let mut full = vec!["exec", self.container.as_str()];
+        full.extend_from_slice(args);
+        run_command(&self.binary, &full).await
+    }
+}
+
+#[async_trait]
+impl SuricataController for ContainerController {
+    async fn suricatasc(&self, command: &str) -> Result<String, String> {
+        let output = self.exec_in_container(&["suricatasc", "-c", command]).await?;
+        suricatasc_output(output, command)
+    }
+
+    async fn test_config(&self, path: &str) -> Result<(), String> {
+        config_test_output(self.exec_in_container(&["suricata", "-T", "-c", path]).await?)
+    }
+
+    async fn restart(&self) -> Result<(), String> {
+        let output = run_command(&self.binary, &["restart", self.container.as_str()]).await?;
+        if !output.success() {
+            error!("Failed to restart Suricata container: {}", output.stderr);
+            return Err(format!("Failed to restart container: {}", output.stderr));
+        }
+        Ok(())
+    }
+
+    async fn exec(&self, args: &[&str]) -> Result<CommandOutput, String> {
+        self.exec_in_container(args).await
+    }
+}
+
+// 호스트에서 직접 실행 중인 Suricata (유닉스 소켓, systemd 서비스)
+pub struct LocalController {
+    socket: String,
+    service: String,
+}
+
+#[async_trait]
+impl SuricataController for LocalController {
+    async fn suricatasc(&self, command: &str) -> Result<String, String> {
+        let output = run_command("suricatasc", &["-c", command, self.socket.as_str()]).await?;
+        suricatasc_output(output, command)
+    }
+
+    async fn test_config(&self, path: &str) -> Result<(), String> {
+        config_test_output(run_command("suricata", &["-T", "-c", path]).await?)
+    }
+
+    async fn restart(&self) -> Result<(), String> {
+        let output = run_command("systemctl", &["restart", self.service.as_str()]).await?;
+        if !output.success() {
+            error!("Failed to restart Suricata service: {}", output.stderr);
+            return Err(format!("Failed to restart service {}: {}", self.service, output.stderr));
+        }
+        Ok(())
+    }
+
+    async fn exec(&self, args: &[&str]) -> Result<CommandOutput, String> {
+        let (program, rest) = args.split_first().ok_or("No command given")?;
+        run_command(program, rest).await
+    }
+}
+
+// 엔진 없이 제어 흐름을 시험하기 위한 가짜 백엔드
+// FAKE_CONTROLLER_FAIL로 실패 단계 지정 (test, restart, stall)
+pub struct FakeController {
+    fail: String,
+    packets: AtomicU64,
+}
+
+impl FakeController {
+    pub fn from_env() -> Self {
+        FakeController {
+            fail: env_or("FAKE_CONTROLLER_FAIL", String::new()),
+            packets: AtomicU64::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl SuricataController for FakeController {
+    async fn suricatasc(&self, command: &str) -> Result<String, String> {
+        let message = match command {
+            "uptime" => json!(10),
+            "iface-list" => json!({ "count": 0, "ifaces": [] }),
+            "dump-counters" => {
+                // stall이면 카운터가 멈춘 것처럼 응답
+                let packets = if self.fail == "stall" {
+                    self.packets.load(Ordering::Relaxed)
+                } else {
+                    self.packets.fetch_add(100, Ordering::Relaxed) + 100
+                };
+                json!({ "capture": { "kernel_packets": packets, "kernel_drops": 0 } })
+            }
+            _ => json!("done"),
+        };
+        Ok(json!({ "return": "OK", "message": message }).to_string())
+    }
+
+    async fn test_config(&self, path: &str) -> Result<(), String> {
+        if self.fail == "test" {
+            return Err(format!("Config test failed: fake failure for {}", path));
+        }
+        Ok(())
+    }
+
+    async fn restart(&self) -> Result<(), String> {
+        if self.fail == "restart" {
+            return Err("Failed to restart container: fake failure".to_string());
+        }
+        self.packets.store(0, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn exec(&self, args: &[&str]) -> Result<CommandOutput, String> {
+        info!("Fake controller exec: {:?}", args);
+        Ok(CommandOutput {
+            code: Some(0),
+            stdout: String::new(),
+            stderr: String::new(),
+        })
+    }
+}
+
+// SURICATA_CONTROLLER 설정에 따라 백엔드 선택
+pub fn controller_from_env() -> Arc<dyn SuricataController> {
+    let backend = env_or("SURICATA_CONTROLLER", "docker".to_string());
+    let container = env_or("SURICATA_CONTAINER", "suricata".to_string());
+    info!("Using {} Suricata controller", backend);
+
+    match backend.as_str() {
+        "docker" | "podman" | "nerdctl" => Arc::new(ContainerController::new(&backend, container)),
+        "local" => Arc::new(LocalController {
+            socket: env_or("SURICATA_SOCKET", "/var/run/suricata/suricata-command.socket".to_string()),
+            service: env_or("SURICATA_SERVICE", "suricata".to_string()),
+        }),
+        "fake" => Arc::new(FakeController::from_env()),
+        other => {
+            warn!("Unknown SURICATA_CONTROLLER {}, falling back to docker", other);
+            Arc::new(ContainerController::new("docker", container))
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use serde_json::Value;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::models::counters::{CounterPoint, CounterQuery, CounterSeries, CounterSeriesList};
+use crate::services::controller::SuricataController;
+use crate::utils::config::env_or;
+use crate::utils::time::now_secs;
+
+// 카운터 수집 경로
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CounterSource {
+    // suricatasc dump-counters 주기 호출
+    DumpCounters,
+    // EVE stats 이벤트 수신
+    Eve,
+}
+
+impl CounterSource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            CounterSource::DumpCounters => "dump-counters",
+            CounterSource::Eve => "eve",
+        }
+    }
+}
+
+// 최근 N시간 카운터를 보관하는 링 버퍼
+pub struct CounterStore {
+    series: RwLock<HashMap<String, VecDeque<(u64, u64)>>>,
+    source: CounterSource,
+    interval_secs: u64,
+    retention_secs: u64,
+}
+
+impl CounterStore {
+    pub fn from_env() -> Self {
+        let source = match env_or("COUNTERS_SOURCE", "dump-counters".to_string()).as_str() {
+            "eve" => CounterSource::Eve,
+            _ => CounterSource::DumpCounters,
+        };
+        let interval_secs = env_or("COUNTERS_POLL_INTERVAL_SECS", 10u64).max(1);
+        let retention_secs = env_or("COUNTERS_RETENTION_HOURS", 6u64).max(1) * 3600;
+
+        CounterStore {
+            series: RwLock::new(HashMap::new()),
+            source,
+            interval_secs,
+            retention_secs,
+        }
+    }
+
+    // 카운터 스냅샷 기록 (중첩 객체는 점으로 연결한 이름으로 평탄화)
+    pub async fn record(&self, timestamp: u64, counters: &Value) {
+        let mut flat = Vec::new();
+        flatten_counters("", counters, &mut flat);
+
+        let cutoff = timestamp.saturating_sub(self.retention_secs);
+        let mut series = self.series.write().await;
+        for (name, value) in flat {
+            let points = series.entry(name).or_default();
+            points.push_back((timestamp, value));
+            while points.front().is_some_and(|(ts, _)| *ts < cutoff) {
+                points.pop_front();
+            }
+        }
+        // 더 이상 갱신되지 않는 카운터 정리
+        series.retain(|_, points| points.back().is_some_and(|(ts, _)| *ts >= cutoff));
+    }
+
+    // EVE stats 이벤트를 카운터 시계열에 기록 (수집 경로가 eve일 때만)
+    pub async fn record_eve_stats(&self, items: &[Value]) {
+        if self.source != CounterSource::Eve {
+            return;
+        }
+        for item in items {
+            if item.get("event_type").and_then(Value::as_str) == Some("stats")
+                && let Some(stats) = item.get("stats") {
+                self.record(now_secs(), stats).await;
+            }
+        }
+    }
+
+    // 카운터별 최신 값
+    pub async fn latest(&self) -> HashMap<String, u64> {
+        let series = self.series.read().await;
+        series.iter()
+            .filter_map(|(name, points)| points.back().map(|(_, value)| (name.clone(), *value)))
+            .collect()
+    }
+
+    pub async fn query(&self, query: &CounterQuery) -> CounterSeriesList {
+        let names: Option<Vec<&str>> = query.names.as_deref().map(|names| {
+            names.split(',').map(str::trim).filter(|name| !name.is_empty()).collect()
+        });
+        let since = now_secs().saturating_sub(query.window.unwrap_or(self.retention_secs));
+
+        let series = self.series.read().await;
+        let mut result: Vec<CounterSeries> = series.iter()
+            .filter(|(name, _)| match &names {
+                Some(names) => names.contains(&name.as_str()),
+                None => true,
+            })
+            .filter(|(name, _)| match &query.prefix {
+                Some(prefix) => name.starts_with(prefix.as_str()),
+                None => true,
+            })
+            .map(|(name, points)| {
+                let samples: Vec<(u64, u64)> = points.iter()
+                    .copied()
+                    .filter(|(ts, _)| *ts >= since)
+                    .collect();
+                let samples = match query.step {
+                    Some(step) if step > 0 => downsample(&samples, step),
+                    _ => samples,
+                };
+                CounterSeries {
+                    name: name.clone(),
+                    points: with_rates(&samples),
+                }
+            })
+            .collect();
+        result.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let count = result.len();
+        CounterSeriesList {
+            source: self.source.as_str().to_string(),
+            interval: self.interval_secs,
+            retention: self.retention_secs,
+            series: result,
+            count,
+        }
+    }
+}
+
+// dump-counters 주기 수집 태스크
+pub fn spawn_counter_poller(store: Arc<CounterStore>, controller: Arc<dyn SuricataController>) {
+    if store.source != CounterSource::DumpCounters {
+        info!("Counter source is EVE stats events, dump-counters polling disabled");
+        return;
+    }
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(store.interval_secs));
+        loop {
+            ticker.tick().await;
+            match poll_counters(controller.as_ref()).await {
+                Ok(counters) => store.record(now_secs(), &counters).await,
+                Err(e) => warn!("Failed to poll Suricata counters: {}", e),
+            }
+        }
+    });
+}
+
+// dump-counters 결과에서 카운터 객체만 추출
+pub async fn poll_counters(controller: &dyn SuricataController) -> Result<Value, String> {
+    parse_counters(&controller.suricatasc("dump-counters").await?)
+}
+
+pub fn parse_counters(output: &str) -> Result<Value, String> {
+    let response: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Invalid dump-counters output: {}", e))?;
+
+    if response.get("return").and_then(Value::as_str) != Some("OK") {
+        return Err(format!("dump-counters returned error: {}", response));
+    }
+
+    response.get("message")
+        .cloned()
+        .ok_or_else(|| "dump-counters output has no message".to_string())
+}
+
+fn flatten_counters(prefix: &str, value: &Value, out: &mut Vec<(String, u64)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                flatten_counters(&name, child, out);
+            }
+        }
+        Value::Number(number) => {
+            if let Some(value) = number.as_u64() {
+                out.push((prefix.to_string(), value));
+            }
+        }
+        _ => {}
+    }
+}
+
+// step 구간마다 마지막 샘플만 남김
+fn downsample(samples: &[(u64, u64)], step: u64) -> Vec<(u64, u64)> {
+    let mut result: Vec<(u64, u64)> = Vec::new();
+    for &(ts, value) in samples {
+        match result.last_mut() {
+            Some(last) if last.0 / step == ts / step => *last = (ts, value),
+            _ => result.push((ts, value)),
+        }
+    }
+    result
+}
+
+// 직전 지점 대비 초당 증가량 계산 (카운터 리셋 시 현재 값을 증가량으로 간주)
+fn with_rates(samples: &[(u64, u64)]) -> Vec<CounterPoint> {
+    let mut points = Vec::with_capacity(samples.len());
+    let mut previous: Option<(u64, u64)> = None;
+    for &(ts, value) in samples {
+        let rate = previous.and_then(|(prev_ts, prev_value)| {
+            let elapsed = ts.checked_sub(prev_ts).filter(|elapsed| *elapsed > 0)?;
+            let delta = if value >= prev_value { value - prev_value } else { value };
+            Some(delta as f64 / elapsed as f64)
+        });
+        points.push(CounterPoint { timestamp: ts, value, rate });
+        previous = Some((ts, value));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn store(source: CounterSource, retention_secs: u64) -> CounterStore {
+        CounterStore {
+            series: RwLock::new(HashMap::new()),
+            source,
+            interval_secs: 10,
+            retention_secs,
+        }
+    }
+
+    fn query(names: Option<&str>, prefix: Option<&str>, step: Option<u64>) -> CounterQuery {
+        CounterQuery {
+            names: names.map(str::to_string),
+            prefix: prefix.map(str::to_string),
+            window: None,
+            step,
+        }
+    }
+
+    #[tokio::test]
+    async fn old_points_and_stale_counters_are_evicted() {
+        let store = store(CounterSource::DumpCounters, 60);
+        let now = now_secs();
+        store.record(now - 100, &json!({"capture": {"kernel_packets": 1, "kernel_drops": 0}})).await;
+        store.record(now - 50, &json!({"capture": {"kernel_packets": 2}})).await;
+        store.record(now, &json!({"capture": {"kernel_packets": 3}})).await;
+
+        // 더 이상 갱신되지 않는 kernel_drops는 사라짐
+        let list = store.query(&query(None, None, None)).await;
+        assert_eq!(list.count, 1);
+        assert_eq!(list.series[0].name, "capture.kernel_packets");
+        let timestamps: Vec<u64> = list.series[0].points.iter().map(|point| point.timestamp).collect();
+        assert_eq!(timestamps, vec![now - 50, now]);
+    }
+
+    #[tokio::test]
+    async fn query_filters_and_computes_rates() {
+        let store = store(CounterSource::DumpCounters, 3600);
+        let now = now_secs();
+        store.record(now - 20, &json!({"decoder": {"pkts": 100, "bytes": 1000}, "uptime": 5})).await;
+        store.record(now - 10, &json!({"decoder": {"pkts": 200, "bytes": 1500}, "uptime": 15})).await;
+        // 재시작으로 카운터가 리셋됨
+        store.record(now, &json!({"decoder": {"pkts": 50, "bytes": 2000}, "uptime": 1})).await;
+
+        let list = store.query(&query(None, Some("decoder."), None)).await;
+        let names: Vec<&str> = list.series.iter().map(|series| series.name.as_str()).collect();
+        assert_eq!(names, vec!["decoder.bytes", "decoder.pkts"]);
+
+        let pkts = &list.series[1].points;
+        let rates: Vec<Option<f64>> = pkts.iter().map(|point| point.rate).collect();
+        assert_eq!(rates, vec![None, Some(10.0), Some(5.0)]);
+
+        let list = store.query(&query(Some("uptime, decoder.pkts"), None, None)).await;
+        assert_eq!(list.count, 2);
+    }
+
+    #[test]
+    fn downsample_keeps_last_sample_per_step() {
+        let samples = [(100, 1), (105, 2), (110, 3), (119, 4), (120, 5)];
+        assert_eq!(downsample(&samples, 10), vec![(105, 2), (119, 4), (120, 5)]);
+    }
+
+    #[tokio::test]
+    async fn eve_stats_are_recorded_only_for_eve_source() {
+        let items = [
+            json!({"event_type": "stats", "stats": {"uptime": 42}}),
+            json!({"event_type": "alert", "stats": {"uptime": 7}}),
+        ];
+        let eve = store(CounterSource::Eve, 3600);
+        eve.record_eve_stats(&items).await;
+        assert_eq!(eve.latest().await.get("uptime"), Some(&42));
+
+        let polled = store(CounterSource::DumpCounters, 3600);
+        polled.record_eve_stats(&items).await;
+        assert!(polled.latest().await.is_empty());
+    }
+
+    #[test]
+    fn dump_counters_output_is_parsed() {
+        let counters = parse_counters(r#"{"return": "OK", "message": {"uptime": 10}}"#).unwrap();
+        assert_eq!(counters["uptime"], 10);
+        assert!(parse_counters(r#"{"return": "NOK", "message": "Unix socket closed"}"#).is_err());
+        assert!(parse_counters("not json").is_err());
+    }
+}
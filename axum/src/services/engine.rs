@@ -0,0 +1,519 @@
+use std::{sync::Arc, time::Duration};
+
+use serde_json::Value;
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Instant},
+};
+use tracing::{error, info, warn};
+
+use crate::models::engine::{EngineMode, EngineModeRequest, EngineModeView, EngineReport, EngineStep};
+use crate::services::config::{af_packet_interfaces, parse_config, ConfigError, ConfigLock, ConfigManager};
+use crate::services::counters::parse_counters;
+use crate::services::controller::SuricataController;
+use crate::services::supervisor::Supervisor;
+use crate::utils::config::env_or;
+use crate::utils::yaml::{quote_scalar, remove_key, set_value};
+
+// 엔진 제어 실패 사유 (절차 시작 전)
+#[derive(Debug)]
+pub enum EngineError {
+    // 다른 재시작/전환이 진행 중
+    Busy,
+    Config(ConfigError),
+}
+
+impl EngineError {
+    pub fn message(&self) -> &str {
+        match self {
+            EngineError::Busy => "Another engine restart is in progress",
+            EngineError::Config(e) => e.message(),
+        }
+    }
+}
+
+// 점검, 드레인, 재시작, 사후 점검, 롤백 순서로 엔진을 제어
+pub struct EngineController {
+    controller: Arc<dyn SuricataController>,
+    config: Arc<ConfigManager>,
+    supervisor: Arc<Supervisor>,
+    lock: Mutex<()>,
+    start_timeout: Duration,
+    // 재시작 전 출력이 비워지길 기다리는 시간
+    drain: Duration,
+    // 재시작 후 캡처 카운터 증가를 확인하는 시간 (0이면 생략)
+    post_check: Duration,
+}
+
+impl EngineController {
+    pub fn from_env(
+        controller: Arc<dyn SuricataController>,
+        config: Arc<ConfigManager>,
+        supervisor: Arc<Supervisor>,
+    ) -> Self {
+        EngineController {
+            controller,
+            config,
+            supervisor,
+            lock: Mutex::new(()),
+            start_timeout: Duration::from_secs(env_or("ENGINE_START_TIMEOUT_SECS", 60u64)),
+            drain: Duration::from_secs(env_or("ENGINE_DRAIN_SECS", 5u64)),
+            post_check: Duration::from_secs(env_or("ENGINE_POST_CHECK_SECS", 30u64)),
+        }
+    }
+
+    // 설정 변경 없이 재시작
+    pub async fn restart(&self) -> Result<EngineReport, EngineError> {
+        let _guard = self.lock.try_lock().map_err(|_| EngineError::Busy)?;
+        Ok(self.run("restart", None).await)
+    }
+
+    // 설정 편집 후 재시작 (실패 시 편집 전 내용으로 복구)
+    // 편집부터 복구까지 설정 잠금을 유지하고, 편집 결과가 같으면 복구 없이 재시작만 함
+    pub async fn apply_and_restart<F>(&self, action: &str, edit: F) -> Result<EngineReport, EngineError>
+    where
+        F: FnOnce(&str) -> Result<String, ConfigError>,
+    {
+        let _guard = self.lock.try_lock().map_err(|_| EngineError::Busy)?;
+        let lock = self.config.lock().await;
+        let previous = self.config.update_locked(&lock, edit).await.map_err(EngineError::Config)?;
+
+        let applied = match previous {
+            Some(_) => "Configuration updated",
+            None => "Configuration unchanged",
+        };
+        let mut report = self.run(action, previous.as_deref().map(|previous| (&lock, previous))).await;
+        report.steps.insert(0, step("apply", Ok(applied.to_string())));
+        Ok(report)
+    }
+
+    pub fn mode(&self) -> Result<EngineModeView, ConfigError> {
+        Ok(mode_view(&self.config.read_value()?))
+    }
+
+    // IDS/IPS 전환: 설정 변경 후 재시작, 실패 시 복구
+    pub async fn switch_mode(&self, request: &EngineModeRequest) -> Result<EngineReport, EngineError> {
+        let _guard = self.lock.try_lock().map_err(|_| EngineError::Busy)?;
+        let lock = self.config.lock().await;
+        let previous = self.config
+            .update_locked(&lock, |text| apply_mode(text, request))
+            .await
+            .map_err(EngineError::Config)?;
+
+        let action = match request.mode {
+            EngineMode::Ids => "switch_to_ids",
+            EngineMode::Ips => "switch_to_ips",
+        };
+        let Some(previous) = previous else {
+            return Ok(EngineReport {
+                action: action.to_string(),
+                success: true,
+                rolled_back: false,
+                steps: vec![step("apply", Ok("Already in requested mode".to_string()))],
+            });
+        };
+
+        let mut report = self.run(action, Some((&lock, &previous))).await;
+        report.steps.insert(0, step("apply", Ok("Updated af-packet copy-mode settings".to_string())));
+        Ok(report)
+    }
+
+    // restore: 실패 시 되돌릴 편집 전 설정 (설정 잠금을 잡은 상태)
+    async fn run(&self, action: &str, restore: Option<(&ConfigLock<'_>, &str)>) -> EngineReport {
+        info!("Engine {} started", action);
+        self.supervisor.set_maintenance(true);
+        let mut report = EngineReport {
+            action: action.to_string(),
+            success: false,
+            rolled_back: false,
+            steps: Vec::new(),
+        };
+
+        let failed = 'sequence: {
+            let preflight = self.controller
+                .test_config(self.config.engine_path())
+                .await
+                .map(|_| "Configuration test passed".to_string());
+            if push_step(&mut report, "preflight", preflight) {
+                // 재시작 전이므로 설정만 되돌리면 됨
+                if let Some((lock, previous)) = restore {
+                    let rollback = self.restore(lock, previous).await
+                        .map(|_| "Previous configuration restored".to_string())
+                        .map_err(|e| e.message().to_string());
+                    report.rolled_back = !push_step(&mut report, "rollback", rollback);
+                }
+                break 'sequence false;
+            }
+
+            let drain = self.drain().await;
+            push_step(&mut report, "drain", Ok(drain));
+
+            let restart = self.restart_and_wait().await.map(|_| "Suricata is up".to_string());
+            if push_step(&mut report, "restart", restart) {
+                break 'sequence true;
+            }
+
+            if self.post_check.is_zero() {
+                push_step(&mut report, "post_check", Ok("Skipped".to_string()));
+            } else {
+                let check = self.check_capture().await;
+                if push_step(&mut report, "post_check", check) {
+                    break 'sequence true;
+                }
+            }
+            report.success = true;
+            false
+        };
+
+        // 재시작 이후 실패는 직전 설정으로 복구 후 다시 재시작
+        if failed && let Some((lock, previous)) = restore {
+            let rollback = match self.restore(lock, previous).await {
+                Ok(()) => self.restart_and_wait().await
+                    .map(|_| "Previous configuration restored and Suricata restarted".to_string()),
+                Err(e) => Err(e.message().to_string()),
+            };
+            report.rolled_back = !push_step(&mut report, "rollback", rollback);
+        }
+
+        self.supervisor.set_maintenance(false);
+        if report.success {
+            info!("Engine {} completed", action);
+        } else {
+            error!("Engine {} failed (rolled back: {})", action, report.rolled_back);
+        }
+        report
+    }
+
+    async fn restore(&self, lock: &ConfigLock<'_>, previous: &str) -> Result<(), ConfigError> {
+        self.config.update_locked(lock, |_| Ok(previous.to_string())).await.map(|_| ())
+    }
+
+    // 재시작 전 기준 카운터 기록 후 출력이 비워지길 대기
+    async fn drain(&self) -> String {
+        let packets = self.kernel_packets().await.ok().flatten();
+        sleep(self.drain).await;
+        match packets {
+            Some(packets) => format!("Waited {}s, kernel_packets was {}", self.drain.as_secs(), packets),
+            None => format!("Waited {}s", self.drain.as_secs()),
+        }
+    }
+
+    async fn restart_and_wait(&self) -> Result<(), String> {
+        self.controller.restart().await?;
+        wait_for_engine(self.controller.as_ref(), self.start_timeout).await
+    }
+
+    // 재시작 후 캡처 카운터가 증가하는지 확인
+    async fn check_capture(&self) -> Result<String, String> {
+        let deadline = Instant::now() + self.post_check;
+        let mut first = None;
+        loop {
+            match self.kernel_packets().await {
+                Ok(Some(packets)) => match first {
+                    None => first = Some(packets),
+                    Some(start) if packets > start => {
+                        return Ok(format!("kernel_packets increased from {} to {}", start, packets));
+                    }
+                    Some(_) => {}
+                },
+                Ok(None) => return Err("capture.kernel_packets counter is not available".to_string()),
+                Err(e) => warn!("Post-check failed to read counters: {}", e),
+            }
+            if Instant::now() >= deadline {
+                return Err(format!(
+                    "capture.kernel_packets did not increase within {}s",
+                    self.post_check.as_secs()
+                ));
+            }
+            sleep(Duration::from_secs(2)).await;
+        }
+    }
+
+    async fn kernel_packets(&self) -> Result<Option<u64>, String> {
+        let output = self.controller.suricatasc("dump-counters").await?;
+        Ok(parse_counters(&output)?
+            .pointer("/capture/kernel_packets")
+            .and_then(Value::as_u64))
+    }
+}
+
+fn step(name: &str, result: Result<String, String>) -> EngineStep {
+    let (ok, message) = match result {
+        Ok(message) => (true, message),
+        Err(message) => (false, message),
+    };
+    EngineStep {
+        step: name.to_string(),
+        ok,
+        message: Some(message),
+    }
+}
+
+// 단계 결과 기록, 실패했으면 true
+fn push_step(report: &mut EngineReport, name: &str, result: Result<String, String>) -> bool {
+    let step = step(name, result);
+    let failed = !step.ok;
+    report.steps.push(step);
+    failed
+}
+
+// 엔진 재시작 후 유닉스 소켓이 응답할 때까지 대기
+async fn wait_for_engine(controller: &dyn SuricataController, timeout: Duration) -> Result<(), String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        match controller.suricatasc("uptime").await {
+            Ok(_) => {
+                info!("Suricata is up after restart");
+                return Ok(());
+            }
+            Err(e) => {
+                if Instant::now() >= deadline {
+                    return Err(format!("Suricata did not come up within {}s: {}", timeout.as_secs(), e));
+                }
+                warn!("Waiting for Suricata to start: {}", e);
+            }
+        }
+        sleep(Duration::from_secs(2)).await;
+    }
+}
+
+fn mode_view(config: &Value) -> EngineModeView {
+    let interfaces = af_packet_interfaces(config);
+    let mut ips_pairs: Vec<[String; 2]> = Vec::new();
+    for entry in &interfaces {
+        if entry.copy_mode.as_deref() != Some("ips") {
+            continue;
+        }
+        if let Some(peer) = &entry.copy_iface
+            && !ips_pairs.iter().any(|pair| pair.contains(&entry.interface)) {
+            ips_pairs.push([entry.interface.clone(), peer.clone()]);
+        }
+    }
+    EngineModeView {
+        mode: if ips_pairs.is_empty() { EngineMode::Ids } else { EngineMode::Ips },
+        ips_pairs,
+    }
+}
+
+// 요청 모드에 맞게 af-packet copy-mode 편집
+fn apply_mode(text: &str, request: &EngineModeRequest) -> Result<String, ConfigError> {
+    let interfaces = af_packet_interfaces(&parse_config(text)?);
+    let mut text = text.to_string();
+    let item = |name: &str| format!("af-packet[interface={}]", name);
+
+    match request.mode {
+        EngineMode::Ids => {
+            if !request.pairs.is_empty() {
+                return Err(ConfigError::Invalid("pairs is only valid for ips mode".to_string()));
+            }
+            for entry in interfaces.iter().filter(|entry| entry.copy_mode.as_deref() == Some("ips")) {
+                text = remove_key(&text, &format!("{}.copy-mode", item(&entry.interface)))
+                    .map_err(ConfigError::Invalid)?;
+                if entry.copy_iface.is_some() {
+                    text = remove_key(&text, &format!("{}.copy-iface", item(&entry.interface)))
+                        .map_err(ConfigError::Invalid)?;
+                }
+            }
+        }
+        EngineMode::Ips => {
+            if request.pairs.is_empty() {
+                return Err(ConfigError::Invalid("ips mode requires at least one interface pair".to_string()));
+            }
+            let mut seen: Vec<&str> = Vec::new();
+            for [a, b] in &request.pairs {
+                for name in [a, b] {
+                    if name == "default" || !interfaces.iter().any(|entry| &entry.interface == name) {
+                        return Err(ConfigError::Invalid(format!(
+                            "Interface {} is not configured under af-packet",
+                            name
+                        )));
+                    }
+                    if seen.contains(&name.as_str()) {
+                        return Err(ConfigError::Invalid(format!("Interface {} is used in more than one pair", name)));
+                    }
+                    seen.push(name);
+                }
+                for (name, peer) in [(a, b), (b, a)] {
+                    text = set_value(&text, &format!("{}.copy-mode", item(name)), "ips")
+                        .and_then(|text| set_value(&text, &format!("{}.copy-iface", item(name)), &quote_scalar(peer)))
+                        .map_err(ConfigError::Invalid)?;
+                }
+            }
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::{Path, PathBuf}, sync::Mutex as StdMutex};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::models::config::ConfigPatch;
+    use crate::models::engine::EngineModeRequest;
+    use crate::services::controller::CommandOutput;
+    use crate::utils::random::random_u64;
+
+    const CONFIG: &str = "%YAML 1.1\n---\naf-packet:\n  - interface: eth0\n  - interface: eth1\n  - interface: default\nstats:\n  enabled: no\n";
+
+    // 호출을 기록하고 지정한 단계에서 실패하는 백엔드
+    struct MockController {
+        calls: StdMutex<Vec<String>>,
+        fail_test: bool,
+        // 처음 몇 번의 재시작을 실패시킬지
+        fail_restarts: StdMutex<u32>,
+    }
+
+    impl MockController {
+        fn new(fail_test: bool, fail_restarts: u32) -> Arc<Self> {
+            Arc::new(MockController {
+                calls: StdMutex::new(Vec::new()),
+                fail_test,
+                fail_restarts: StdMutex::new(fail_restarts),
+            })
+        }
+
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl SuricataController for MockController {
+        async fn suricatasc(&self, command: &str) -> Result<String, String> {
+            self.calls.lock().unwrap().push(command.to_string());
+            Ok(r#"{"return":"OK","message":{"capture":{"kernel_packets":1}}}"#.to_string())
+        }
+
+        async fn test_config(&self, path: &str) -> Result<(), String> {
+            self.calls.lock().unwrap().push(format!("test {}", path));
+            if self.fail_test && !path.ends_with(".candidate") {
+                return Err("invalid config".to_string());
+            }
+            Ok(())
+        }
+
+        async fn restart(&self) -> Result<(), String> {
+            self.calls.lock().unwrap().push("restart".to_string());
+            // 컨테이너 재시작처럼 잠시 걸림
+            sleep(Duration::from_millis(50)).await;
+            let mut failures = self.fail_restarts.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err("container did not start".to_string());
+            }
+            Ok(())
+        }
+
+        async fn exec(&self, _args: &[&str]) -> Result<CommandOutput, String> {
+            Err("not supported".to_string())
+        }
+    }
+
+    fn engine(controller: Arc<MockController>) -> (EngineController, PathBuf) {
+        let path = std::env::temp_dir().join(format!("engine-test-{}-{}.yaml", std::process::id(), random_u64()));
+        fs::write(&path, CONFIG).unwrap();
+        let config = Arc::new(ConfigManager::new(path.clone(), path.display().to_string(), controller.clone()));
+        let engine = EngineController {
+            controller: controller.clone(),
+            config,
+            supervisor: Arc::new(Supervisor::from_env(controller, None)),
+            lock: Mutex::new(()),
+            start_timeout: Duration::from_secs(5),
+            drain: Duration::ZERO,
+            post_check: Duration::ZERO,
+        };
+        (engine, path)
+    }
+
+    fn step_names(report: &EngineReport) -> Vec<&str> {
+        report.steps.iter().map(|step| step.step.as_str()).collect()
+    }
+
+    fn cleanup(path: &Path) {
+        for suffix in ["", ".bak", ".candidate"] {
+            let _ = fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+    }
+
+    #[tokio::test]
+    async fn restart_runs_every_step() {
+        let controller = MockController::new(false, 0);
+        let (engine, path) = engine(controller.clone());
+        let report = engine.restart().await.unwrap();
+
+        assert!(report.success);
+        assert_eq!(step_names(&report), ["preflight", "drain", "restart", "post_check"]);
+        assert_eq!(controller.calls(), [format!("test {}", path.display()), "dump-counters".to_string(), "restart".to_string(), "uptime".to_string()]);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn failed_preflight_does_not_restart() {
+        let controller = MockController::new(true, 0);
+        let (engine, path) = engine(controller.clone());
+        let report = engine.restart().await.unwrap();
+
+        assert!(!report.success);
+        assert!(!report.rolled_back);
+        assert_eq!(step_names(&report), ["preflight"]);
+        assert!(!controller.calls().contains(&"restart".to_string()));
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn failed_restart_after_mode_switch_restores_config() {
+        let controller = MockController::new(false, 1);
+        let (engine, path) = engine(controller.clone());
+        let request: EngineModeRequest = serde_json::from_str(r#"{"mode":"ips","pairs":[["eth0","eth1"]]}"#).unwrap();
+        let report = engine.switch_mode(&request).await.unwrap();
+
+        assert!(!report.success);
+        assert!(report.rolled_back);
+        assert_eq!(step_names(&report), ["apply", "preflight", "drain", "restart", "rollback"]);
+        assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG);
+        assert_eq!(controller.calls().iter().filter(|call| *call == "restart").count(), 2);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn config_edits_wait_until_rollback_finishes() {
+        let controller = MockController::new(false, 1);
+        let (engine, path) = engine(controller.clone());
+        let request: EngineModeRequest = serde_json::from_str(r#"{"mode":"ips","pairs":[["eth0","eth1"]]}"#).unwrap();
+        let patch = [ConfigPatch { path: "stats.enabled".to_string(), value: Value::String("yes".to_string()) }];
+
+        // 재시작 중에 들어온 변경은 복구가 끝난 뒤 적용되어 복구 대상이 바뀌지 않음
+        let (report, patched) = tokio::join!(engine.switch_mode(&request), async {
+            sleep(Duration::from_millis(10)).await;
+            engine.config.patch(&patch).await
+        });
+        let report = report.unwrap();
+        patched.unwrap();
+
+        assert!(report.rolled_back);
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("copy-mode"), "{}", text);
+        assert!(text.contains("enabled: \"yes\""), "{}", text);
+        cleanup(&path);
+    }
+
+    #[tokio::test]
+    async fn unchanged_config_restarts_without_rollback() {
+        let controller = MockController::new(false, 1);
+        let (engine, path) = engine(controller.clone());
+        // 이전의 관계없는 변경이 남긴 백업
+        fs::write(format!("{}.bak", path.display()), "stale: yes\n").unwrap();
+
+        let report = engine.apply_and_restart("apply_interfaces", |text| Ok(text.to_string())).await.unwrap();
+        assert!(!report.success);
+        assert!(!report.rolled_back);
+        assert_eq!(step_names(&report), ["apply", "preflight", "drain", "restart"]);
+        assert_eq!(report.steps[0].message.as_deref(), Some("Configuration unchanged"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), CONFIG);
+        assert_eq!(controller.calls().iter().filter(|call| *call == "restart").count(), 1);
+        cleanup(&path);
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::IpAddr,
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use maxminddb::{geoip2, Reader};
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tracing::{info, warn};
+
+use crate::models::enrichment::{AssetEntry, AssetInventory, IpEnrichment};
+use crate::models::eve_json_log::EveEvent;
+use crate::utils::cidr::Cidr;
+use crate::utils::config::env_or;
+
+// 보강 정보를 넣는 최상위 키
+pub const ENRICHMENT_KEY: &str = "enrichment";
+
+fn open_database(path: &str, kind: &str) -> Option<Reader<Vec<u8>>> {
+    if path.is_empty() || !Path::new(path).exists() {
+        info!("No {} database at '{}', skipping", kind, path);
+        return None;
+    }
+    match Reader::open_readfile(path) {
+        Ok(reader) => {
+            info!("Loaded {} database {} ({})", kind, path, reader.metadata().database_type);
+            Some(reader)
+        }
+        Err(e) => {
+            warn!("Failed to open {} database {}: {}", kind, path, e);
+            None
+        }
+    }
+}
+
+// 자산 목록 (접두사가 긴 대역 우선)
+fn load_assets(path: &str) -> Vec<(Cidr, AssetEntry)> {
+    if path.is_empty() || !Path::new(path).exists() {
+        info!("No asset inventory at '{}', skipping", path);
+        return Vec::new();
+    }
+    let inventory: AssetInventory = match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_yaml::from_str(&text).map_err(|e| e.to_string())) {
+        Ok(inventory) => inventory,
+        Err(e) => {
+            warn!("Failed to load asset inventory {}: {}", path, e);
+            return Vec::new();
+        }
+    };
+
+    let mut assets: Vec<(Cidr, AssetEntry)> = inventory.assets
+        .into_iter()
+        .filter_map(|asset| match Cidr::parse(&asset.cidr) {
+            Ok(cidr) => Some((cidr, asset)),
+            Err(e) => {
+                warn!("Skipping asset '{}': {}", asset.name, e);
+                None
+            }
+        })
+        .collect();
+    assets.sort_by_key(|(cidr, _)| std::cmp::Reverse(cidr.prefix()));
+    info!("Loaded {} assets from {}", assets.len(), path);
+    assets
+}
+
+// 조회 결과 캐시 (조회 중이면 hostname 없이 기록)
+struct CachedName {
+    hostname: Option<String>,
+    expires: Instant,
+}
+
+// 수신 경로를 막지 않는 역방향 DNS: 캐시에 없으면 백그라운드에서 조회하고 이번 이벤트는 건너뜀
+struct ReverseDns {
+    cache: Mutex<HashMap<IpAddr, CachedName>>,
+    permits: Arc<Semaphore>,
+    capacity: usize,
+    ttl: Duration,
+    timeout: Duration,
+}
+
+impl ReverseDns {
+    fn hostname(self: &Arc<Self>, address: IpAddr) -> Option<String> {
+        let now = Instant::now();
+        {
+            let mut cache = self.lock();
+            if let Some(entry) = cache.get(&address)
+                && entry.expires > now {
+                return entry.hostname.clone();
+            }
+            let Ok(permit) = Arc::clone(&self.permits).try_acquire_owned() else {
+                return None;
+            };
+            if cache.len() >= self.capacity {
+                cache.retain(|_, entry| entry.expires > now);
+                if cache.len() >= self.capacity {
+                    cache.clear();
+                }
+            }
+            // 조회가 끝날 때까지 같은 주소를 다시 조회하지 않도록 자리 표시
+            cache.insert(address, CachedName { hostname: None, expires: now + self.timeout });
+
+            // 제한 시간이 지나도 블로킹 조회는 계속되므로 허가는 조회가 실제로 끝날 때 반납
+            let resolver = Arc::clone(self);
+            tokio::spawn(async move {
+                let lookup = tokio::task::spawn_blocking(move || {
+                    let hostname = dns_lookup::lookup_addr(&address).ok();
+                    drop(permit);
+                    hostname
+                });
+                let hostname = tokio::time::timeout(resolver.timeout, lookup)
+                    .await
+                    .ok()
+                    .and_then(Result::ok)
+                    .flatten();
+                resolver.lock().insert(address, CachedName {
+                    hostname,
+                    expires: Instant::now() + resolver.ttl,
+                });
+            });
+        }
+        None
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<IpAddr, CachedName>> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+// EVE 이벤트의 src_ip/dest_ip에 GeoIP, ASN, 자산 정보, 역방향 DNS를 덧붙임
+// 모든 자료는 로컬 파일에서 읽으므로 오프라인에서도 동작 (역방향 DNS는 기본 비활성)
+pub struct Enricher {
+    event_types: Vec<String>,
+    city: Option<Reader<Vec<u8>>>,
+    asn: Option<Reader<Vec<u8>>>,
+    assets: Vec<(Cidr, AssetEntry)>,
+    rdns: Option<Arc<ReverseDns>>,
+}
+
+impl Enricher {
+    pub fn from_env() -> Self {
+        let event_types = env_or("ENRICH_EVENT_TYPES", "alert,drop".to_string())
+            .split(',')
+            .map(|event_type| event_type.trim().to_string())
+            .filter(|event_type| !event_type.is_empty())
+            .collect();
+        let rdns = env_or("RDNS_ENABLED", false).then(|| {
+            Arc::new(ReverseDns {
+                cache: Mutex::new(HashMap::new()),
+                permits: Arc::new(Semaphore::new(env_or("RDNS_MAX_INFLIGHT", 8usize).max(1))),
+                capacity: env_or("RDNS_CACHE_SIZE", 10_000usize).max(1),
+                ttl: Duration::from_secs(env_or("RDNS_CACHE_TTL_SECS", 3600u64)),
+                timeout: Duration::from_millis(env_or("RDNS_TIMEOUT_MS", 1000u64).max(1)),
+            })
+        });
+
+        Enricher {
+            event_types,
+            city: open_database(&env_or("GEOIP_CITY_DB", "/logs/geoip/GeoLite2-City.mmdb".to_string()), "GeoIP city"),
+            asn: open_database(&env_or("GEOIP_ASN_DB", "/logs/geoip/GeoLite2-ASN.mmdb".to_string()), "GeoIP ASN"),
+            assets: load_assets(&env_or("ASSET_INVENTORY_FILE", "/logs/assets.yaml".to_string())),
+            rdns,
+        }
+    }
+
+    // 자산 목록만 쓰는 보강기 (다른 서비스 테스트용)
+    #[cfg(test)]
+    pub fn with_assets(path: &str) -> Self {
+        Enricher {
+            event_types: Vec::new(),
+            city: None,
+            asn: None,
+            assets: load_assets(path),
+            rdns: None,
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.city.is_some() || self.asn.is_some() || !self.assets.is_empty() || self.rdns.is_some()
+    }
+
+    pub fn enrich(&self, event: &mut EveEvent) {
+        if !self.is_enabled()
+            || !self.event_types.iter().any(|event_type| event_type == "*" || event_type == event.event_type()) {
+            return;
+        }
+
+        let common = event.common();
+        let mut enrichment = serde_json::Map::new();
+        for (key, address) in [("src_ip", &common.src_ip), ("dest_ip", &common.dest_ip)] {
+            let Some(address) = address.as_deref().and_then(|address| address.parse::<IpAddr>().ok()) else {
+                continue;
+            };
+            let info = self.lookup(address);
+            if !info.is_empty()
+                && let Ok(value) = serde_json::to_value(&info) {
+                enrichment.insert(key.to_string(), value);
+            }
+        }
+        if !enrichment.is_empty() {
+            event.extra_mut().insert(ENRICHMENT_KEY.to_string(), Value::Object(enrichment));
+        }
+    }
+
+    // 주소가 속한 자산 (가장 좁은 대역)
+    pub fn asset(&self, address: &IpAddr) -> Option<&AssetEntry> {
+        self.assets.iter()
+            .find(|(cidr, _)| cidr.contains(address))
+            .map(|(_, asset)| asset)
+    }
+
+    fn lookup(&self, address: IpAddr) -> IpEnrichment {
+        let mut info = IpEnrichment::default();
+
+        if let Some(reader) = &self.city
+            && let Ok(Some(city)) = reader.lookup(address).and_then(|result| result.decode::<geoip2::City>()) {
+            info.country_code = city.country.iso_code.map(str::to_string);
+            info.country = city.country.names.english.map(str::to_string);
+            info.city = city.city.names.english.map(str::to_string);
+            info.latitude = city.location.latitude;
+            info.longitude = city.location.longitude;
+        }
+        if let Some(reader) = &self.asn
+            && let Ok(Some(asn)) = reader.lookup(address).and_then(|result| result.decode::<geoip2::Asn>()) {
+            info.asn = asn.autonomous_system_number;
+            info.as_org = asn.autonomous_system_organization.map(str::to_string);
+        }
+        info.asset = self.asset(&address).cloned();
+        if let Some(rdns) = &self.rdns {
+            info.hostname = rdns.hostname(address);
+        }
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample_event;
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    fn enricher(rdns: Option<Arc<ReverseDns>>) -> Enricher {
+        Enricher {
+            event_types: vec!["alert".to_string()],
+            city: open_database(&fixture("geoip-city.mmdb"), "GeoIP city"),
+            asn: open_database(&fixture("geoip-asn.mmdb"), "GeoIP ASN"),
+            assets: load_assets(&fixture("assets.yaml")),
+            rdns,
+        }
+    }
+
+    #[test]
+    fn alerts_get_geoip_asn_and_asset() {
+        let mut alert = sample_event("alert");
+        enricher(None).enrich(&mut alert);
+        let enrichment = &alert.extra()[ENRICHMENT_KEY];
+
+        let dest = &enrichment["dest_ip"];
+        assert_eq!(dest["country_code"], "US");
+        assert_eq!(dest["city"], "Norwell");
+        assert_eq!(dest["asn"], 15133);
+        assert_eq!(dest["as_org"], "EDGECAST");
+        assert!(dest.get("asset").is_none());
+        // 가장 좁은 대역의 자산
+        let src = &enrichment["src_ip"];
+        assert_eq!(src["asset"]["name"], "build-server");
+        assert!(src.get("country_code").is_none());
+    }
+
+    #[test]
+    fn other_event_types_are_untouched() {
+        let mut flow = sample_event("flow");
+        enricher(None).enrich(&mut flow);
+        assert!(flow.extra().get(ENRICHMENT_KEY).is_none());
+    }
+
+    #[test]
+    fn invalid_asset_ranges_are_skipped() {
+        let assets = load_assets(&fixture("assets.yaml"));
+        let names: Vec<&str> = assets.iter().map(|(_, asset)| asset.name.as_str()).collect();
+        assert_eq!(names, ["build-server", "corp-network"]);
+    }
+
+    #[tokio::test]
+    async fn reverse_dns_holds_its_permit_until_the_lookup_returns() {
+        let rdns = Arc::new(ReverseDns {
+            cache: Mutex::new(HashMap::new()),
+            permits: Arc::new(Semaphore::new(1)),
+            capacity: 16,
+            ttl: Duration::from_secs(60),
+            timeout: Duration::from_secs(5),
+        });
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+
+        // 처음에는 백그라운드 조회만 시작
+        assert_eq!(rdns.hostname(localhost), None);
+        assert_eq!(rdns.permits.available_permits(), 0);
+        // 조회 중에는 다른 주소도 조회를 시작하지 않음
+        assert_eq!(rdns.hostname("127.0.0.2".parse().unwrap()), None);
+        assert!(!rdns.lock().contains_key(&"127.0.0.2".parse().unwrap()));
+
+        for _ in 0..100 {
+            if rdns.lock().get(&localhost).is_some_and(|entry| entry.hostname.is_some()) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(rdns.hostname(localhost).as_deref(), Some("localhost"));
+        assert_eq!(rdns.permits.available_permits(), 1);
+    }
+}
@@ -0,0 +1,386 @@
+use std::{
+    collections::{HashMap, HashSet},
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+
+use tokio::sync::{
+    mpsc::{self, error::{TryRecvError, TrySendError}},
+    Notify,
+};
+
+use crate::models::eve_json_log::EveEvent;
+use crate::models::event_stream::EventStreamQuery;
+use crate::services::metrics::Metrics;
+use crate::utils::cidr::Cidr;
+use crate::utils::config::env_or;
+
+#[derive(Debug)]
+pub enum EventStreamError {
+    // 잘못된 필터
+    Invalid(String),
+    // 구독자 수 한도 초과
+    Busy(String),
+}
+
+impl EventStreamError {
+    pub fn message(&self) -> &str {
+        match self {
+            EventStreamError::Invalid(message) | EventStreamError::Busy(message) => message,
+        }
+    }
+}
+
+enum SignatureMatch {
+    Id(u64),
+    // 소문자로 바꾼 이름 일부
+    Name(String),
+}
+
+// 구독자별 서버 측 필터
+struct StreamFilter {
+    event_types: Option<HashSet<String>>,
+    severities: Option<HashSet<u8>>,
+    ip: Option<Cidr>,
+    signature: Option<SignatureMatch>,
+}
+
+impl StreamFilter {
+    fn parse(query: &EventStreamQuery) -> Result<Self, EventStreamError> {
+        let list = |value: &str| -> Vec<String> {
+            value.split(',')
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect()
+        };
+        let severities = match &query.severity {
+            Some(severity) => Some(list(severity).iter()
+                .map(|item| item.parse::<u8>()
+                    .map_err(|_| EventStreamError::Invalid(format!("Invalid severity '{}'", item))))
+                .collect::<Result<HashSet<_>, _>>()?),
+            None => None,
+        };
+        let ip = query.ip.as_deref()
+            .map(Cidr::parse)
+            .transpose()
+            .map_err(EventStreamError::Invalid)?;
+        let signature = query.signature.as_deref().map(str::trim).map(|signature| match signature.parse::<u64>() {
+            Ok(id) => SignatureMatch::Id(id),
+            Err(_) => SignatureMatch::Name(signature.to_lowercase()),
+        });
+
+        Ok(StreamFilter {
+            event_types: query.event_type.as_deref().map(|event_type| list(event_type).into_iter().collect()),
+            severities,
+            ip,
+            signature,
+        })
+    }
+
+    fn matches(&self, event: &EveEvent) -> bool {
+        if let Some(event_types) = &self.event_types
+            && !event_types.contains(event.event_type()) {
+            return false;
+        }
+        if let Some(ip) = &self.ip {
+            let common = event.common();
+            let matched = [&common.src_ip, &common.dest_ip].into_iter()
+                .flatten()
+                .filter_map(|address| address.parse::<IpAddr>().ok())
+                .any(|address| ip.contains(&address));
+            if !matched {
+                return false;
+            }
+        }
+        if self.severities.is_none() && self.signature.is_none() {
+            return true;
+        }
+        // severity/시그니처 조건은 경보에만 해당
+        let Some(alert) = event.alert() else {
+            return false;
+        };
+        if let Some(severities) = &self.severities
+            && !severities.contains(&alert.severity) {
+            return false;
+        }
+        match &self.signature {
+            Some(SignatureMatch::Id(id)) => alert.signature_id == *id,
+            Some(SignatureMatch::Name(name)) => alert.signature.to_lowercase().contains(name),
+            None => true,
+        }
+    }
+}
+
+// 버퍼가 차서 버린 이벤트 수 (버리는 즉시 구독자를 깨워 다음 이벤트를 기다리지 않고 알림)
+#[derive(Default)]
+struct Lag {
+    dropped: AtomicU64,
+    notify: Notify,
+}
+
+struct Subscriber {
+    filter: StreamFilter,
+    sender: mpsc::Sender<Arc<str>>,
+    lag: Arc<Lag>,
+    transport: &'static str,
+}
+
+// 구독 중 받을 항목
+pub enum StreamItem {
+    Event(Arc<str>),
+    // 직전까지 버린 이벤트 수
+    Dropped(u64),
+}
+
+// 구독 하나 (놓으면 구독 해제)
+pub struct Subscription {
+    id: u64,
+    receiver: mpsc::Receiver<Arc<str>>,
+    lag: Arc<Lag>,
+    stream: Arc<EventStream>,
+}
+
+impl Subscription {
+    // 다음 항목 (버퍼에 남은 이벤트를 다 받은 뒤 버린 이벤트가 있으면 그 수를 알림)
+    // 취소해도 항목을 잃지 않음 (select!에서 사용)
+    pub async fn next(&mut self) -> Option<StreamItem> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(event) => return Some(StreamItem::Event(event)),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => {}
+            }
+            let dropped = self.lag.dropped.swap(0, Ordering::Relaxed);
+            if dropped > 0 {
+                return Some(StreamItem::Dropped(dropped));
+            }
+            tokio::select! {
+                event = self.receiver.recv() => return event.map(StreamItem::Event),
+                _ = self.lag.notify.notified() => {}
+            }
+        }
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.stream.unsubscribe(self.id);
+    }
+}
+
+// 수신 경로의 이벤트를 UI 실시간 구독자(SSE, WebSocket)에게 나눠 줌
+// 구독자마다 버퍼가 있고, 버퍼가 찬 느린 구독자에게는 기다리지 않고 버린 뒤 개수를 셈
+pub struct EventStream {
+    metrics: Arc<Metrics>,
+    subscribers: Mutex<HashMap<u64, Subscriber>>,
+    next_id: AtomicU64,
+    max_subscribers: usize,
+    buffer: usize,
+}
+
+impl EventStream {
+    pub fn from_env(metrics: Arc<Metrics>) -> Self {
+        EventStream {
+            metrics,
+            subscribers: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            max_subscribers: env_or("EVENT_STREAM_MAX_SUBSCRIBERS", 16usize),
+            buffer: env_or("EVENT_STREAM_BUFFER", 256usize).max(1),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<u64, Subscriber>> {
+        self.subscribers.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub fn subscribe(self: &Arc<Self>, query: &EventStreamQuery, transport: &'static str) -> Result<Subscription, EventStreamError> {
+        let filter = StreamFilter::parse(query)?;
+        let mut subscribers = self.lock();
+        if subscribers.len() >= self.max_subscribers {
+            return Err(EventStreamError::Busy(format!(
+                "Too many live stream subscribers (max {})", self.max_subscribers)));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::channel(self.buffer);
+        let lag = Arc::new(Lag::default());
+        subscribers.insert(id, Subscriber { filter, sender, lag: lag.clone(), transport });
+        self.metrics.stream_subscribers(subscribers.len());
+
+        Ok(Subscription { id, receiver, lag, stream: Arc::clone(self) })
+    }
+
+    fn unsubscribe(&self, id: u64) {
+        let mut subscribers = self.lock();
+        subscribers.remove(&id);
+        self.metrics.stream_subscribers(subscribers.len());
+    }
+
+    // 필터에 맞는 구독자에게 전달 (직렬화는 맞는 구독자가 있을 때 한 번만)
+    pub fn publish(&self, event: &EveEvent) {
+        let subscribers = self.lock();
+        if subscribers.is_empty() {
+            return;
+        }
+        let mut line: Option<Arc<str>> = None;
+        for subscriber in subscribers.values() {
+            if !subscriber.filter.matches(event) {
+                continue;
+            }
+            if line.is_none() {
+                match serde_json::to_string(event) {
+                    Ok(text) => line = Some(text.into()),
+                    Err(_) => return,
+                }
+            }
+            if let Some(line) = &line
+                && let Err(TrySendError::Full(_)) = subscriber.sender.try_send(line.clone()) {
+                subscriber.lag.dropped.fetch_add(1, Ordering::Relaxed);
+                subscriber.lag.notify.notify_one();
+                self.metrics.stream_dropped(subscriber.transport);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::models::eve_json_log::test_support::{sample, sample_event};
+
+    fn stream(buffer: usize, max_subscribers: usize) -> Arc<EventStream> {
+        Arc::new(EventStream {
+            metrics: Arc::new(Metrics::new()),
+            subscribers: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+            max_subscribers,
+            buffer,
+        })
+    }
+
+    fn query(pairs: &[(&str, &str)]) -> EventStreamQuery {
+        let mut query = EventStreamQuery::default();
+        for (key, value) in pairs {
+            let value = Some(value.to_string());
+            match *key {
+                "event_type" => query.event_type = value,
+                "severity" => query.severity = value,
+                "ip" => query.ip = value,
+                "signature" => query.signature = value,
+                _ => unreachable!(),
+            }
+        }
+        query
+    }
+
+    fn flow(seq: u64) -> EveEvent {
+        let mut value = sample("flow");
+        value["flow_id"] = seq.into();
+        EveEvent::try_from(value).unwrap()
+    }
+
+    async fn next(subscription: &mut Subscription) -> Option<StreamItem> {
+        tokio::time::timeout(Duration::from_secs(1), subscription.next()).await
+            .expect("no stream item")
+    }
+
+    fn flow_id(item: Option<StreamItem>) -> u64 {
+        match item {
+            Some(StreamItem::Event(line)) => serde_json::from_str::<Value>(&line).unwrap()["flow_id"].as_u64().unwrap(),
+            Some(StreamItem::Dropped(dropped)) => panic!("unexpected notice for {} dropped events", dropped),
+            None => panic!("stream closed"),
+        }
+    }
+
+    #[tokio::test]
+    async fn lagged_subscribers_hear_about_drops_without_waiting_for_the_next_event() {
+        let stream = stream(1, 4);
+        let mut subscription = stream.subscribe(&EventStreamQuery::default(), "sse").unwrap();
+
+        // 버퍼 하나가 찬 뒤 두 개는 버림, 그 뒤로 이벤트가 더 오지 않아도 알림
+        for seq in 0..3 {
+            stream.publish(&flow(seq));
+        }
+        assert_eq!(flow_id(next(&mut subscription).await), 0);
+        assert!(matches!(next(&mut subscription).await, Some(StreamItem::Dropped(2))));
+        assert!(tokio::time::timeout(Duration::from_millis(50), subscription.next()).await.is_err());
+
+        stream.publish(&flow(3));
+        assert_eq!(flow_id(next(&mut subscription).await), 3);
+    }
+
+    #[tokio::test]
+    async fn a_waiting_subscriber_is_woken_by_the_drop_notice() {
+        let stream = stream(1, 4);
+        let mut subscription = stream.subscribe(&EventStreamQuery::default(), "ws").unwrap();
+        stream.publish(&flow(0));
+        assert_eq!(flow_id(next(&mut subscription).await), 0);
+
+        // 구독자가 기다리는 동안 버퍼를 채우고 넘침
+        let waiting = tokio::spawn(async move {
+            let first = flow_id(next(&mut subscription).await);
+            let notice = next(&mut subscription).await;
+            (first, notice)
+        });
+        stream.publish(&flow(1));
+        stream.publish(&flow(2));
+        let (first, notice) = waiting.await.unwrap();
+        assert_eq!(first, 1);
+        assert!(matches!(notice, Some(StreamItem::Dropped(1))));
+    }
+
+    #[tokio::test]
+    async fn subscribers_only_get_matching_events() {
+        let stream = stream(16, 16);
+        let cases = [
+            (query(&[("event_type", "flow, dns")]), vec!["flow", "dns"]),
+            (query(&[("severity", "1,2")]), vec!["alert"]),
+            (query(&[("severity", "3")]), vec![]),
+            (query(&[("signature", "2100498")]), vec!["alert"]),
+            (query(&[("signature", "attack_response")]), vec!["alert"]),
+            (query(&[("ip", "93.184.216.0/24")]), vec!["alert", "flow"]),
+            (query(&[("ip", "10.0.0.1")]), vec!["dns"]),
+            (query(&[("ip", "192.0.2.0/24")]), vec![]),
+            (query(&[("event_type", "alert"), ("severity", "2")]), vec!["alert"]),
+        ];
+        let mut subscriptions: Vec<(Subscription, Vec<&str>)> = cases.into_iter()
+            .map(|(query, expected)| (stream.subscribe(&query, "sse").unwrap(), expected))
+            .collect();
+
+        for event_type in ["alert", "flow", "dns"] {
+            stream.publish(&sample_event(event_type));
+        }
+        for (subscription, expected) in &mut subscriptions {
+            let mut received = Vec::new();
+            while let Ok(Some(StreamItem::Event(line))) =
+                tokio::time::timeout(Duration::from_millis(20), subscription.next()).await {
+                let value: Value = serde_json::from_str(&line).unwrap();
+                received.push(value["event_type"].as_str().unwrap().to_string());
+            }
+            assert_eq!(&received, expected);
+        }
+    }
+
+    #[test]
+    fn invalid_filters_are_rejected() {
+        let stream = stream(1, 4);
+        for pairs in [[("severity", "high")], [("ip", "not-an-ip")]] {
+            assert!(matches!(stream.subscribe(&query(&pairs), "sse"), Err(EventStreamError::Invalid(_))));
+        }
+    }
+
+    #[test]
+    fn subscriber_limit_is_released_when_a_subscription_is_dropped() {
+        let stream = stream(1, 1);
+        let first = stream.subscribe(&EventStreamQuery::default(), "sse").unwrap();
+        assert!(matches!(stream.subscribe(&EventStreamQuery::default(), "ws"), Err(EventStreamError::Busy(_))));
+        drop(first);
+        assert!(stream.subscribe(&EventStreamQuery::default(), "ws").is_ok());
+    }
+}
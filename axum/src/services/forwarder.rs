@@ -0,0 +1,895 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+use tokio::sync::{Notify, Semaphore};
+use tracing::{error, info, warn};
+
+use crate::models::eve_json_log::{EveEvent, EveIngestResult, EveItemError, EveItemStatus};
+use crate::models::spool::ForwardStatus;
+use crate::services::aggregation::AlertAggregator;
+use crate::services::alert_stats::AlertStats;
+use crate::services::alert_store::AlertStore;
+use crate::services::central::CentralApi;
+use crate::services::enrichment::Enricher;
+use crate::services::event_stream::EventStream;
+use crate::services::metrics::Metrics;
+use crate::services::policy::PolicyEngine;
+use crate::services::scoring::AlertScorer;
+use crate::services::sink::SinkManager;
+use crate::services::sink_route::RoutedEvent;
+use crate::services::spool::{Position, Spool};
+use crate::utils::config::env_or;
+use crate::utils::random::random_u64;
+
+// 보관 기간 초과 세그먼트 정리 주기
+const AGE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+// 경보 롤업 확인 주기
+const ROLLUP_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+// 적재 실패 사유
+#[derive(Debug)]
+pub enum ForwardError {
+    // 중앙 API 주소 미설정
+    Config(String),
+    // 스풀을 열지 못함
+    Spool(String),
+}
+
+impl ForwardError {
+    pub fn message(&self) -> &str {
+        match self {
+            ForwardError::Config(message) | ForwardError::Spool(message) => message,
+        }
+    }
+}
+
+// 전송 경로 (우선 전송 경보는 별도 스풀에 기록하고 먼저 전송)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lane {
+    Priority = 0,
+    Normal = 1,
+}
+
+// 항목별 처리 결과
+enum Disposition {
+    Spool(Lane, String),
+    // 중앙 API로는 보내지 않고 다른 전달 대상만 받음
+    Routed,
+    Filtered,
+    Aggregated,
+}
+
+// 전송 중인 배치 (같은 경로 안에서 순서대로 확인되어야 스풀 커서가 전진)
+struct InFlight {
+    lane: Lane,
+    end: Position,
+    events: u64,
+    done: bool,
+}
+
+#[derive(Default)]
+struct Delivery {
+    next_id: u64,
+    in_flight: BTreeMap<u64, InFlight>,
+    // 재시도 중인 배치 수
+    failing: usize,
+    // 밀린 이벤트를 재전송 중 (한 배치씩 순서대로 전송)
+    replaying: bool,
+    attempts: u32,
+    last_error: Option<String>,
+}
+
+// 전달과 별도로 센서에서 이벤트를 보여 주는 기능 (로컬 저장소, 통계, 실시간 구독)
+pub struct LocalViews {
+    pub store: Arc<AlertStore>,
+    pub stats: Arc<AlertStats>,
+    pub stream: Arc<EventStream>,
+}
+
+// 수신과 전달을 분리하는 EVE 이벤트 전달기
+// 이벤트는 디스크 스풀에 먼저 기록되고, 배치 크기 또는 대기 시간이 차면 묶어서 중앙 API로 전송
+// 전송 실패 시 지수 백오프로 재시도하며 그동안 새 배치는 보내지 않음
+// 연결이 돌아오면 밀린 이벤트를 한 배치씩 순서대로 재전송
+// 점수가 높은 경보는 우선 전송 스풀을 거쳐 밀린 일반 이벤트보다 먼저 전송
+// 수신 단계(정책, 보강, 점수, 집계, 라우팅)와 스풀 순서 관리를 맡고, HTTP 전송은 CentralApi가 담당
+pub struct Forwarder {
+    metrics: Arc<Metrics>,
+    policy: Arc<PolicyEngine>,
+    enricher: Enricher,
+    aggregator: AlertAggregator,
+    scorer: AlertScorer,
+    local: LocalViews,
+    // 중앙 API 외 추가 전달 대상
+    sinks: Arc<SinkManager>,
+    // 중앙 API (주소가 없으면 None)
+    central: Option<CentralApi>,
+    // 경로별 스풀 (Lane 순서)
+    spools: [Spool; 2],
+    // 경로별로 스풀 기록에 실패한 줄 (다음 기록 때 먼저 다시 씀)
+    unspooled: Mutex<[Vec<String>; 2]>,
+    notify: Notify,
+    // 스풀 커서가 전진하면 알림
+    progress: Notify,
+    delivery: Mutex<Delivery>,
+    // 동시에 전송 중인 배치 수 제한
+    permits: Arc<Semaphore>,
+    batch_size: usize,
+    batch_max_wait: Duration,
+    retry_base: Duration,
+    retry_max: Duration,
+}
+
+impl Forwarder {
+    pub fn from_env(
+        metrics: Arc<Metrics>,
+        policy: Arc<PolicyEngine>,
+        enricher: Enricher,
+        aggregator: AlertAggregator,
+        scorer: AlertScorer,
+        local: LocalViews,
+        sinks: Arc<SinkManager>,
+    ) -> Result<Self, ForwardError> {
+        let timeout = Duration::from_secs(env_or("FORWARD_TIMEOUT_SECS", 10u64).max(1));
+        let url = env_or("CENTRAL_API_SERVER_URL", String::new());
+        let central = (!url.is_empty()).then(|| CentralApi::new(url, timeout, metrics.clone()));
+        let spool_dir = PathBuf::from(env_or("SPOOL_DIR", "/logs/spool".to_string()));
+        let open = |dir: PathBuf| Spool::open(
+            dir,
+            env_or("SPOOL_SEGMENT_BYTES", 8 * 1024 * 1024u64),
+            env_or("SPOOL_MAX_BYTES", 1024 * 1024 * 1024u64),
+            Duration::from_secs(env_or("SPOOL_MAX_AGE_HOURS", 72u64) * 3600),
+            env_or("SPOOL_FSYNC", true),
+        )
+        .map_err(|e| ForwardError::Spool(format!("Failed to open EVE spool: {}", e)));
+        let spools = [open(spool_dir.join("priority"))?, open(spool_dir)?];
+        let pending: u64 = spools.iter().map(Spool::pending_events).sum();
+        metrics.forward_queue(pending);
+        // 이전 실행에서 남은 이벤트는 순서대로 재전송
+        let delivery = Delivery {
+            replaying: pending > 0,
+            ..Delivery::default()
+        };
+
+        Ok(Forwarder {
+            metrics,
+            policy,
+            enricher,
+            aggregator,
+            scorer,
+            local,
+            sinks,
+            central,
+            spools,
+            unspooled: Mutex::new(Default::default()),
+            notify: Notify::new(),
+            progress: Notify::new(),
+            delivery: Mutex::new(delivery),
+            permits: Arc::new(Semaphore::new(env_or("FORWARD_CONCURRENCY", 4usize).max(1))),
+            batch_size: env_or("FORWARD_BATCH_SIZE", 100usize).max(1),
+            batch_max_wait: Duration::from_millis(env_or("FORWARD_BATCH_MAX_WAIT_MS", 1000u64)),
+            retry_base: Duration::from_millis(env_or("FORWARD_RETRY_BASE_MS", 500u64).max(1)),
+            retry_max: Duration::from_secs(env_or("FORWARD_RETRY_MAX_SECS", 60u64).max(1)),
+        })
+    }
+
+    // 이벤트를 스풀에 기록
+    // 이전에 기록하지 못한 줄을 먼저 다시 쓰고, 그래도 실패하면 아무것도 처리하지 않고 전부 failed
+    // (정책 건수, 로컬 저장소, 통계, 구독, 싱크에 반영되지 않으므로 호출자가 그대로 다시 보내도 중복 없음)
+    // 처리를 시작한 뒤의 기록 실패는 메모리에 남겨 다음 기록 때 다시 씀 (같은 이벤트를 다시 받지 않도록 accepted)
+    pub async fn enqueue(self: &Arc<Self>, items: Vec<Value>) -> Result<EveIngestResult, ForwardError> {
+        self.ingest(items, true).await
+    }
+
+    // 오프라인 분석(pcap 작업) 결과 전달
+    // 실시간 이벤트가 아니므로 로컬 저장소, 통계, 실시간 구독에 넣지 않고 경보 집계도 거치지 않음
+    pub async fn enqueue_replay(self: &Arc<Self>, items: Vec<Value>) -> Result<EveIngestResult, ForwardError> {
+        self.ingest(items, false).await
+    }
+
+    async fn ingest(self: &Arc<Self>, items: Vec<Value>, live: bool) -> Result<EveIngestResult, ForwardError> {
+        if self.central.is_none() && self.sinks.is_empty() {
+            error!("CENTRAL_API_SERVER_URL is not set");
+            return Err(ForwardError::Config("서버 구성 오류".to_string()));
+        }
+        if let Err(e) = self.blocking(Forwarder::retry_unspooled).await {
+            error!("{}", e);
+            let errors: Vec<EveItemError> = (0..items.len())
+                .map(|index| EveItemError { index, status: EveItemStatus::Failed, error: e.clone() })
+                .collect();
+            return Ok(EveIngestResult {
+                accepted: 0,
+                filtered: 0,
+                aggregated: 0,
+                rejected: 0,
+                failed: errors.len(),
+                queue_depth: self.pending_events(),
+                errors,
+            });
+        }
+
+        let mut lanes: [Vec<String>; 2] = Default::default();
+        let mut errors = Vec::new();
+        let mut routed = 0;
+        let mut filtered = 0;
+        let mut aggregated = 0;
+        let mut stored = Vec::new();
+        let mut sink_batch = self.sinks.batch();
+        for (index, item) in items.into_iter().enumerate() {
+            let event_type = item.get("event_type")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown")
+                .to_string();
+            self.metrics.eve_received(&event_type);
+
+            // event_type별 EVE 이벤트 형식으로 변환 후 정책 적용, 유지할 이벤트만 보강/점수 계산
+            // 로컬 저장소, 통계, 실시간 구독에는 집계 전 이벤트를 넘기고, 전달은 경보 집계 뒤
+            // 라우팅 조건에 맞는 전달 대상이 하나도 없으면 filtered
+            let disposition = EveEvent::try_from(item).and_then(|mut event| {
+                if !self.policy.evaluate(&event) {
+                    return Ok(Disposition::Filtered);
+                }
+                self.enricher.enrich(&mut event);
+                let lane = if self.scorer.score(&mut event, &self.enricher) {
+                    Lane::Priority
+                } else {
+                    Lane::Normal
+                };
+                if live {
+                    stored.extend(self.local.store.row(&event));
+                    self.local.stats.record(&event);
+                    self.local.stream.publish(&event);
+                    if !self.aggregator.observe(&event) {
+                        return Ok(Disposition::Aggregated);
+                    }
+                }
+                let target = RoutedEvent::new(&event);
+                let sunk = self.sinks.collect(&mut sink_batch, &target);
+                if !self.to_central(&target) {
+                    return Ok(if sunk { Disposition::Routed } else { Disposition::Filtered });
+                }
+                serde_json::to_string(&event).map(|line| Disposition::Spool(lane, line))
+            });
+            match disposition {
+                Ok(Disposition::Spool(lane, line)) => lanes[lane as usize].push(line),
+                Ok(Disposition::Routed) => routed += 1,
+                Ok(Disposition::Filtered) => filtered += 1,
+                Ok(Disposition::Aggregated) => aggregated += 1,
+                Err(e) => {
+                    warn!("항목을 EVE 이벤트로 변환 실패: {}", e);
+                    self.metrics.eve_failed(&event_type);
+                    errors.push(EveItemError {
+                        index,
+                        status: EveItemStatus::Rejected,
+                        error: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let rejected = errors.len();
+        let accepted = routed + lanes.iter().map(Vec::len).sum::<usize>();
+        self.local.store.insert(stored);
+        self.blocking(move |forwarder| {
+            forwarder.sinks.append(sink_batch);
+            for (lane, lines) in [Lane::Priority, Lane::Normal].into_iter().zip(lanes) {
+                forwarder.write_lane(lane, lines);
+            }
+        }).await;
+        let queue_depth = self.pending_events();
+        self.metrics.forward_queue(queue_depth);
+
+        Ok(EveIngestResult {
+            accepted,
+            filtered,
+            aggregated,
+            rejected,
+            failed: 0,
+            queue_depth,
+            errors,
+        })
+    }
+
+    // 에이전트가 만든 이벤트 (감시 사건 등)를 수신 단계를 거치지 않고 우선 전송 스풀에 기록
+    // 스풀 기록에 실패해도 메모리에 남겨 다음 기록 때 다시 씀
+    pub async fn submit(self: &Arc<Self>, event: Value) -> Result<(), ForwardError> {
+        if self.central.is_none() {
+            return Err(ForwardError::Config("CENTRAL_API_SERVER_URL is not set".to_string()));
+        }
+        let line = serde_json::to_string(&event).map_err(|e| ForwardError::Config(e.to_string()))?;
+        self.blocking(move |forwarder| forwarder.write_lane(Lane::Priority, vec![line])).await;
+        self.metrics.forward_queue(self.pending_events());
+        Ok(())
+    }
+
+    // 디스크 기록(스풀 fsync, 싱크 큐)은 비동기 작업 스레드를 막지 않도록 블로킹 스레드에서
+    async fn blocking<T, F>(self: &Arc<Self>, work: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&Forwarder) -> T + Send + 'static,
+    {
+        let forwarder = Arc::clone(self);
+        tokio::task::spawn_blocking(move || work(&forwarder)).await
+            .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
+
+    fn lock_unspooled(&self) -> std::sync::MutexGuard<'_, [Vec<String>; 2]> {
+        self.unspooled.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 이전에 기록하지 못한 줄 뒤에 이어서 기록 (실패하면 모두 남겨 두고 다음에 다시 씀)
+    fn write_lane(&self, lane: Lane, lines: Vec<String>) {
+        let mut unspooled = self.lock_unspooled();
+        let pending = &mut unspooled[lane as usize];
+        pending.extend(lines);
+        if pending.is_empty() {
+            return;
+        }
+        match self.spool(lane).append(pending) {
+            Ok(_) => {
+                pending.clear();
+                self.notify.notify_one();
+            }
+            Err(e) => error!("Keeping {} unspooled events in memory: {}", pending.len(), e),
+        }
+    }
+
+    // 기록하지 못한 줄이 남아 있으면 다시 기록
+    fn retry_unspooled(&self) -> Result<(), String> {
+        for lane in [Lane::Priority, Lane::Normal] {
+            self.write_lane(lane, Vec::new());
+            let left = self.lock_unspooled()[lane as usize].len();
+            if left > 0 {
+                return Err(format!("{} events are still waiting for the spool", left));
+            }
+        }
+        Ok(())
+    }
+
+    // 구간이 끝난 경보 롤업을 스풀에 기록
+    fn flush_rollups(&self) {
+        let rollups = self.aggregator.flush();
+        let mut sink_batch = self.sinks.batch();
+        let mut lines = Vec::new();
+        for rollup in &rollups {
+            let target = RoutedEvent::new(rollup);
+            self.sinks.collect(&mut sink_batch, &target);
+            if self.to_central(&target)
+                && let Ok(line) = serde_json::to_string(rollup) {
+                lines.push(line);
+            }
+        }
+        self.sinks.append(sink_batch);
+        self.write_lane(Lane::Normal, lines);
+        self.metrics.forward_queue(self.pending_events());
+    }
+
+    // 중앙 API 주소가 있고 중앙 API 라우팅 조건에 맞는지
+    fn to_central(&self, target: &RoutedEvent) -> bool {
+        self.central.is_some() && self.sinks.to_central(target)
+    }
+
+    fn spool(&self, lane: Lane) -> &Spool {
+        &self.spools[lane as usize]
+    }
+
+    // 모든 경로에서 전달 대기 중인 이벤트 수
+    fn pending_events(&self) -> u64 {
+        self.spools.iter().map(Spool::pending_events).sum()
+    }
+
+    pub fn status(&self) -> ForwardStatus {
+        let delivery = self.lock_delivery();
+        ForwardStatus {
+            spool: self.spool(Lane::Normal).status(),
+            priority_spool: self.spool(Lane::Priority).status(),
+            retrying: delivery.failing > 0,
+            replaying: delivery.replaying,
+            retry_attempts: delivery.attempts,
+            last_error: delivery.last_error.clone(),
+        }
+    }
+
+    fn lock_delivery(&self) -> std::sync::MutexGuard<'_, Delivery> {
+        self.delivery.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 우선 전송 경보가 있으면 바로 읽고, 없으면 배치가 찰 때까지 또는 첫 이벤트 이후 최대 대기 시간까지 기다린 뒤 읽음
+    async fn next_batch(&self, cursors: [Position; 2]) -> (Lane, Vec<String>, Position) {
+        let mut first_seen: Option<Instant> = None;
+        loop {
+            match self.spool(Lane::Priority).read(cursors[Lane::Priority as usize], self.batch_size) {
+                Ok((lines, end)) if !lines.is_empty() => return (Lane::Priority, lines, end),
+                Ok(_) => {}
+                Err(e) => error!("Failed to read priority EVE spool: {}", e),
+            }
+            match self.spool(Lane::Normal).read(cursors[Lane::Normal as usize], self.batch_size) {
+                Ok((lines, end)) => {
+                    let ready = match first_seen {
+                        _ if lines.len() >= self.batch_size => true,
+                        Some(seen) => !lines.is_empty() && seen.elapsed() >= self.batch_max_wait,
+                        None => false,
+                    };
+                    if ready {
+                        return (Lane::Normal, lines, end);
+                    }
+                    if !lines.is_empty() && first_seen.is_none() {
+                        first_seen = Some(Instant::now());
+                    }
+                }
+                Err(e) => error!("Failed to read EVE spool: {}", e),
+            }
+
+            let wait = match first_seen {
+                Some(seen) => self.batch_max_wait.saturating_sub(seen.elapsed()),
+                None => AGE_CHECK_INTERVAL,
+            };
+            let _ = tokio::time::timeout(wait, self.notify.notified()).await;
+            if first_seen.is_none() {
+                self.spools.iter().for_each(Spool::enforce_age);
+            }
+        }
+    }
+
+    // 재시도 중인 배치가 있으면 전달될 때까지, 재전송 중이면 앞선 배치가 모두 전달될 때까지 대기
+    async fn wait_ready(&self) {
+        loop {
+            let progress = self.progress.notified();
+            {
+                let delivery = self.lock_delivery();
+                if delivery.failing == 0 && (!delivery.replaying || delivery.in_flight.is_empty()) {
+                    return;
+                }
+            }
+            progress.await;
+        }
+    }
+
+    // 같은 경로의 앞선 배치가 모두 전달될 때까지 대기 (재시도 순서 유지)
+    // 일반 경로는 전송 중인 우선 전송 배치가 먼저 전달되도록 함께 대기
+    async fn wait_turn(&self, lane: Lane, id: u64) {
+        loop {
+            let progress = self.progress.notified();
+            {
+                let delivery = self.lock_delivery();
+                let first = delivery.in_flight.iter()
+                    .find(|(_, batch)| batch.lane == lane)
+                    .map(|(first, _)| *first);
+                let priority_pending = delivery.in_flight.values()
+                    .any(|batch| batch.lane == Lane::Priority && !batch.done);
+                if first == Some(id) && (lane == Lane::Priority || !priority_pending) {
+                    return;
+                }
+            }
+            progress.await;
+        }
+    }
+
+    fn register(&self, lane: Lane, end: Position, events: u64) -> u64 {
+        let mut delivery = self.lock_delivery();
+        let id = delivery.next_id;
+        delivery.next_id += 1;
+        delivery.in_flight.insert(id, InFlight { lane, end, events, done: false });
+        id
+    }
+
+    // 경로별로 앞선 배치가 모두 확인된 구간까지만 스풀 커서 전진
+    fn acknowledge(&self, id: u64) {
+        let mut committed: [Option<(Position, u64)>; 2] = [None, None];
+        {
+            let mut delivery = self.lock_delivery();
+            if let Some(batch) = delivery.in_flight.get_mut(&id) {
+                batch.done = true;
+            }
+            let mut blocked = [false, false];
+            let mut confirmed = Vec::new();
+            for (id, batch) in &delivery.in_flight {
+                let lane = batch.lane as usize;
+                if blocked[lane] {
+                    continue;
+                }
+                if !batch.done {
+                    blocked[lane] = true;
+                    continue;
+                }
+                let events = committed[lane].map_or(0, |(_, events)| events) + batch.events;
+                committed[lane] = Some((batch.end, events));
+                confirmed.push(*id);
+            }
+            for id in confirmed {
+                delivery.in_flight.remove(&id);
+            }
+        }
+        if committed.iter().any(Option::is_some) {
+            for (spool, committed) in self.spools.iter().zip(committed) {
+                if let Some((position, events)) = committed {
+                    spool.commit(position, events);
+                }
+            }
+            let pending = self.pending_events();
+            self.metrics.forward_queue(pending);
+            if pending == 0 {
+                self.lock_delivery().replaying = false;
+            }
+            self.progress.notify_waiters();
+        }
+    }
+
+    // 전달될 때까지 지수 백오프(지터 포함)로 재시도, 재시도는 스풀 순서대로
+    async fn deliver(&self, lane: Lane, id: u64, lines: &[String]) {
+        let mut attempt = 0u32;
+        loop {
+            let sent = match &self.central {
+                Some(central) => central.send_batch(lines).await,
+                None => Ok(()),
+            };
+            match sent {
+                Ok(()) => break,
+                Err(e) => {
+                    attempt += 1;
+                    let delay = self.backoff(attempt);
+                    {
+                        let mut delivery = self.lock_delivery();
+                        if attempt == 1 {
+                            delivery.failing += 1;
+                            delivery.replaying = true;
+                        }
+                        delivery.attempts = delivery.attempts.max(attempt);
+                        delivery.last_error = Some(e.clone());
+                    }
+                    warn!(
+                        "Failed to deliver batch of {} events ({}), retry {} in {}ms",
+                        lines.len(),
+                        e,
+                        attempt,
+                        delay.as_millis()
+                    );
+                    tokio::time::sleep(delay).await;
+                    self.wait_turn(lane, id).await;
+                }
+            }
+        }
+
+        if attempt > 0 {
+            let mut delivery = self.lock_delivery();
+            delivery.failing -= 1;
+            if delivery.failing == 0 {
+                info!("Central API is reachable again, resuming spool replay");
+                delivery.attempts = 0;
+                delivery.last_error = None;
+            }
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.retry_base.saturating_mul(1u32 << attempt.saturating_sub(1).min(20));
+        let ceiling = exponential.min(self.retry_max);
+        // 절반은 고정, 나머지 절반은 무작위 (동시 재접속 분산)
+        let half = ceiling / 2;
+        let jitter = random_u64() % (half.as_millis() as u64 + 1);
+        half + Duration::from_millis(jitter)
+    }
+}
+
+// 스풀에서 순서대로 배치를 읽어 동시 전송 수 한도 안에서 전송
+pub fn spawn_forwarder(forwarder: Arc<Forwarder>) {
+    info!(
+        "Forwarding EVE events in batches of {} (max wait {}ms)",
+        forwarder.batch_size,
+        forwarder.batch_max_wait.as_millis()
+    );
+    if forwarder.aggregator.is_enabled() {
+        let forwarder = Arc::clone(&forwarder);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(ROLLUP_FLUSH_INTERVAL);
+            loop {
+                ticker.tick().await;
+                forwarder.blocking(Forwarder::flush_rollups).await;
+            }
+        });
+    }
+    tokio::spawn(async move {
+        let mut cursors = [
+            forwarder.spool(Lane::Priority).committed(),
+            forwarder.spool(Lane::Normal).committed(),
+        ];
+        loop {
+            let (lane, lines, end) = forwarder.next_batch(cursors).await;
+            let Ok(permit) = Arc::clone(&forwarder.permits).acquire_owned().await else {
+                return;
+            };
+            forwarder.wait_ready().await;
+            // 밀린 이벤트를 다 읽었으면 다시 동시 전송 (일반 경로는 우선 경로가 비어 있을 때만 읽음)
+            if lane == Lane::Normal && lines.len() < forwarder.batch_size {
+                forwarder.lock_delivery().replaying = false;
+            }
+
+            cursors[lane as usize] = end;
+            let id = forwarder.register(lane, end, lines.len() as u64);
+            let forwarder = Arc::clone(&forwarder);
+            tokio::spawn(async move {
+                forwarder.deliver(lane, id, &lines).await;
+                forwarder.acknowledge(id);
+                drop(permit);
+            });
+        }
+    });
+}
+
+// 테스트용 전달기 (임시 디렉터리 스풀, 전송 태스크는 spawn_forwarder로 따로 띄움)
+#[cfg(test)]
+pub mod test_support {
+    use std::path::Path;
+
+    use super::*;
+
+    pub fn forwarder(dir: &Path, url: Option<&str>, batch_size: usize) -> (Arc<Forwarder>, LocalViews) {
+        let metrics = Arc::new(Metrics::new());
+        let open = |dir: PathBuf| Spool::open(dir, 1 << 20, 1 << 30, Duration::from_secs(3600), false).unwrap();
+        let event_types = ["alert".to_string()].into_iter().collect();
+        let store = Arc::new(AlertStore::new(
+            dir.join("alerts.db").to_str().unwrap(), event_types, Duration::from_secs(3600), 1 << 20));
+        let stats = Arc::new(AlertStats::from_env());
+        let stream = Arc::new(EventStream::from_env(metrics.clone()));
+        let spools = [open(dir.join("spool").join("priority")), open(dir.join("spool"))];
+        // 이전 실행에서 남은 이벤트는 from_env와 같이 재전송 상태로 시작
+        let delivery = Delivery {
+            replaying: spools.iter().any(|spool| spool.pending_events() > 0),
+            ..Delivery::default()
+        };
+
+        let forwarder = Forwarder {
+            metrics: metrics.clone(),
+            policy: Arc::new(PolicyEngine::from_env(metrics.clone())),
+            enricher: Enricher::from_env(),
+            aggregator: AlertAggregator::from_env(metrics.clone()),
+            scorer: AlertScorer::from_env(),
+            local: LocalViews { store: store.clone(), stats: stats.clone(), stream: stream.clone() },
+            sinks: Arc::new(SinkManager::from_env(metrics.clone())),
+            central: url.map(|url| CentralApi::new(url.to_string(), Duration::from_secs(5), metrics.clone())),
+            spools,
+            unspooled: Mutex::new(Default::default()),
+            notify: Notify::new(),
+            progress: Notify::new(),
+            delivery: Mutex::new(delivery),
+            // 배치를 하나씩 보내 전송 순서를 확인할 수 있도록
+            permits: Arc::new(Semaphore::new(1)),
+            batch_size,
+            batch_max_wait: Duration::from_millis(50),
+            retry_base: Duration::from_millis(20),
+            retry_max: Duration::from_millis(100),
+        };
+        (Arc::new(forwarder), LocalViews { store, stats, stream })
+    }
+
+    // 스풀에 쌓인 전달 대기 이벤트 수
+    pub fn pending(forwarder: &Forwarder) -> u64 {
+        forwarder.pending_events()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use axum::{extract::State, http::StatusCode, routing::post, Router};
+    use serde_json::json;
+
+    use super::test_support::{forwarder, pending};
+    use super::*;
+    use crate::models::alert_stats::AlertStatsQuery;
+    use crate::models::event_stream::EventStreamQuery;
+    use crate::services::event_stream::StreamItem;
+
+    // 받은 요청 본문과 응답 상태를 기록하는 가짜 중앙 API
+    #[derive(Default)]
+    struct Central {
+        requests: Mutex<Vec<(u16, String)>>,
+        // 차례로 돌려줄 상태 코드 (비면 200)
+        responses: Mutex<VecDeque<u16>>,
+    }
+
+    impl Central {
+        fn requests(&self) -> Vec<(u16, String)> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        // 받아들여진 본문의 seq를 보낸 순서대로
+        fn delivered(&self) -> Vec<u64> {
+            self.requests().iter()
+                .filter(|(status, _)| *status == 200)
+                .flat_map(|(_, body)| match serde_json::from_str::<Value>(body).unwrap() {
+                    Value::Array(events) => events,
+                    event => vec![event],
+                })
+                .map(|event| event["seq"].as_u64().unwrap())
+                .collect()
+        }
+    }
+
+    async fn receive(State(central): State<Arc<Central>>, body: String) -> StatusCode {
+        let status = central.responses.lock().unwrap().pop_front().unwrap_or(200);
+        central.requests.lock().unwrap().push((status, body));
+        StatusCode::from_u16(status).unwrap()
+    }
+
+    async fn central(responses: &[u16]) -> (String, Arc<Central>) {
+        let central = Arc::new(Central::default());
+        central.responses.lock().unwrap().extend(responses);
+        let app = Router::new().route("/", post(receive)).with_state(central.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, central)
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("forwarder-test-{}-{}", std::process::id(), random_u64()))
+    }
+
+    fn events(range: std::ops::Range<u64>) -> Vec<Value> {
+        range.map(|seq| json!({"event_type": "smb", "seq": seq})).collect()
+    }
+
+    // 스풀이 빌 때까지 대기
+    async fn drained(forwarder: &Forwarder) {
+        for _ in 0..300 {
+            if pending(forwarder) == 0 {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("{} events were not delivered", pending(forwarder));
+    }
+
+    #[tokio::test]
+    async fn events_are_sent_in_batches() {
+        let dir = temp_dir();
+        let (url, central) = central(&[]).await;
+        let (forwarder, _) = forwarder(&dir, Some(&url), 2);
+
+        let result = forwarder.enqueue(events(0..5)).await.unwrap();
+        assert_eq!((result.accepted, result.queue_depth), (5, 5));
+        spawn_forwarder(forwarder.clone());
+        drained(&forwarder).await;
+
+        // 가득 찬 배치 둘은 배열로, 남은 하나는 대기 시간이 지난 뒤 단일 객체로
+        let bodies: Vec<Value> = central.requests().iter()
+            .map(|(_, body)| serde_json::from_str(body).unwrap())
+            .collect();
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(bodies[0].as_array().map(Vec::len), Some(2));
+        assert_eq!(bodies[1].as_array().map(Vec::len), Some(2));
+        assert!(bodies[2].is_object());
+        assert_eq!(central.delivered(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(forwarder.spool(Lane::Normal).status().pending_events, 0);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_batches_are_retried_in_order() {
+        let dir = temp_dir();
+        let (url, central) = central(&[503, 503]).await;
+        let (forwarder, _) = forwarder(&dir, Some(&url), 2);
+
+        forwarder.enqueue(events(0..4)).await.unwrap();
+        spawn_forwarder(forwarder.clone());
+        drained(&forwarder).await;
+
+        // 같은 배치를 받아들여질 때까지 다시 보낸 뒤 다음 배치
+        let requests = central.requests();
+        let statuses: Vec<u16> = requests.iter().map(|(status, _)| *status).collect();
+        assert_eq!(statuses, vec![503, 503, 200, 200]);
+        assert_eq!(requests[0].1, requests[2].1);
+        assert_eq!(central.delivered(), vec![0, 1, 2, 3]);
+
+        let status = forwarder.status();
+        assert!(!status.retrying && !status.replaying);
+        assert_eq!((status.retry_attempts, status.last_error), (0, None));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejected_batches_are_split_and_only_rejected_events_dropped() {
+        let dir = temp_dir();
+        let (url, central) = central(&[400, 200, 422, 200]).await;
+        let (forwarder, _) = forwarder(&dir, Some(&url), 3);
+
+        forwarder.enqueue(events(0..3)).await.unwrap();
+        spawn_forwarder(forwarder.clone());
+        drained(&forwarder).await;
+
+        assert_eq!(central.requests().len(), 4);
+        assert_eq!(central.delivered(), vec![0, 2]);
+        assert!(!forwarder.status().retrying);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn backoff_grows_to_the_cap_with_jitter() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, None, 1);
+        for attempt in 1..40 {
+            let ceiling = forwarder.retry_base
+                .saturating_mul(1u32 << (attempt - 1).min(20))
+                .min(forwarder.retry_max);
+            for _ in 0..20 {
+                let delay = forwarder.backoff(attempt);
+                assert!(delay >= ceiling / 2 && delay <= ceiling, "attempt {}: {:?}", attempt, delay);
+            }
+        }
+        assert_eq!(forwarder.retry_base, Duration::from_millis(20));
+        assert!(forwarder.backoff(1) <= Duration::from_millis(20));
+        assert!(forwarder.backoff(30) >= forwarder.retry_max / 2);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn spooled_events_are_delivered_after_restart() {
+        let dir = temp_dir();
+        let (url, central) = central(&[]).await;
+        {
+            // 전송 태스크 없이 스풀에만 기록하고 종료
+            let (forwarder, _) = forwarder(&dir, Some(&url), 10);
+            forwarder.enqueue(events(0..3)).await.unwrap();
+            forwarder.submit(json!({"event_type": "agent_incident", "seq": 100})).await.unwrap();
+            assert_eq!(pending(&forwarder), 4);
+        }
+        assert!(central.requests().is_empty());
+
+        let (forwarder, _) = forwarder(&dir, Some(&url), 10);
+        assert_eq!(pending(&forwarder), 4);
+        assert!(forwarder.status().replaying);
+        spawn_forwarder(forwarder.clone());
+        drained(&forwarder).await;
+
+        // 우선 전송 스풀의 사건이 밀린 일반 이벤트보다 먼저
+        assert_eq!(central.delivered(), vec![100, 0, 1, 2]);
+        assert!(!forwarder.status().replaying);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn alert(signature_id: u64) -> Value {
+        json!({
+            "event_type": "alert",
+            "src_ip": "10.0.0.1",
+            "dest_ip": "10.0.0.2",
+            "alert": {"action": "allowed", "gid": 1, "signature_id": signature_id, "rev": 1, "signature": "TEST", "category": "Cat", "severity": 3}
+        })
+    }
+
+    #[tokio::test]
+    async fn replayed_events_stay_out_of_local_views() {
+        let dir = temp_dir();
+        let (forwarder, local) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let mut subscription = local.stream.subscribe(&EventStreamQuery::default(), "sse").unwrap();
+
+        // 같은 경보라도 재생 이벤트는 집계되지 않고 모두 전달
+        let result = forwarder.enqueue_replay(vec![alert(1), alert(1)]).await.unwrap();
+        assert_eq!((result.accepted, result.aggregated), (2, 0));
+        assert_eq!(pending(&forwarder), 2);
+        assert_eq!(local.stats.report(&AlertStatsQuery::default()).unwrap().total, 0);
+        assert!(tokio::time::timeout(Duration::from_millis(50), subscription.next()).await.is_err());
+
+        let result = forwarder.enqueue(vec![alert(2)]).await.unwrap();
+        assert_eq!(result.accepted, 1);
+        assert_eq!(local.stats.report(&AlertStatsQuery::default()).unwrap().total, 1);
+        assert!(matches!(subscription.next().await, Some(StreamItem::Event(_))));
+
+        // 저장소에는 실시간 경보 하나만
+        let mut stored = 0;
+        for _ in 0..250 {
+            stored = local.store.query(&Default::default()).unwrap().total;
+            if stored > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(stored, 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+
+use serde_json::Value;
+use tracing::warn;
+
+use crate::services::counters::CounterStore;
+use crate::services::forwarder::Forwarder;
+use crate::services::metrics::Metrics;
+
+// 줄 단위 EVE 입력(파일, unix 소켓, redis 프로토콜)을 HTTP 수신과 같은 경로로 전달
+pub struct LineIngest {
+    forwarder: Arc<Forwarder>,
+    counters: Arc<CounterStore>,
+    metrics: Arc<Metrics>,
+}
+
+impl LineIngest {
+    pub fn new(forwarder: Arc<Forwarder>, counters: Arc<CounterStore>, metrics: Arc<Metrics>) -> Self {
+        LineIngest { forwarder, counters, metrics }
+    }
+
+    // JSON 한 줄씩 파싱해 스풀에 기록하고 파싱하지 못한 줄 수 반환
+    // 스풀 기록에 실패하면 오류 (호출자가 다시 보내거나 실패를 알림)
+    pub async fn ingest(&self, input: &str, lines: Vec<Vec<u8>>) -> Result<u64, String> {
+        let mut invalid = 0;
+        let mut items = Vec::with_capacity(lines.len());
+        for line in lines {
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            match serde_json::from_slice::<Value>(&line) {
+                Ok(item) => items.push(item),
+                Err(e) => {
+                    warn!("Skipping invalid EVE line from {}: {}", input, e);
+                    invalid += 1;
+                }
+            }
+        }
+        self.metrics.eve_input(input, "invalid", invalid);
+        if items.is_empty() {
+            return Ok(invalid);
+        }
+
+        let count = items.len() as u64;
+        let stats: Vec<Value> = items.iter()
+            .filter(|item| item.get("event_type").and_then(Value::as_str) == Some("stats"))
+            .cloned()
+            .collect();
+        let result = self.forwarder.enqueue(items).await
+            .map_err(|e| e.message().to_string())?;
+        if result.failed > 0 {
+            self.metrics.eve_input(input, "failed", count);
+            return Err(format!("Failed to spool {} EVE events", result.failed));
+        }
+        self.metrics.eve_input(input, "parsed", count);
+        self.counters.record_eve_stats(&stats).await;
+        Ok(invalid)
+    }
+}
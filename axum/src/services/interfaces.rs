@@ -0,0 +1,212 @@
+use std::collections::HashSet;
+
+use serde_json::Value;
+use tracing::warn;
+
+use crate::models::interface::{CaptureInterfaceRequest, CaptureInterfaces, InterfaceOverview};
+use crate::services::config::{af_packet_interfaces, parse_config, ConfigError, ConfigManager};
+use crate::utils::config::env_or;
+use crate::utils::interfaces::{is_valid_interface_name, list_host_interfaces};
+use crate::utils::yaml::{insert_item, quote_scalar, remove_item, remove_key, set_value, Segment};
+
+// 엔진이 사용하는 캡처 방식
+pub fn capture_mode() -> String {
+    env_or("SURICATA_CAPTURE_MODE", "af-packet".to_string())
+}
+
+fn host_sys_dir() -> String {
+    env_or("HOST_SYS_DIR", "/sys".to_string())
+}
+
+// 설정의 시퀀스에서 interface 이름 목록 추출 (default 제외)
+fn interface_names(config: &Value, section: &str) -> Vec<String> {
+    config.get(section)
+        .and_then(Value::as_array)
+        .map(|entries| {
+            entries.iter()
+                .filter_map(|entry| entry.get("interface").and_then(Value::as_str))
+                .filter(|name| *name != "default")
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 호스트 인터페이스와 캡처 설정 현황
+pub fn overview(config: &ConfigManager) -> Result<InterfaceOverview, ConfigError> {
+    let value = config.read_value()?;
+    let mode = capture_mode();
+
+    let af_packet: Vec<_> = af_packet_interfaces(&value)
+        .into_iter()
+        .filter(|entry| entry.interface != "default")
+        .collect();
+    let capture = CaptureInterfaces {
+        af_packet,
+        af_xdp: interface_names(&value, "af-xdp"),
+        pcap: interface_names(&value, "pcap"),
+    };
+
+    let monitored: Vec<&str> = match mode.as_str() {
+        "af-xdp" => capture.af_xdp.iter().map(String::as_str).collect(),
+        "pcap" => capture.pcap.iter().map(String::as_str).collect(),
+        _ => capture.af_packet.iter().map(|entry| entry.interface.as_str()).collect(),
+    };
+
+    let host = match list_host_interfaces(&host_sys_dir()) {
+        Ok(mut host) => {
+            for interface in host.iter_mut() {
+                interface.monitored = monitored.contains(&interface.name.as_str());
+            }
+            host
+        }
+        Err(e) => {
+            warn!("Failed to list host interfaces: {}", e);
+            Vec::new()
+        }
+    };
+
+    Ok(InterfaceOverview { mode, host, capture })
+}
+
+// 요청 검증 (이름, 호스트 존재 여부, copy-mode 짝)
+fn validate_requests(requests: &[CaptureInterfaceRequest]) -> Result<(), ConfigError> {
+    if requests.is_empty() {
+        return Err(ConfigError::Invalid("At least one capture interface is required".to_string()));
+    }
+
+    // sysfs를 읽을 수 없는 환경에서는 존재 여부 검증 생략
+    let host: Option<HashSet<String>> = list_host_interfaces(&host_sys_dir())
+        .ok()
+        .map(|interfaces| interfaces.into_iter().map(|interface| interface.name).collect());
+
+    let mut seen = HashSet::new();
+    for request in requests {
+        let name = request.interface.as_str();
+        if !is_valid_interface_name(name) || name == "default" {
+            return Err(ConfigError::Invalid(format!("Invalid interface name: {}", name)));
+        }
+        if !seen.insert(name) {
+            return Err(ConfigError::Invalid(format!("Duplicate interface: {}", name)));
+        }
+        if let Some(host) = &host
+            && !host.contains(name) {
+            return Err(ConfigError::Invalid(format!("Interface {} does not exist on the host", name)));
+        }
+        if let Some(threads) = &request.threads
+            && threads != "auto"
+            && !threads.parse::<u32>().is_ok_and(|threads| threads > 0) {
+            return Err(ConfigError::Invalid(format!("threads must be 'auto' or a positive number: {}", threads)));
+        }
+
+        match (request.copy_mode.as_deref(), request.copy_iface.as_deref()) {
+            (None, None) => {}
+            (Some(mode @ ("ips" | "tap")), Some(peer)) => {
+                if peer == name {
+                    return Err(ConfigError::Invalid(format!("{} cannot copy to itself", name)));
+                }
+                // IPS/TAP 모드는 양방향 짝이 모두 있어야 함
+                let paired = requests.iter().any(|other| {
+                    other.interface == peer
+                        && other.copy_iface.as_deref() == Some(name)
+                        && other.copy_mode.as_deref() == Some(mode)
+                });
+                if !paired {
+                    return Err(ConfigError::Invalid(format!(
+                        "copy-mode {} requires {} to be listed with copy_iface {} and the same copy_mode",
+                        mode, peer, name
+                    )));
+                }
+            }
+            (Some(mode @ ("ips" | "tap")), None) => {
+                return Err(ConfigError::Invalid(format!("copy-mode {} requires copy_iface on {}", mode, name)));
+            }
+            (Some(mode), _) => {
+                return Err(ConfigError::Invalid(format!("Invalid copy_mode: {}. Must be ips or tap", mode)));
+            }
+            (None, Some(_)) => {
+                return Err(ConfigError::Invalid(format!("copy_iface on {} requires copy_mode", name)));
+            }
+        }
+    }
+    Ok(())
+}
+
+// af-packet 인터페이스 목록 교체 (기존 항목은 주석을 유지한 채 수정)
+pub async fn set_af_packet_interfaces(
+    config: &ConfigManager,
+    requests: &[CaptureInterfaceRequest],
+) -> Result<(), ConfigError> {
+    config.update(|text| apply_af_packet_interfaces(text, requests)).await
+}
+
+// 요청 목록대로 af-packet 항목을 편집한 설정 내용
+pub fn apply_af_packet_interfaces(text: &str, requests: &[CaptureInterfaceRequest]) -> Result<String, ConfigError> {
+    validate_requests(requests)?;
+    let current = af_packet_interfaces(&parse_config(text)?);
+    let mut text = text.to_string();
+    let invalid = ConfigError::Invalid;
+
+    // 요청에 없는 인터페이스 제거
+    for entry in &current {
+        if entry.interface != "default"
+            && !requests.iter().any(|request| request.interface == entry.interface) {
+            text = remove_item(&text, &format!("af-packet[interface={}]", entry.interface))
+                .map_err(invalid)?;
+        }
+    }
+
+    let mut used_ids: HashSet<u32> = current.iter()
+        .filter_map(|entry| entry.cluster_id.as_deref().and_then(|id| id.parse().ok()))
+        .collect();
+    let default_item = Segment::Item { key: "interface".to_string(), value: "default".to_string() };
+
+    for request in requests {
+        let optional = [
+            ("threads", request.threads.clone()),
+            ("copy-mode", request.copy_mode.clone()),
+            ("copy-iface", request.copy_iface.clone()),
+        ];
+
+        match current.iter().find(|entry| entry.interface == request.interface) {
+            Some(existing) => {
+                let item = format!("af-packet[interface={}]", request.interface);
+                if let Some(cluster_id) = request.cluster_id {
+                    text = set_value(&text, &format!("{}.cluster-id", item), &cluster_id.to_string())
+                        .map_err(invalid)?;
+                }
+                let had = [&existing.threads, &existing.copy_mode, &existing.copy_iface];
+                for ((key, value), had) in optional.iter().zip(had) {
+                    let path = format!("{}.{}", item, key);
+                    text = match (value, had) {
+                        (Some(value), _) => set_value(&text, &path, &quote_scalar(value)),
+                        (None, Some(_)) => remove_key(&text, &path),
+                        (None, None) => Ok(text),
+                    }
+                    .map_err(invalid)?;
+                }
+            }
+            None => {
+                let cluster_id = request.cluster_id.unwrap_or_else(|| {
+                    (1..=99).rev().find(|id| !used_ids.contains(id)).unwrap_or(1)
+                });
+                used_ids.insert(cluster_id);
+
+                let mut fields = vec![
+                    ("interface".to_string(), quote_scalar(&request.interface)),
+                    ("cluster-id".to_string(), cluster_id.to_string()),
+                    ("cluster-type".to_string(), "cluster_flow".to_string()),
+                    ("defrag".to_string(), "yes".to_string()),
+                ];
+                for (key, value) in optional {
+                    if let Some(value) = value {
+                        fields.push((key.to_string(), quote_scalar(&value)));
+                    }
+                }
+                text = insert_item(&text, "af-packet", &fields, Some(&default_item))
+                    .map_err(invalid)?;
+            }
+        }
+    }
+    Ok(text)
+}
@@ -0,0 +1,620 @@
+use std::{
+    fs,
+    io,
+    os::unix::fs::FileTypeExt,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::{
+    io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream, UnixDatagram, UnixListener, UnixStream},
+    time::Instant,
+};
+use tracing::{error, info, warn};
+
+use crate::services::ingest::LineIngest;
+use crate::utils::config::env_or;
+use crate::utils::time::now_secs;
+
+// RESP 명령 하나의 최대 인자 수와 인자를 모두 합친 최대 크기
+const MAX_ARGS: usize = 1024 * 1024;
+const MAX_COMMAND_BYTES: usize = 64 * 1024 * 1024;
+// unix_stream 이벤트 한 줄, RESP 헤더/인라인 명령 한 줄의 최대 크기
+const MAX_LINE_BYTES: usize = 16 * 1024 * 1024;
+// unix_dgram 데이터그램 하나의 최대 크기
+const DATAGRAM_BYTES: usize = 1024 * 1024;
+
+// Suricata eve-log filetype에 맞춘 unix 소켓 종류
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SocketType {
+    Stream,
+    Dgram,
+}
+
+// redis 클라이언트에 보낼 응답
+enum Reply {
+    Status(&'static str),
+    Integer(i64),
+    Bulk(Vec<u8>),
+    Error(String),
+    // ERR 외의 오류 코드로 시작하는 응답 (NOAUTH, WRONGPASS)
+    Denied(&'static str),
+    EmptyArray,
+    // 이벤트를 스풀에 기록한 결과에 따라 정해지는 응답 (실패하면 오류)
+    Stored(Box<Reply>),
+}
+
+impl Reply {
+    fn encode(self, out: &mut Vec<u8>, stored: &Result<u64, String>) {
+        match self {
+            Reply::Status(status) => out.extend_from_slice(format!("+{}\r\n", status).as_bytes()),
+            Reply::Integer(value) => out.extend_from_slice(format!(":{}\r\n", value).as_bytes()),
+            Reply::Bulk(value) => {
+                out.extend_from_slice(format!("${}\r\n", value.len()).as_bytes());
+                out.extend_from_slice(&value);
+                out.extend_from_slice(b"\r\n");
+            }
+            Reply::Error(message) => out.extend_from_slice(format!("-ERR {}\r\n", message).as_bytes()),
+            Reply::Denied(message) => out.extend_from_slice(format!("-{}\r\n", message).as_bytes()),
+            Reply::EmptyArray => out.extend_from_slice(b"*0\r\n"),
+            Reply::Stored(reply) => match stored {
+                Ok(_) => reply.encode(out, stored),
+                Err(e) => Reply::Error(e.clone()).encode(out, stored),
+            },
+        }
+    }
+}
+
+// Suricata EVE 출력을 파일 대신 직접 받는 수신기
+// unix_stream / unix_dgram 소켓과, redis 출력(list, channel, stream 모드)을 받을 만큼의 RESP 서버를 제공
+pub struct EveListener {
+    ingest: Arc<LineIngest>,
+    socket_path: Option<PathBuf>,
+    socket_type: SocketType,
+    redis_listen: Option<String>,
+    // 설정되면 redis 클라이언트는 AUTH를 먼저 보내야 함
+    redis_password: Option<String>,
+    batch_lines: usize,
+    flush_interval: Duration,
+    // XADD 응답용 항목 ID 순번
+    stream_sequence: AtomicU64,
+}
+
+impl EveListener {
+    pub fn from_env(ingest: Arc<LineIngest>) -> Self {
+        let socket_path = env_or("EVE_SOCKET_PATH", String::new());
+        let socket_type = match env_or("EVE_SOCKET_TYPE", "stream".to_string()).as_str() {
+            "dgram" | "unix_dgram" => SocketType::Dgram,
+            _ => SocketType::Stream,
+        };
+        let redis_listen = env_or("EVE_REDIS_LISTEN", String::new());
+        let redis_password = env_or("EVE_REDIS_PASSWORD", String::new());
+
+        EveListener {
+            ingest,
+            socket_path: (!socket_path.is_empty()).then(|| PathBuf::from(socket_path)),
+            socket_type,
+            redis_listen: (!redis_listen.is_empty()).then_some(redis_listen),
+            redis_password: (!redis_password.is_empty()).then_some(redis_password),
+            batch_lines: env_or("EVE_SOCKET_BATCH_LINES", 500usize).max(1),
+            flush_interval: Duration::from_millis(env_or("EVE_SOCKET_FLUSH_MS", 200u64).max(1)),
+            stream_sequence: AtomicU64::new(0),
+        }
+    }
+
+    // 남아 있는 소켓 파일을 지우고 바인드 (소켓이 아닌 파일은 건드리지 않음)
+    fn prepare_socket_path(&self, path: &PathBuf) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        match fs::symlink_metadata(path) {
+            Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path)
+                .map_err(|e| format!("Failed to remove stale socket {}: {}", path.display(), e)),
+            Ok(_) => Err(format!("{} exists and is not a socket", path.display())),
+            Err(_) => Ok(()),
+        }
+    }
+
+    async fn flush(&self, input: &str, lines: &mut Vec<Vec<u8>>) {
+        if lines.is_empty() {
+            return;
+        }
+        let count = lines.len();
+        if let Err(e) = self.ingest.ingest(input, std::mem::take(lines)).await {
+            error!("Dropped {} EVE lines from {}: {}", count, input, e);
+        }
+    }
+
+    // unix_stream: 연결마다 줄 단위로 읽어 배치 크기 또는 첫 줄 이후 최대 대기 시간마다 전달
+    async fn read_stream(&self, stream: UnixStream) {
+        let mut reader = BufReader::new(stream);
+        let mut lines = Vec::new();
+        let mut line = BoundedLine::new(MAX_LINE_BYTES);
+        let mut deadline: Option<Instant> = None;
+        loop {
+            let read = match deadline {
+                // 읽다 만 줄은 line에 남아 있으므로 시간이 지나도 다음에 이어 읽음
+                Some(at) => match tokio::time::timeout_at(at, line.read(&mut reader)).await {
+                    Ok(read) => read,
+                    Err(_) => {
+                        self.flush("unix", &mut lines).await;
+                        deadline = None;
+                        continue;
+                    }
+                },
+                None => line.read(&mut reader).await,
+            };
+            match read {
+                Ok(None) => break,
+                Ok(Some(read)) => {
+                    lines.push(read);
+                    deadline.get_or_insert_with(|| Instant::now() + self.flush_interval);
+                    if lines.len() >= self.batch_lines {
+                        self.flush("unix", &mut lines).await;
+                        deadline = None;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to read EVE socket connection: {}", e);
+                    break;
+                }
+            }
+        }
+        self.flush("unix", &mut lines).await;
+    }
+
+    // unix_dgram: 데이터그램 하나에 이벤트 하나 (개행으로 구분된 여러 줄도 허용)
+    async fn read_datagrams(&self, socket: UnixDatagram) {
+        let mut buffer = vec![0u8; DATAGRAM_BYTES];
+        let mut lines = Vec::new();
+        let mut deadline: Option<Instant> = None;
+        loop {
+            let received = match deadline {
+                Some(at) => match tokio::time::timeout_at(at, socket.recv(&mut buffer)).await {
+                    Ok(received) => received,
+                    Err(_) => {
+                        self.flush("unix", &mut lines).await;
+                        deadline = None;
+                        continue;
+                    }
+                },
+                None => socket.recv(&mut buffer).await,
+            };
+            match received {
+                Ok(size) => {
+                    lines.extend(buffer[..size]
+                        .split(|byte| *byte == b'\n')
+                        .filter(|line| !line.is_empty())
+                        .map(<[u8]>::to_vec));
+                    deadline.get_or_insert_with(|| Instant::now() + self.flush_interval);
+                    if lines.len() >= self.batch_lines {
+                        self.flush("unix", &mut lines).await;
+                        deadline = None;
+                    }
+                }
+                Err(e) => {
+                    warn!("Failed to receive EVE datagram: {}", e);
+                    tokio::time::sleep(self.flush_interval).await;
+                }
+            }
+        }
+    }
+
+    // redis 클라이언트 하나 처리
+    // 파이프라인으로 들어온 명령을 모두 읽으면 이벤트를 한 번에 기록한 뒤 응답을 순서대로 보냄
+    async fn serve_redis_client(&self, stream: TcpStream) {
+        let (read, mut write) = stream.into_split();
+        let mut reader = BufReader::new(read);
+        let mut lines = Vec::new();
+        let mut replies = Vec::new();
+        let mut session = RedisSession::new(self.redis_password.as_deref(), &self.stream_sequence);
+        loop {
+            let args = match read_command(&mut reader).await {
+                Ok(Some(args)) => args,
+                Ok(None) => break,
+                Err(e) => {
+                    let _ = write.write_all(format!("-ERR Protocol error: {}\r\n", e).as_bytes()).await;
+                    break;
+                }
+            };
+            let quit = session.handle_command(args, &mut lines, &mut replies);
+
+            if quit || reader.buffer().is_empty() || lines.len() >= self.batch_lines {
+                let stored = if lines.is_empty() {
+                    Ok(0)
+                } else {
+                    self.ingest.ingest("redis", std::mem::take(&mut lines)).await
+                };
+                if let Err(e) = &stored {
+                    error!("Failed to store EVE events from redis client: {}", e);
+                }
+                let mut out = Vec::new();
+                for reply in replies.drain(..) {
+                    reply.encode(&mut out, &stored);
+                }
+                if write.write_all(&out).await.is_err() || quit {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+// redis 연결 하나의 상태
+struct RedisSession<'a> {
+    password: Option<&'a str>,
+    authenticated: bool,
+    stream_sequence: &'a AtomicU64,
+}
+
+impl<'a> RedisSession<'a> {
+    fn new(password: Option<&'a str>, stream_sequence: &'a AtomicU64) -> Self {
+        RedisSession { password, authenticated: password.is_none(), stream_sequence }
+    }
+
+    // Suricata redis 출력이 쓰는 명령과 연결 확인용 명령만 처리
+    fn handle_command(&mut self, args: Vec<Vec<u8>>, lines: &mut Vec<Vec<u8>>, replies: &mut Vec<Reply>) -> bool {
+        let Some(name) = args.first() else {
+            return false;
+        };
+        let name = String::from_utf8_lossy(name).to_ascii_uppercase();
+        let wrong_arity = || Reply::Error(format!("wrong number of arguments for '{}' command", name.to_lowercase()));
+
+        // 비밀번호가 설정되면 AUTH 전에는 AUTH, QUIT만 허용
+        if !self.authenticated && name != "AUTH" && name != "QUIT" {
+            replies.push(Reply::Denied("NOAUTH Authentication required."));
+            return false;
+        }
+
+        let reply = match name.as_str() {
+            "PING" => match args.get(1) {
+                Some(message) => Reply::Bulk(message.clone()),
+                None => Reply::Status("PONG"),
+            },
+            "ECHO" if args.len() == 2 => Reply::Bulk(args[1].clone()),
+            "QUIT" => {
+                replies.push(Reply::Status("OK"));
+                return true;
+            }
+            // AUTH password 또는 AUTH default password
+            "AUTH" if args.len() == 2 || args.len() == 3 => match self.password {
+                None => Reply::Error("AUTH <password> called without any password configured for the default user".to_string()),
+                Some(password) if (args.len() == 2 || args[1] == b"default") && args[args.len() - 1] == password.as_bytes() => {
+                    self.authenticated = true;
+                    Reply::Status("OK")
+                }
+                Some(_) => Reply::Denied("WRONGPASS invalid username-password pair or user is disabled."),
+            },
+            "SELECT" | "CLIENT" => Reply::Status("OK"),
+            "INFO" => Reply::Bulk(b"# Server\r\nredis_version:7.0.0\r\nredis_mode:standalone\r\n".to_vec()),
+            "COMMAND" => Reply::EmptyArray,
+            // list 모드 (lpush / rpush)
+            "LPUSH" | "RPUSH" | "LPUSHX" | "RPUSHX" if args.len() >= 3 => {
+                let pushed = args.len() - 2;
+                lines.extend(args.into_iter().skip(2));
+                Reply::Stored(Box::new(Reply::Integer(pushed as i64)))
+            }
+            // channel 모드 (publish)
+            "PUBLISH" if args.len() == 3 => {
+                lines.extend(args.into_iter().skip(2));
+                Reply::Stored(Box::new(Reply::Integer(1)))
+            }
+            // stream 모드 (xadd)
+            "XADD" => match stream_values(&args) {
+                Some(first_field) => {
+                    let id = match args[first_field - 1].as_slice() {
+                        b"*" => format!("{}-{}", now_secs() * 1000, self.stream_sequence.fetch_add(1, Ordering::Relaxed)),
+                        id => String::from_utf8_lossy(id).to_string(),
+                    };
+                    lines.extend(args.into_iter().skip(first_field + 1).step_by(2));
+                    Reply::Stored(Box::new(Reply::Bulk(id.into_bytes())))
+                }
+                None => wrong_arity(),
+            },
+            "AUTH" | "ECHO" | "LPUSH" | "RPUSH" | "LPUSHX" | "RPUSHX" | "PUBLISH" => wrong_arity(),
+            _ => Reply::Error(format!("unknown command '{}'", name.to_lowercase())),
+        };
+        replies.push(reply);
+        false
+    }
+}
+
+// XADD key [NOMKSTREAM] [MAXLEN|MINID [=|~] threshold [LIMIT count]] id field value [field value ...]
+// 첫 field의 위치를 반환 (형식이 맞지 않으면 None)
+fn stream_values(args: &[Vec<u8>]) -> Option<usize> {
+    let mut index = 2;
+    let upper = |index: usize| args.get(index).map(|arg| arg.to_ascii_uppercase());
+    if upper(index).as_deref() == Some(b"NOMKSTREAM") {
+        index += 1;
+    }
+    if matches!(upper(index).as_deref(), Some(b"MAXLEN") | Some(b"MINID")) {
+        index += 1;
+        if matches!(args.get(index).map(Vec::as_slice), Some(b"=") | Some(b"~")) {
+            index += 1;
+        }
+        index += 1;
+        if upper(index).as_deref() == Some(b"LIMIT") {
+            index += 2;
+        }
+    }
+    // id 다음에 field/value 쌍
+    let first_field = index + 1;
+    let pairs = args.len().checked_sub(first_field)?;
+    (pairs > 0 && pairs % 2 == 0).then_some(first_field)
+}
+
+// RESP 배열 명령 또는 인라인 명령 하나 읽기 (연결이 끝나면 None)
+async fn read_command<R: AsyncBufRead + Unpin>(reader: &mut R) -> io::Result<Option<Vec<Vec<u8>>>> {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if read_line(reader, &mut line).await? == 0 {
+            return Ok(None);
+        }
+        let header = trim_line(&line);
+        if header.is_empty() {
+            continue;
+        }
+        // 인라인 명령 (redis-cli, nc로 확인할 때)
+        if header[0] != b'*' {
+            return Ok(Some(header
+                .split(u8::is_ascii_whitespace)
+                .filter(|arg| !arg.is_empty())
+                .map(<[u8]>::to_vec)
+                .collect()));
+        }
+
+        let count = parse_length(&header[1..], MAX_ARGS)?;
+        let mut args = Vec::with_capacity(count.min(64));
+        // 선언된 길이만큼 미리 할당하지 않고 실제로 받은 만큼만 버퍼를 늘림
+        let mut remaining = MAX_COMMAND_BYTES;
+        for _ in 0..count {
+            line.clear();
+            if read_line(reader, &mut line).await? == 0 {
+                return Err(closed_mid_command());
+            }
+            let header = trim_line(&line);
+            if header.first() != Some(&b'$') {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, "expected bulk string"));
+            }
+            let length = parse_length(&header[1..], remaining)?;
+            remaining -= length;
+            let mut arg = Vec::new();
+            (&mut *reader).take(length as u64 + 2).read_to_end(&mut arg).await?;
+            if arg.len() < length + 2 {
+                return Err(closed_mid_command());
+            }
+            arg.truncate(length);
+            args.push(arg);
+        }
+        if !args.is_empty() {
+            return Ok(Some(args));
+        }
+    }
+}
+
+// 개행까지 한 줄 읽기 (MAX_LINE_BYTES를 넘으면 오류)
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R, line: &mut Vec<u8>) -> io::Result<usize> {
+    let read = (&mut *reader).take(MAX_LINE_BYTES as u64 + 1).read_until(b'\n', line).await?;
+    if line.len() > MAX_LINE_BYTES && !line.ends_with(b"\n") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "line too long"));
+    }
+    Ok(read)
+}
+
+fn closed_mid_command() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "connection closed mid-command")
+}
+
+fn trim_line(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn parse_length(value: &[u8], max: usize) -> io::Result<usize> {
+    std::str::from_utf8(value)
+        .ok()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|length| *length <= max)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid length"))
+}
+
+// 최대 크기를 넘는 줄은 버리고 이어서 읽는 줄 단위 수신 버퍼
+// 시간 제한으로 읽기가 취소되어도 읽다 만 줄은 남아 있다가 다음 호출에 이어짐
+struct BoundedLine {
+    line: Vec<u8>,
+    max: usize,
+    // 너무 긴 줄의 나머지를 버리는 중
+    skipping: bool,
+}
+
+impl BoundedLine {
+    fn new(max: usize) -> Self {
+        BoundedLine { line: Vec::new(), max, skipping: false }
+    }
+
+    // 다음 줄 (개행 포함), 연결이 끝나면 None (끝에 개행 없이 남은 줄은 마지막으로 반환)
+    async fn read<R: AsyncBufRead + Unpin>(&mut self, reader: &mut R) -> io::Result<Option<Vec<u8>>> {
+        loop {
+            let limit = (self.max + 1).saturating_sub(self.line.len()) as u64;
+            if (&mut *reader).take(limit).read_until(b'\n', &mut self.line).await? == 0 {
+                let line = std::mem::take(&mut self.line);
+                return Ok((!line.is_empty() && !self.skipping).then_some(line));
+            }
+            let complete = self.line.ends_with(b"\n");
+            if !complete && self.line.len() > self.max {
+                if !self.skipping {
+                    warn!("Dropping EVE line longer than {} bytes", self.max);
+                }
+                self.skipping = true;
+                self.line.clear();
+            } else if complete && self.skipping {
+                self.skipping = false;
+                self.line.clear();
+            } else if complete {
+                return Ok(Some(std::mem::take(&mut self.line)));
+            }
+        }
+    }
+}
+
+// 설정된 unix 소켓과 redis 주소에서 EVE 수신 시작
+pub fn spawn_eve_listeners(listener: Arc<EveListener>) {
+    if let Some(path) = listener.socket_path.clone() {
+        if let Err(e) = listener.prepare_socket_path(&path) {
+            error!("EVE socket listener disabled: {}", e);
+        } else {
+            match listener.socket_type {
+                SocketType::Stream => match UnixListener::bind(&path) {
+                    Ok(socket) => {
+                        info!("Listening for EVE events on unix stream socket {}", path.display());
+                        let listener = Arc::clone(&listener);
+                        tokio::spawn(async move {
+                            loop {
+                                match socket.accept().await {
+                                    Ok((stream, _)) => {
+                                        let listener = Arc::clone(&listener);
+                                        tokio::spawn(async move { listener.read_stream(stream).await });
+                                    }
+                                    Err(e) => {
+                                        warn!("Failed to accept EVE socket connection: {}", e);
+                                        tokio::time::sleep(listener.flush_interval).await;
+                                    }
+                                }
+                            }
+                        });
+                    }
+                    Err(e) => error!("Failed to bind EVE socket {}: {}", path.display(), e),
+                },
+                SocketType::Dgram => match UnixDatagram::bind(&path) {
+                    Ok(socket) => {
+                        info!("Listening for EVE events on unix datagram socket {}", path.display());
+                        let listener = Arc::clone(&listener);
+                        tokio::spawn(async move { listener.read_datagrams(socket).await });
+                    }
+                    Err(e) => error!("Failed to bind EVE socket {}: {}", path.display(), e),
+                },
+            }
+        }
+    }
+
+    if let Some(address) = listener.redis_listen.clone() {
+        tokio::spawn(async move {
+            let socket = match TcpListener::bind(&address).await {
+                Ok(socket) => socket,
+                Err(e) => {
+                    error!("Failed to bind redis EVE listener on {}: {}", address, e);
+                    return;
+                }
+            };
+            info!("Accepting Suricata redis output on {}", address);
+            loop {
+                match socket.accept().await {
+                    Ok((stream, _)) => {
+                        let listener = Arc::clone(&listener);
+                        tokio::spawn(async move { listener.serve_redis_client(stream).await });
+                    }
+                    Err(e) => {
+                        warn!("Failed to accept redis connection: {}", e);
+                        tokio::time::sleep(listener.flush_interval).await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 로컬 TCP 연결로 명령을 보내고 응답과 기록될 이벤트를 돌려받음
+    async fn exchange(password: Option<&str>, input: &[u8]) -> (String, Vec<Vec<u8>>) {
+        let socket = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(socket.local_addr().unwrap()).await.unwrap();
+        client.write_all(input).await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let (stream, _) = socket.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+        let sequence = AtomicU64::new(0);
+        let mut session = RedisSession::new(password, &sequence);
+        let (mut lines, mut replies, mut out) = (Vec::new(), Vec::new(), Vec::new());
+        while let Some(args) = read_command(&mut reader).await.unwrap() {
+            session.handle_command(args, &mut lines, &mut replies);
+        }
+        for reply in replies {
+            reply.encode(&mut out, &Ok(0));
+        }
+        (String::from_utf8(out).unwrap(), lines)
+    }
+
+    #[tokio::test]
+    async fn resp_and_inline_commands_are_parsed() {
+        let (out, lines) = exchange(None, b"*3\r\n$5\r\nRPUSH\r\n$3\r\neve\r\n$9\r\n{\"a\":\"b\"}\r\nPING\r\n").await;
+        assert_eq!(out, ":1\r\n+PONG\r\n");
+        assert_eq!(lines, vec![b"{\"a\":\"b\"}".to_vec()]);
+    }
+
+    #[tokio::test]
+    async fn commands_require_auth_when_password_is_set() {
+        let input = b"RPUSH eve {}\r\nAUTH wrong\r\nRPUSH eve {}\r\nAUTH secret\r\nRPUSH eve {}\r\n";
+        let (out, lines) = exchange(Some("secret"), input).await;
+        assert_eq!(out, "-NOAUTH Authentication required.\r\n\
+            -WRONGPASS invalid username-password pair or user is disabled.\r\n\
+            -NOAUTH Authentication required.\r\n\
+            +OK\r\n\
+            :1\r\n");
+        assert_eq!(lines.len(), 1);
+
+        let (out, _) = exchange(Some("secret"), b"AUTH default secret\r\nPING\r\n").await;
+        assert_eq!(out, "+OK\r\n+PONG\r\n");
+    }
+
+    #[tokio::test]
+    async fn auth_without_password_is_an_error() {
+        let (out, _) = exchange(None, b"AUTH anything\r\n").await;
+        assert!(out.starts_with("-ERR AUTH <password> called without any password configured"));
+    }
+
+    #[tokio::test]
+    async fn bulk_lengths_are_bounded() {
+        let socket = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = TcpStream::connect(socket.local_addr().unwrap()).await.unwrap();
+        let (stream, _) = socket.accept().await.unwrap();
+        let mut reader = BufReader::new(stream);
+
+        // 전체 한도를 넘는 길이는 읽기 전에 거부
+        let oversized = format!("*1\r\n${}\r\n", MAX_COMMAND_BYTES + 1);
+        client.write_all(oversized.as_bytes()).await.unwrap();
+        let error = read_command(&mut reader).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        // 한도 안의 길이를 선언하고 끊기면 받은 만큼만 읽고 오류
+        client.write_all(format!("*1\r\n${}\r\nshort", MAX_COMMAND_BYTES).as_bytes()).await.unwrap();
+        client.shutdown().await.unwrap();
+        let error = read_command(&mut reader).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn unix_stream_drops_overlong_lines() {
+        let (mut client, server) = UnixStream::pair().unwrap();
+        client.write_all(b"{\"a\":1}\n0123456789abcdefXYZ\n{\"b\":2}\npartial").await.unwrap();
+        client.shutdown().await.unwrap();
+
+        let mut reader = BufReader::new(server);
+        let mut line = BoundedLine::new(16);
+        let mut lines = Vec::new();
+        while let Some(read) = line.read(&mut reader).await.unwrap() {
+            lines.push(read);
+        }
+        assert_eq!(lines, vec![b"{\"a\":1}\n".to_vec(), b"{\"b\":2}\n".to_vec(), b"partial".to_vec()]);
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+};
+
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    Opts, Registry, TextEncoder,
+};
+use tracing::warn;
+
+use crate::utils::suricata::get_env;
+use crate::utils::time::now_secs;
+
+// 메트릭 레이블로 쓰는 EVE event_type (클라이언트가 보낸 값으로 시계열이 무한히 늘지 않도록 고정)
+const EVE_EVENT_TYPES: &[&str] = &[
+    "alert", "anomaly", "dcerpc", "dhcp", "dnp3", "dns", "drop", "fileinfo", "flow", "frame",
+    "ftp", "ftp_data", "http", "http2", "ike", "krb5", "ldap", "modbus", "mqtt", "netflow",
+    "nfs", "pgsql", "quic", "rdp", "rfb", "sip", "smb", "smtp", "snmp", "ssh", "stats",
+    "tftp", "tls", "websocket",
+];
+// Prometheus 메트릭 모음
+pub struct Metrics {
+    registry: Registry,
+    // Suricata 카운터
+    capture_kernel_packets: IntGauge,
+    capture_kernel_drops: IntGauge,
+    decoder: IntGaugeVec,
+    flow: IntGaugeVec,
+    detect_alerts: IntGauge,
+    app_layer_flows: IntGaugeVec,
+    app_layer_tx: IntGaugeVec,
+    // 규칙
+    rules: IntGaugeVec,
+    rule_reloads: IntCounterVec,
+    rule_reload_timestamp: GaugeVec,
+    // 에이전트 내부
+    eve_received: IntCounterVec,
+    eve_forwarded: IntCounterVec,
+    eve_failed: IntCounterVec,
+    eve_input_lines: IntCounterVec,
+    central_api_latency: HistogramVec,
+    forward_queue_depth: IntGauge,
+    forward_batches: IntCounterVec,
+    policy_events: IntCounterVec,
+    alert_aggregation: IntCounterVec,
+    stream_subscribers: IntGauge,
+    stream_dropped: IntCounterVec,
+    sink_events: IntCounterVec,
+    sink_queue_depth: IntGaugeVec,
+    sink_up: IntGaugeVec,
+    http_requests: IntCounterVec,
+    http_latency: HistogramVec,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let capture_kernel_packets = IntGauge::new(
+            "suricata_capture_kernel_packets", "Packets captured by the kernel").unwrap();
+        let capture_kernel_drops = IntGauge::new(
+            "suricata_capture_kernel_drops", "Packets dropped by the kernel").unwrap();
+        let decoder = IntGaugeVec::new(
+            Opts::new("suricata_decoder", "Suricata decoder counters"), &["counter"]).unwrap();
+        let flow = IntGaugeVec::new(
+            Opts::new("suricata_flow", "Suricata flow engine counters"), &["counter"]).unwrap();
+        let detect_alerts = IntGauge::new(
+            "suricata_detect_alerts", "Alerts raised by the detection engine").unwrap();
+        let app_layer_flows = IntGaugeVec::new(
+            Opts::new("suricata_app_layer_flows", "Flows per application layer protocol"), &["proto"]).unwrap();
+        let app_layer_tx = IntGaugeVec::new(
+            Opts::new("suricata_app_layer_tx", "Transactions per application layer protocol"), &["proto"]).unwrap();
+
+        let rules = IntGaugeVec::new(
+            Opts::new("suricata_rules", "Enabled rules per file and action"), &["file", "action"]).unwrap();
+        let rule_reloads = IntCounterVec::new(
+            Opts::new("suricata_rule_reloads_total", "Rule reload attempts"), &["result"]).unwrap();
+        let rule_reload_timestamp = GaugeVec::new(
+            Opts::new("suricata_rule_reload_timestamp_seconds", "Unix time of the last rule reload"), &["result"]).unwrap();
+
+        let eve_received = IntCounterVec::new(
+            Opts::new("agent_eve_events_received_total", "EVE events received"), &["event_type"]).unwrap();
+        let eve_forwarded = IntCounterVec::new(
+            Opts::new("agent_eve_events_forwarded_total", "EVE events forwarded to the central API"), &["event_type"]).unwrap();
+        let eve_failed = IntCounterVec::new(
+            Opts::new("agent_eve_events_failed_total", "EVE events that failed to forward"), &["event_type"]).unwrap();
+        let eve_input_lines = IntCounterVec::new(
+            Opts::new("agent_eve_input_lines_total", "EVE lines read from the file tailer and socket listeners"), &["input", "result"]).unwrap();
+        let central_api_latency = HistogramVec::new(
+            HistogramOpts::new("agent_central_api_request_duration_seconds", "Central API request latency"),
+            &["outcome"]).unwrap();
+        let forward_queue_depth = IntGauge::new(
+            "agent_forward_queue_depth", "EVE events waiting in the spool to be forwarded").unwrap();
+        let forward_batches = IntCounterVec::new(
+            Opts::new("agent_forward_batches_total", "Batches sent to the central API"), &["result"]).unwrap();
+        let policy_events = IntCounterVec::new(
+            Opts::new("agent_policy_events_total", "EVE events kept or dropped per policy rule"), &["rule", "result"]).unwrap();
+        let alert_aggregation = IntCounterVec::new(
+            Opts::new("agent_alert_aggregation_total", "Alerts forwarded, suppressed or rolled up by aggregation"), &["result"]).unwrap();
+        let stream_subscribers = IntGauge::new(
+            "agent_event_stream_subscribers", "Connected live event stream subscribers").unwrap();
+        let stream_dropped = IntCounterVec::new(
+            Opts::new("agent_event_stream_dropped_total", "Events dropped for slow live stream subscribers"), &["transport"]).unwrap();
+        let sink_events = IntCounterVec::new(
+            Opts::new("agent_sink_events_total", "Events sent, failed, rejected or dropped per forwarding sink"), &["sink", "result"]).unwrap();
+        let sink_queue_depth = IntGaugeVec::new(
+            Opts::new("agent_sink_queue_depth", "Events waiting in each forwarding sink queue"), &["sink"]).unwrap();
+        let sink_up = IntGaugeVec::new(
+            Opts::new("agent_sink_up", "Whether the last delivery to each forwarding sink succeeded"), &["sink"]).unwrap();
+        let http_requests = IntCounterVec::new(
+            Opts::new("agent_http_requests_total", "HTTP requests handled"), &["method", "route", "status"]).unwrap();
+        let http_latency = HistogramVec::new(
+            HistogramOpts::new("agent_http_request_duration_seconds", "HTTP request latency"),
+            &["method", "route"]).unwrap();
+
+        registry.register(Box::new(capture_kernel_packets.clone())).unwrap();
+        registry.register(Box::new(capture_kernel_drops.clone())).unwrap();
+        registry.register(Box::new(decoder.clone())).unwrap();
+        registry.register(Box::new(flow.clone())).unwrap();
+        registry.register(Box::new(detect_alerts.clone())).unwrap();
+        registry.register(Box::new(app_layer_flows.clone())).unwrap();
+        registry.register(Box::new(app_layer_tx.clone())).unwrap();
+        registry.register(Box::new(rules.clone())).unwrap();
+        registry.register(Box::new(rule_reloads.clone())).unwrap();
+        registry.register(Box::new(rule_reload_timestamp.clone())).unwrap();
+        registry.register(Box::new(eve_received.clone())).unwrap();
+        registry.register(Box::new(eve_forwarded.clone())).unwrap();
+        registry.register(Box::new(eve_failed.clone())).unwrap();
+        registry.register(Box::new(eve_input_lines.clone())).unwrap();
+        registry.register(Box::new(central_api_latency.clone())).unwrap();
+        registry.register(Box::new(forward_queue_depth.clone())).unwrap();
+        registry.register(Box::new(forward_batches.clone())).unwrap();
+        registry.register(Box::new(policy_events.clone())).unwrap();
+        registry.register(Box::new(alert_aggregation.clone())).unwrap();
+        registry.register(Box::new(stream_subscribers.clone())).unwrap();
+        registry.register(Box::new(stream_dropped.clone())).unwrap();
+        registry.register(Box::new(sink_events.clone())).unwrap();
+        registry.register(Box::new(sink_queue_depth.clone())).unwrap();
+        registry.register(Box::new(sink_up.clone())).unwrap();
+        registry.register(Box::new(http_requests.clone())).unwrap();
+        registry.register(Box::new(http_latency.clone())).unwrap();
+
+        Metrics {
+            registry,
+            capture_kernel_packets,
+            capture_kernel_drops,
+            decoder,
+            flow,
+            detect_alerts,
+            app_layer_flows,
+            app_layer_tx,
+            rules,
+            rule_reloads,
+            rule_reload_timestamp,
+            eve_received,
+            eve_forwarded,
+            eve_failed,
+            eve_input_lines,
+            central_api_latency,
+            forward_queue_depth,
+            forward_batches,
+            policy_events,
+            alert_aggregation,
+            stream_subscribers,
+            stream_dropped,
+            sink_events,
+            sink_queue_depth,
+            sink_up,
+            http_requests,
+            http_latency,
+        }
+    }
+
+    pub fn eve_received(&self, event_type: &str) {
+        self.eve_received.with_label_values(&[event_type_label(event_type)]).inc();
+    }
+
+    pub fn eve_forwarded(&self, event_type: &str) {
+        self.eve_forwarded.with_label_values(&[event_type_label(event_type)]).inc();
+    }
+
+    pub fn eve_failed(&self, event_type: &str) {
+        self.eve_failed.with_label_values(&[event_type_label(event_type)]).inc();
+    }
+
+    pub fn eve_input(&self, input: &str, result: &str, lines: u64) {
+        if lines > 0 {
+            self.eve_input_lines.with_label_values(&[input, result]).inc_by(lines);
+        }
+    }
+
+    pub fn central_api_request(&self, outcome: &str, seconds: f64) {
+        self.central_api_latency.with_label_values(&[outcome]).observe(seconds);
+    }
+
+    pub fn forward_queue(&self, depth: u64) {
+        self.forward_queue_depth.set(depth as i64);
+    }
+
+    pub fn forward_batch(&self, result: &str) {
+        self.forward_batches.with_label_values(&[result]).inc();
+    }
+
+    pub fn policy_event(&self, rule: &str, kept: bool) {
+        let result = if kept { "kept" } else { "dropped" };
+        self.policy_events.with_label_values(&[rule, result]).inc();
+    }
+
+    pub fn alert_aggregation(&self, result: &str) {
+        self.alert_aggregation.with_label_values(&[result]).inc();
+    }
+
+    pub fn stream_subscribers(&self, count: usize) {
+        self.stream_subscribers.set(count as i64);
+    }
+
+    pub fn stream_dropped(&self, transport: &str) {
+        self.stream_dropped.with_label_values(&[transport]).inc();
+    }
+
+    pub fn sink_events(&self, sink: &str, result: &str, events: u64) {
+        self.sink_events.with_label_values(&[sink, result]).inc_by(events);
+    }
+
+    pub fn sink_queue(&self, sink: &str, depth: u64) {
+        self.sink_queue_depth.with_label_values(&[sink]).set(depth as i64);
+    }
+
+    pub fn sink_up(&self, sink: &str, up: bool) {
+        self.sink_up.with_label_values(&[sink]).set(up as i64);
+    }
+
+    pub fn http_request(&self, method: &str, route: &str, status: u16, seconds: f64) {
+        self.http_requests.with_label_values(&[method, route, &status.to_string()]).inc();
+        self.http_latency.with_label_values(&[method, route]).observe(seconds);
+    }
+
+    pub fn rule_reload(&self, success: bool) {
+        let result = if success { "success" } else { "failure" };
+        self.rule_reloads.with_label_values(&[result]).inc();
+        self.rule_reload_timestamp.with_label_values(&[result]).set(now_secs() as f64);
+    }
+
+    // 최신 Suricata 카운터와 규칙 파일 상태를 반영한 뒤 텍스트 포맷으로 인코딩
+    pub fn render(&self, counters: &HashMap<String, u64>) -> Result<String, String> {
+        self.update_counters(counters);
+        self.update_rules();
+
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| format!("Failed to encode metrics: {}", e))?;
+        String::from_utf8(buffer).map_err(|e| format!("Failed to encode metrics: {}", e))
+    }
+
+    fn update_counters(&self, counters: &HashMap<String, u64>) {
+        self.decoder.reset();
+        self.flow.reset();
+        self.app_layer_flows.reset();
+        self.app_layer_tx.reset();
+
+        for (name, value) in counters {
+            let value = *value as i64;
+            match name.as_str() {
+                "capture.kernel_packets" => self.capture_kernel_packets.set(value),
+                "capture.kernel_drops" => self.capture_kernel_drops.set(value),
+                "detect.alert" => self.detect_alerts.set(value),
+                _ => {
+                    if let Some(counter) = name.strip_prefix("decoder.") {
+                        self.decoder.with_label_values(&[counter]).set(value);
+                    } else if let Some(counter) = name.strip_prefix("flow.") {
+                        self.flow.with_label_values(&[counter]).set(value);
+                    } else if let Some(proto) = name.strip_prefix("app_layer.flow.") {
+                        self.app_layer_flows.with_label_values(&[proto]).set(value);
+                    } else if let Some(proto) = name.strip_prefix("app_layer.tx.") {
+                        self.app_layer_tx.with_label_values(&[proto]).set(value);
+                    }
+                }
+            }
+        }
+    }
+
+    // 규칙 디렉토리의 *.rules 파일별, 액션별 활성 규칙 수 집계
+    fn update_rules(&self) {
+        self.rules.reset();
+
+        let (rules_dir, _) = get_env();
+        let entries = match fs::read_dir(Path::new(&rules_dir)) {
+            Ok(entries) => entries,
+            Err(e) => {
+                warn!("Failed to read rules directory {}: {}", rules_dir, e);
+                return;
+            }
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("rules") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let file = entry.file_name().to_string_lossy().to_string();
+
+            for line in content.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                if let Some(action) = line.split_whitespace().next() {
+                    self.rules.with_label_values(&[file.as_str(), action]).inc();
+                }
+            }
+        }
+    }
+}
+
+// 허용 목록에 없는 event_type은 other로 묶음
+fn event_type_label(event_type: &str) -> &str {
+    if EVE_EVENT_TYPES.contains(&event_type) {
+        event_type
+    } else {
+        "other"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eve_event_type_labels_are_bounded() {
+        let metrics = Metrics::new();
+        metrics.eve_received("alert");
+        metrics.eve_received("dns");
+        metrics.eve_received("x\"} 1\nfake_metric{a=\"b");
+        for i in 0..100 {
+            metrics.eve_received(&format!("random-{}", i));
+        }
+        metrics.eve_forwarded("unknown");
+        metrics.eve_failed("TLS");
+
+        let output = metrics.render(&HashMap::new()).unwrap();
+        assert!(output.contains("agent_eve_events_received_total{event_type=\"alert\"} 1"));
+        assert!(output.contains("agent_eve_events_received_total{event_type=\"dns\"} 1"));
+        assert!(output.contains("agent_eve_events_received_total{event_type=\"other\"} 101"));
+        assert!(output.contains("agent_eve_events_forwarded_total{event_type=\"other\"} 1"));
+        assert!(output.contains("agent_eve_events_failed_total{event_type=\"other\"} 1"));
+        assert!(!output.contains("random-"));
+        assert!(!output.contains("fake_metric"));
+        assert_eq!(output.lines().filter(|line| line.starts_with("agent_eve_events_received_total{")).count(), 3);
+    }
+}
@@ -0,0 +1,25 @@
+pub mod aggregation;
+pub mod alert_stats;
+pub mod alert_store;
+pub mod capture;
+pub mod central;
+pub mod config;
+pub mod controller;
+pub mod counters;
+pub mod engine;
+pub mod enrichment;
+pub mod event_stream;
+pub mod forwarder;
+pub mod ingest;
+pub mod interfaces;
+pub mod listener;
+pub mod metrics;
+pub mod pcap_jobs;
+pub mod policy;
+pub mod scoring;
+pub mod sink;
+pub mod sink_format;
+pub mod sink_route;
+pub mod spool;
+pub mod supervisor;
+pub mod tailer;
@@ -0,0 +1,687 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs::{self, File},
+    io::{BufRead, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+use tokio::sync::{RwLock, Semaphore};
+use tracing::{error, info, warn};
+
+use crate::models::eve_json_log::EveItemStatus;
+use crate::models::pcap_job::{
+    PcapJobEvents, PcapJobEventsQuery, PcapJobInfo, PcapJobRequest, PcapJobStatus,
+};
+use crate::services::controller::SuricataController;
+use crate::services::forwarder::Forwarder;
+use crate::utils::config::env_or;
+use crate::utils::time::now_secs;
+
+// 이벤트 조회 한 번에 반환하는 최대 개수
+const MAX_EVENTS_PAGE: usize = 1000;
+// 결과 이벤트 한 줄 최대 크기
+const MAX_EVENT_LINE_BYTES: u64 = 16 * 1024 * 1024;
+
+// 분석 작업 실패 사유
+#[derive(Debug)]
+pub enum PcapJobError {
+    // 잘못된 업로드 (형식, 크기)
+    Invalid(String),
+    // 대상 없음
+    NotFound(String),
+    // 아직 결과가 없는 작업
+    Conflict(String),
+    // 파일 입출력 실패
+    Io(String),
+}
+
+impl PcapJobError {
+    pub fn message(&self) -> &str {
+        match self {
+            PcapJobError::Invalid(message)
+            | PcapJobError::NotFound(message)
+            | PcapJobError::Conflict(message)
+            | PcapJobError::Io(message) => message,
+        }
+    }
+}
+
+// 결과 eve.json의 이벤트 줄 위치
+struct EventLine {
+    offset: u64,
+    len: u32,
+    // EventIndex::kinds 인덱스
+    kind: u32,
+}
+
+// 작업이 끝날 때 한 번 만드는 결과 색인 (조회와 전달은 필요한 줄만 다시 읽음)
+#[derive(Default)]
+struct EventIndex {
+    lines: Vec<EventLine>,
+    kinds: Vec<String>,
+}
+
+impl EventIndex {
+    // event_type별 이벤트 수
+    fn counts(&self) -> BTreeMap<String, u64> {
+        let mut counts = vec![0u64; self.kinds.len()];
+        for line in &self.lines {
+            counts[line.kind as usize] += 1;
+        }
+        self.kinds.iter().cloned().zip(counts).collect()
+    }
+
+    // event_type 조건에 맞는 줄 (없으면 전체)
+    fn select(&self, event_type: Option<&str>) -> Vec<&EventLine> {
+        match event_type {
+            Some(event_type) => match self.kinds.iter().position(|kind| kind == event_type) {
+                Some(kind) => self.lines.iter().filter(|line| line.kind as usize == kind).collect(),
+                None => Vec::new(),
+            },
+            None => self.lines.iter().collect(),
+        }
+    }
+}
+
+// 업로드된 pcap을 pcap-file 모드 Suricata로 분석하는 작업 관리
+// 작업 디렉터리는 suricata 컨테이너와 같은 경로로 공유되어야 함 (suricata-log 볼륨)
+pub struct PcapJobManager {
+    controller: Arc<dyn SuricataController>,
+    dir: PathBuf,
+    jobs: RwLock<VecDeque<PcapJobInfo>>,
+    // 완료된 작업의 결과 색인
+    indexes: RwLock<HashMap<String, Arc<EventIndex>>>,
+    // 동시에 실행하는 분석 수 (나머지는 대기)
+    permits: Arc<Semaphore>,
+    sequence: AtomicU64,
+    max_upload_bytes: usize,
+    timeout_secs: u64,
+    max_stored: usize,
+    // 전달할 때 한 번에 읽어 넘기는 이벤트 수
+    forward_batch: usize,
+}
+
+impl PcapJobManager {
+    pub fn from_env(controller: Arc<dyn SuricataController>) -> Self {
+        let log_dir = env_or("SURICATA_LOG_DIR", "/var/log/suricata".to_string());
+        PcapJobManager {
+            controller,
+            dir: PathBuf::from(env_or("PCAP_JOB_DIR", format!("{}/pcap_jobs", log_dir))),
+            jobs: RwLock::new(VecDeque::new()),
+            indexes: RwLock::new(HashMap::new()),
+            permits: Arc::new(Semaphore::new(env_or("PCAP_JOB_MAX_CONCURRENT", 1usize).max(1))),
+            sequence: AtomicU64::new(0),
+            max_upload_bytes: env_or("PCAP_JOB_MAX_UPLOAD_BYTES", 100 * 1024 * 1024usize),
+            timeout_secs: env_or("PCAP_JOB_TIMEOUT_SECS", 600u64).max(1),
+            max_stored: env_or("PCAP_JOB_MAX_STORED", 20usize).max(1),
+            forward_batch: env_or("PCAP_JOB_FORWARD_BATCH", 500usize).max(1),
+        }
+    }
+
+    pub fn max_upload_bytes(&self) -> usize {
+        self.max_upload_bytes
+    }
+
+    pub async fn list(&self) -> Vec<PcapJobInfo> {
+        self.jobs.read().await.iter().rev().cloned().collect()
+    }
+
+    pub async fn get(&self, id: &str) -> Result<PcapJobInfo, PcapJobError> {
+        self.jobs.read().await
+            .iter()
+            .find(|job| job.id == id)
+            .cloned()
+            .ok_or_else(|| PcapJobError::NotFound(format!("Job {} not found", id)))
+    }
+
+    pub async fn delete(&self, id: &str) -> Result<(), PcapJobError> {
+        let mut jobs = self.jobs.write().await;
+        let index = jobs.iter()
+            .position(|job| job.id == id)
+            .ok_or_else(|| PcapJobError::NotFound(format!("Job {} not found", id)))?;
+        if matches!(jobs[index].status, PcapJobStatus::Queued | PcapJobStatus::Running) {
+            return Err(PcapJobError::Conflict(format!("Job {} is still in progress", id)));
+        }
+        jobs.remove(index);
+        self.indexes.write().await.remove(id);
+        self.remove_dir(id);
+        Ok(())
+    }
+
+    // 업로드 저장 후 분석 작업 등록
+    // overrides: 분석 실행에 덧붙일 --set 설정 (file_only_outputs)
+    pub async fn submit(
+        self: &Arc<Self>,
+        engine_config: String,
+        overrides: Vec<String>,
+        forwarder: Arc<Forwarder>,
+        request: PcapJobRequest,
+        data: &[u8],
+    ) -> Result<PcapJobInfo, PcapJobError> {
+        if data.len() > self.max_upload_bytes {
+            return Err(PcapJobError::Invalid(format!(
+                "pcap must be at most {} bytes",
+                self.max_upload_bytes
+            )));
+        }
+        if !is_capture_file(data) {
+            return Err(PcapJobError::Invalid("Upload is not a pcap or pcapng file".to_string()));
+        }
+
+        let created_at = now_secs();
+        let id = format!("{}-{}", created_at, self.sequence.fetch_add(1, Ordering::Relaxed));
+        let job_dir = self.dir.join(&id);
+        fs::create_dir_all(job_dir.join("log"))
+            .and_then(|_| fs::write(job_dir.join("input.pcap"), data))
+            .map_err(|e| PcapJobError::Io(format!("Failed to store upload in {}: {}", job_dir.display(), e)))?;
+
+        let job = PcapJobInfo {
+            id: id.clone(),
+            name: request.name,
+            status: PcapJobStatus::Queued,
+            forward: request.forward,
+            size: data.len() as u64,
+            created_at,
+            started_at: None,
+            finished_at: None,
+            events: BTreeMap::new(),
+            forwarded: None,
+            error: None,
+        };
+        self.register(job.clone()).await;
+
+        let manager = Arc::clone(self);
+        tokio::spawn(async move {
+            let Ok(_permit) = Arc::clone(&manager.permits).acquire_owned().await else {
+                return;
+            };
+            manager.update(&id, |job| {
+                job.status = PcapJobStatus::Running;
+                job.started_at = Some(now_secs());
+            }).await;
+
+            let result = manager.run(&id, &engine_config, &overrides, &forwarder).await;
+            manager.update(&id, |job| {
+                job.finished_at = Some(now_secs());
+                match result {
+                    Ok(()) => job.status = PcapJobStatus::Completed,
+                    Err(e) => {
+                        error!("pcap job {} failed: {}", job.id, e);
+                        job.status = PcapJobStatus::Failed;
+                        job.error = Some(e);
+                    }
+                }
+            }).await;
+        });
+        Ok(job)
+    }
+
+    // 결과 EVE 이벤트 조회 (색인으로 요청한 페이지의 줄만 읽음)
+    pub async fn events(&self, id: &str, query: &PcapJobEventsQuery) -> Result<PcapJobEvents, PcapJobError> {
+        let job = self.get(id).await?;
+        if job.status != PcapJobStatus::Completed {
+            return Err(PcapJobError::Conflict(format!("Job {} has not completed", id)));
+        }
+        let index = self.indexes.read().await
+            .get(id)
+            .cloned()
+            .ok_or_else(|| PcapJobError::NotFound(format!("Job {} has no events", id)))?;
+
+        let limit = query.limit.unwrap_or(100).min(MAX_EVENTS_PAGE);
+        let (total, page) = {
+            let lines = index.select(query.event_type.as_deref());
+            let page: Vec<(u64, u32)> = lines.iter()
+                .skip(query.offset)
+                .take(limit)
+                .map(|line| (line.offset, line.len))
+                .collect();
+            (lines.len(), page)
+        };
+        let events = self.read_lines(id, page).await.map_err(PcapJobError::Io)?;
+
+        Ok(PcapJobEvents {
+            job_id: id.to_string(),
+            total,
+            offset: query.offset,
+            events,
+        })
+    }
+
+    fn events_path(&self, id: &str) -> PathBuf {
+        self.job_dir(id).join("log").join("eve.json")
+    }
+
+    // 색인된 줄만 읽어 파싱
+    async fn read_lines(&self, id: &str, lines: Vec<(u64, u32)>) -> Result<Vec<Value>, String> {
+        let path = self.events_path(id);
+        tokio::task::spawn_blocking(move || read_lines(&path, &lines))
+            .await
+            .map_err(|e| format!("Failed to read events: {}", e))?
+    }
+
+    fn job_dir(&self, id: &str) -> PathBuf {
+        self.dir.join(id)
+    }
+
+    fn remove_dir(&self, id: &str) {
+        let path = self.job_dir(id);
+        if let Err(e) = fs::remove_dir_all(&path)
+            && e.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to remove pcap job {}: {}", path.display(), e);
+        }
+    }
+
+    // 보관 한도를 넘으면 오래된 완료 작업부터 삭제
+    async fn register(&self, job: PcapJobInfo) {
+        let mut jobs = self.jobs.write().await;
+        while jobs.len() >= self.max_stored {
+            let Some(index) = jobs.iter()
+                .position(|job| matches!(job.status, PcapJobStatus::Completed | PcapJobStatus::Failed)) else {
+                break;
+            };
+            if let Some(evicted) = jobs.remove(index) {
+                self.indexes.write().await.remove(&evicted.id);
+                self.remove_dir(&evicted.id);
+            }
+        }
+        jobs.push_back(job);
+    }
+
+    async fn update<F: FnOnce(&mut PcapJobInfo)>(&self, id: &str, apply: F) {
+        if let Some(job) = self.jobs.write().await.iter_mut().find(|job| job.id == id) {
+            apply(job);
+        }
+    }
+
+    async fn run(
+        &self,
+        id: &str,
+        engine_config: &str,
+        overrides: &[String],
+        forwarder: &Arc<Forwarder>,
+    ) -> Result<(), String> {
+        let job_dir = self.job_dir(id);
+        let input = job_dir.join("input.pcap").to_string_lossy().to_string();
+        let log_dir = job_dir.join("log").to_string_lossy().to_string();
+        let timeout = self.timeout_secs.to_string();
+
+        info!("Running pcap job {}", id);
+        let mut args = vec![
+            "timeout", timeout.as_str(),
+            "suricata", "-c", engine_config, "-r", input.as_str(), "-l", log_dir.as_str(),
+            "-k", "none", "--set", "unix-command.enabled=no",
+        ];
+        for setting in overrides {
+            args.extend(["--set", setting.as_str()]);
+        }
+        let output = self.controller.exec(&args).await?;
+        match output.code {
+            Some(0) => {}
+            Some(124) => return Err(format!("Analysis timed out after {}s", self.timeout_secs)),
+            _ => return Err(output.stderr.trim().to_string()),
+        }
+
+        let path = self.events_path(id);
+        let index = tokio::task::spawn_blocking(move || index_events(&path))
+            .await
+            .map_err(|e| format!("Failed to index events: {}", e))??;
+        let index = Arc::new(index);
+        let forward = {
+            let mut jobs = self.jobs.write().await;
+            let job = jobs.iter_mut().find(|job| job.id == id).ok_or("Job was removed")?;
+            job.events = index.counts();
+            job.forward
+        };
+        self.indexes.write().await.insert(id.to_string(), index.clone());
+
+        if forward {
+            self.forward(id, &index, forwarder).await?;
+        }
+        Ok(())
+    }
+
+    // 결과를 일정 개수씩 읽어 전달 (stats 제외)
+    // 실시간 이벤트와 구분되도록 작업 ID를 붙이고, 로컬 저장소/통계/실시간 구독에는 넣지 않음
+    async fn forward(&self, id: &str, index: &EventIndex, forwarder: &Arc<Forwarder>) -> Result<(), String> {
+        let stats = index.kinds.iter().position(|kind| kind == "stats");
+        let lines: Vec<(u64, u32)> = index.lines.iter()
+            .filter(|line| Some(line.kind as usize) != stats)
+            .map(|line| (line.offset, line.len))
+            .collect();
+
+        let mut forwarded = 0u64;
+        for chunk in lines.chunks(self.forward_batch) {
+            let tagged: Vec<Value> = self.read_lines(id, chunk.to_vec()).await?
+                .into_iter()
+                .map(|mut event| {
+                    if let Some(fields) = event.as_object_mut() {
+                        fields.insert("pcap_job_id".to_string(), Value::String(id.to_string()));
+                        let tags = fields.entry("tags").or_insert_with(|| Value::Array(Vec::new()));
+                        if let Some(tags) = tags.as_array_mut() {
+                            tags.push(Value::String(format!("pcap_job:{}", id)));
+                        }
+                    }
+                    event
+                })
+                .collect();
+            let result = forwarder.enqueue_replay(tagged).await
+                .map_err(|e| e.message().to_string())?;
+            forwarded += result.accepted as u64;
+            self.update(id, |job| job.forwarded = Some(forwarded)).await;
+            if let Some(error) = result.errors.iter().find(|error| matches!(error.status, EveItemStatus::Failed)) {
+                return Err(format!("Failed to forward events: {}", error.error));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 오프라인 분석용 출력 재정의
+// 첫 eve-log만 작업 로그 디렉터리의 eve.json 파일로 쓰고 나머지 출력은 끔
+// (운영 설정의 unix socket, redis, syslog 출력이나 절대 경로 파일로 결과가 새지 않도록)
+pub fn file_only_outputs(config: &Value) -> Result<Vec<String>, PcapJobError> {
+    let outputs = config.get("outputs")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut overrides = Vec::new();
+    let mut eve = false;
+    for (index, output) in outputs.iter().enumerate() {
+        let Some(name) = output.as_object().and_then(|fields| fields.keys().next()) else {
+            continue;
+        };
+        if name == "eve-log" && !eve {
+            eve = true;
+            for (key, value) in [("enabled", "yes"), ("filetype", "regular"), ("filename", "eve.json"), ("threaded", "no")] {
+                overrides.push(format!("outputs.{}.eve-log.{}={}", index, key, value));
+            }
+        } else {
+            overrides.push(format!("outputs.{}.{}.enabled=no", index, name));
+        }
+    }
+    if !eve {
+        return Err(PcapJobError::Invalid("eve-log output is not configured".to_string()));
+    }
+    Ok(overrides)
+}
+
+// 결과 eve.json을 한 줄씩 읽어 위치와 event_type만 기록 (파싱할 수 없는 줄은 건너뜀)
+fn index_events(path: &Path) -> Result<EventIndex, String> {
+    #[derive(Deserialize)]
+    struct EventType {
+        event_type: Option<String>,
+    }
+
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(EventIndex::default()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let mut reader = BufReader::new(file);
+    let mut index = EventIndex::default();
+    let mut line = Vec::new();
+    let mut offset = 0u64;
+    loop {
+        line.clear();
+        let read = (&mut reader).take(MAX_EVENT_LINE_BYTES).read_until(b'\n', &mut line)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        if read == 0 {
+            break;
+        }
+        let start = offset;
+        offset += read as u64;
+        // 한도를 넘는 줄은 나머지를 버림
+        if line.last() != Some(&b'\n') && read as u64 == MAX_EVENT_LINE_BYTES {
+            let mut rest = Vec::new();
+            let skipped = reader.read_until(b'\n', &mut rest)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            offset += skipped as u64;
+            continue;
+        }
+        let Ok(event) = serde_json::from_slice::<EventType>(&line) else {
+            continue;
+        };
+        let event_type = event.event_type.unwrap_or_else(|| "unknown".to_string());
+        let kind = match index.kinds.iter().position(|kind| *kind == event_type) {
+            Some(kind) => kind,
+            None => {
+                index.kinds.push(event_type);
+                index.kinds.len() - 1
+            }
+        };
+        index.lines.push(EventLine { offset: start, len: read as u32, kind: kind as u32 });
+    }
+    Ok(index)
+}
+
+fn read_lines(path: &Path, lines: &[(u64, u32)]) -> Result<Vec<Value>, String> {
+    if lines.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut file = File::open(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut events = Vec::with_capacity(lines.len());
+    let mut buffer = Vec::new();
+    for (offset, len) in lines {
+        buffer.resize(*len as usize, 0);
+        file.seek(SeekFrom::Start(*offset))
+            .and_then(|_| file.read_exact(&mut buffer))
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        events.push(serde_json::from_slice(&buffer).map_err(|e| format!("Failed to parse event: {}", e))?);
+    }
+    Ok(events)
+}
+
+// pcap / pcapng 매직 넘버 확인
+fn is_capture_file(data: &[u8]) -> bool {
+    matches!(
+        data.get(..4),
+        Some([0xd4, 0xc3, 0xb2, 0xa1])
+            | Some([0xa1, 0xb2, 0xc3, 0xd4])
+            | Some([0x4d, 0x3c, 0xb2, 0xa1])
+            | Some([0xa1, 0xb2, 0x3c, 0x4d])
+            | Some([0x0a, 0x0d, 0x0d, 0x0a])
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::models::alert_stats::AlertStatsQuery;
+    use crate::models::eve_json_log::test_support::SAMPLES;
+    use crate::services::config::parse_config;
+    use crate::services::controller::CommandOutput;
+    use crate::services::forwarder::test_support::{forwarder, pending};
+    use crate::utils::random::random_u64;
+
+    const SURICATA_YAML: &str = include_str!("../../../docker/suricata/suricata.yaml");
+    const PCAP: [u8; 24] = [0xd4, 0xc3, 0xb2, 0xa1, 2, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0, 0, 1, 0, 0, 0];
+
+    // pcap-file 실행 대신 -l 디렉터리에 결과 eve.json을 쓰는 가짜 컨트롤러
+    struct ReplayController {
+        output: String,
+        args: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl SuricataController for ReplayController {
+        async fn suricatasc(&self, _command: &str) -> Result<String, String> {
+            Err("not supported".to_string())
+        }
+
+        async fn test_config(&self, _path: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn restart(&self) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn exec(&self, args: &[&str]) -> Result<CommandOutput, String> {
+            *self.args.lock().unwrap() = args.iter().map(|arg| arg.to_string()).collect();
+            let log_dir = args.iter().position(|arg| *arg == "-l").map(|index| args[index + 1]).unwrap();
+            fs::write(Path::new(log_dir).join("eve.json"), &self.output).unwrap();
+            Ok(CommandOutput { code: Some(0), stdout: String::new(), stderr: String::new() })
+        }
+    }
+
+    fn manager(dir: &Path, output: String) -> (Arc<PcapJobManager>, Arc<ReplayController>) {
+        let controller = Arc::new(ReplayController { output, args: Mutex::new(Vec::new()) });
+        let manager = PcapJobManager {
+            controller: controller.clone(),
+            dir: dir.join("jobs"),
+            jobs: RwLock::new(VecDeque::new()),
+            indexes: RwLock::new(HashMap::new()),
+            permits: Arc::new(Semaphore::new(1)),
+            sequence: AtomicU64::new(0),
+            max_upload_bytes: 1024,
+            timeout_secs: 10,
+            max_stored: 5,
+            forward_batch: 3,
+        };
+        (Arc::new(manager), controller)
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("pcap-jobs-test-{}-{}", std::process::id(), random_u64()))
+    }
+
+    async fn finished(manager: &PcapJobManager, id: &str) -> PcapJobInfo {
+        for _ in 0..200 {
+            let job = manager.get(id).await.unwrap();
+            if matches!(job.status, PcapJobStatus::Completed | PcapJobStatus::Failed) {
+                return job;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("job {} did not finish", id);
+    }
+
+    fn query(event_type: Option<&str>, offset: usize, limit: Option<usize>) -> PcapJobEventsQuery {
+        PcapJobEventsQuery { event_type: event_type.map(str::to_string), offset, limit }
+    }
+
+    #[test]
+    fn offline_outputs_are_file_only() {
+        let config = parse_config(SURICATA_YAML).unwrap();
+        let overrides = file_only_outputs(&config).unwrap();
+        assert!(overrides.contains(&"outputs.1.eve-log.filetype=regular".to_string()));
+        assert!(overrides.contains(&"outputs.1.eve-log.filename=eve.json".to_string()));
+        assert!(overrides.contains(&"outputs.0.fast.enabled=no".to_string()));
+        assert!(overrides.contains(&"outputs.5.pcap-log.enabled=no".to_string()));
+        assert_eq!(overrides.iter().filter(|setting| setting.contains("eve-log")).count(), 4);
+
+        // 두 번째 eve-log (예: redis)는 끔
+        let config: Value = serde_json::json!({"outputs": [
+            {"eve-log": {"filetype": "regular"}},
+            {"eve-log": {"filetype": "redis"}},
+            {"syslog": {"enabled": "yes"}},
+        ]});
+        let overrides = file_only_outputs(&config).unwrap();
+        assert!(overrides.contains(&"outputs.1.eve-log.enabled=no".to_string()));
+        assert!(overrides.contains(&"outputs.2.syslog.enabled=no".to_string()));
+
+        let config: Value = serde_json::json!({"outputs": [{"fast": {"enabled": "yes"}}]});
+        assert!(matches!(file_only_outputs(&config), Err(PcapJobError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn events_are_indexed_once_and_paged() {
+        let dir = temp_dir();
+        let output = format!("{}not json\n", SAMPLES);
+        let (manager, controller) = manager(&dir, output);
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let overrides = vec!["outputs.1.eve-log.filetype=regular".to_string()];
+        let request = PcapJobRequest { name: Some("test.pcap".to_string()), forward: false };
+
+        let job = manager.submit("/etc/suricata/suricata.yaml".to_string(), overrides, forwarder.clone(), request, &PCAP)
+            .await
+            .unwrap();
+        let job = finished(&manager, &job.id).await;
+        assert_eq!(job.status, PcapJobStatus::Completed, "{:?}", job.error);
+        assert_eq!(job.events.values().sum::<u64>(), 10);
+        assert_eq!(job.events.get("alert"), Some(&1));
+        assert_eq!(job.forwarded, None);
+        assert_eq!(pending(&forwarder), 0);
+        let args = controller.args.lock().unwrap().join(" ");
+        assert!(args.ends_with("--set unix-command.enabled=no --set outputs.1.eve-log.filetype=regular"), "{}", args);
+
+        // 전체 수는 색인에서, 페이지는 해당 줄만 읽음 (파싱할 수 없는 줄은 제외)
+        let page = manager.events(&job.id, &query(None, 8, Some(5))).await.unwrap();
+        assert_eq!(page.total, 10);
+        assert_eq!(page.events.len(), 2);
+        let last: Value = serde_json::from_str(SAMPLES.lines().last().unwrap()).unwrap();
+        assert_eq!(page.events[1], last);
+
+        let page = manager.events(&job.id, &query(Some("dns"), 0, None)).await.unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.events[0]["event_type"], "dns");
+
+        let page = manager.events(&job.id, &query(Some("smb"), 0, None)).await.unwrap();
+        assert_eq!((page.total, page.events.len()), (0, 0));
+
+        manager.delete(&job.id).await.unwrap();
+        assert!(matches!(manager.events(&job.id, &query(None, 0, None)).await, Err(PcapJobError::NotFound(_))));
+        assert!(manager.indexes.read().await.is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn forwarded_events_are_batched_tagged_and_kept_out_of_local_views() {
+        let dir = temp_dir();
+        let (manager, _) = manager(&dir, SAMPLES.to_string());
+        let (forwarder, local) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let request = PcapJobRequest { name: None, forward: true };
+
+        let job = manager.submit("/etc/suricata/suricata.yaml".to_string(), Vec::new(), forwarder.clone(), request, &PCAP)
+            .await
+            .unwrap();
+        let job = finished(&manager, &job.id).await;
+        assert_eq!(job.status, PcapJobStatus::Completed, "{:?}", job.error);
+        // stats를 뺀 9개를 3개씩 나눠 전달
+        assert_eq!(job.forwarded, Some(9));
+        assert_eq!(pending(&forwarder), 9);
+        assert_eq!(local.stats.report(&AlertStatsQuery::default()).unwrap().total, 0);
+
+        let spooled = fs::read_dir(dir.join("spool")).unwrap()
+            .flatten()
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+            .collect::<String>();
+        let tag = format!("\"pcap_job:{}\"", job.id);
+        let events: Vec<&str> = spooled.lines().filter(|line| line.starts_with('{')).collect();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|line| line.contains(&tag)));
+        assert!(spooled.lines().all(|line| !line.contains("\"event_type\":\"stats\"")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn index_skips_invalid_and_oversized_lines() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("eve.json");
+        assert!(index_events(&path).unwrap().lines.is_empty());
+
+        let oversized = format!("{{\"event_type\":\"flow\",\"pad\":\"{}\"}}\n", "x".repeat(MAX_EVENT_LINE_BYTES as usize));
+        fs::write(&path, format!("{{\"event_type\":\"dns\"}}\n{}garbage\n{{\"flow_id\":1}}\n{{\"event_type\":\"dns\"}}", oversized)).unwrap();
+        let index = index_events(&path).unwrap();
+        assert_eq!(index.counts(), BTreeMap::from([("dns".to_string(), 2), ("unknown".to_string(), 1)]));
+
+        let lines: Vec<(u64, u32)> = index.select(Some("dns")).iter().map(|line| (line.offset, line.len)).collect();
+        let events = read_lines(&path, &lines).unwrap();
+        assert_eq!(events, vec![serde_json::json!({"event_type": "dns"}); 2]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,405 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use tracing::{error, info, warn};
+
+use crate::models::eve_json_log::EveEvent;
+use crate::models::policy::{PolicyAction, PolicyConfig, PolicyRule, PolicyRuleCounter, PolicyStatus};
+use crate::services::metrics::Metrics;
+use crate::utils::cidr::{in_ranges, Cidr};
+use crate::utils::config::env_or;
+use crate::utils::random::random_fraction;
+use crate::utils::time::now_secs;
+
+// 규칙 미일치 카운터 이름
+const DEFAULT_RULE: &str = "default";
+
+// 정책 변경 실패 사유
+#[derive(Debug)]
+pub enum PolicyError {
+    // 잘못된 정책 (CIDR, 비율, 중복 이름 등)
+    Invalid(String),
+    // 파일 입출력 실패
+    Io(String),
+}
+
+impl PolicyError {
+    pub fn message(&self) -> &str {
+        match self {
+            PolicyError::Invalid(message) | PolicyError::Io(message) => message,
+        }
+    }
+}
+
+// 검증을 마치고 CIDR을 해석해 둔 규칙
+struct CompiledRule {
+    rule: PolicyRule,
+    src: Vec<Cidr>,
+    dest: Vec<Cidr>,
+}
+
+impl CompiledRule {
+    fn matches(&self, event: &EveEvent) -> bool {
+        if !self.rule.event_types.is_empty()
+            && !self.rule.event_types.iter().any(|event_type| event_type == event.event_type()) {
+            return false;
+        }
+        if let Some(threshold) = self.rule.severity_below
+            && event.alert().is_none_or(|alert| alert.severity <= threshold) {
+            return false;
+        }
+        if !self.rule.signature_ids.is_empty()
+            && event.alert().is_none_or(|alert| !self.rule.signature_ids.contains(&alert.signature_id)) {
+            return false;
+        }
+        let common = event.common();
+        in_ranges(&self.src, common.src_ip.as_deref()) && in_ranges(&self.dest, common.dest_ip.as_deref())
+    }
+}
+
+struct CompiledPolicy {
+    config: PolicyConfig,
+    rules: Vec<CompiledRule>,
+    loaded_at: Option<u64>,
+}
+
+fn compile(config: PolicyConfig) -> Result<CompiledPolicy, PolicyError> {
+    let mut rules = Vec::with_capacity(config.rules.len());
+    for rule in &config.rules {
+        if rule.name.trim().is_empty() || rule.name == DEFAULT_RULE {
+            return Err(PolicyError::Invalid(format!("Invalid rule name '{}'", rule.name)));
+        }
+        if rules.iter().any(|compiled: &CompiledRule| compiled.rule.name == rule.name) {
+            return Err(PolicyError::Invalid(format!("Duplicate rule name '{}'", rule.name)));
+        }
+        match (rule.action, rule.rate) {
+            (PolicyAction::Sample, Some(rate)) if (0.0..=1.0).contains(&rate) => {}
+            (PolicyAction::Sample, _) => {
+                return Err(PolicyError::Invalid(format!(
+                    "Rule '{}' needs a rate between 0.0 and 1.0",
+                    rule.name
+                )));
+            }
+            (_, Some(_)) => {
+                return Err(PolicyError::Invalid(format!("rate is only valid for sample rules ('{}')", rule.name)));
+            }
+            (_, None) => {}
+        }
+        let parse = |ranges: &[String]| ranges.iter()
+            .map(|range| Cidr::parse(range))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| PolicyError::Invalid(format!("Rule '{}': {}", rule.name, e)));
+        rules.push(CompiledRule {
+            rule: rule.clone(),
+            src: parse(&rule.src_cidrs)?,
+            dest: parse(&rule.dest_cidrs)?,
+        });
+    }
+    if config.default_action == PolicyAction::Sample {
+        return Err(PolicyError::Invalid("default_action must be keep or drop".to_string()));
+    }
+    Ok(CompiledPolicy {
+        config,
+        rules,
+        loaded_at: Some(now_secs()),
+    })
+}
+
+// 수신 EVE 이벤트를 전달할지 결정하는 정책 엔진
+// 정책 파일이 바뀌면 감시 태스크가 다시 읽음 (파싱 실패 시 기존 정책 유지)
+pub struct PolicyEngine {
+    metrics: Arc<Metrics>,
+    path: PathBuf,
+    policy: RwLock<Arc<CompiledPolicy>>,
+    counters: Mutex<BTreeMap<String, PolicyRuleCounter>>,
+    modified: Mutex<Option<SystemTime>>,
+    reload_interval: Duration,
+}
+
+impl PolicyEngine {
+    pub fn from_env(metrics: Arc<Metrics>) -> Self {
+        let engine = PolicyEngine {
+            metrics,
+            path: PathBuf::from(env_or("POLICY_FILE", "/logs/policy.yaml".to_string())),
+            policy: RwLock::new(Arc::new(CompiledPolicy {
+                config: PolicyConfig::default(),
+                rules: Vec::new(),
+                loaded_at: None,
+            })),
+            counters: Mutex::new(BTreeMap::new()),
+            modified: Mutex::new(None),
+            reload_interval: Duration::from_secs(env_or("POLICY_RELOAD_SECS", 5u64).max(1)),
+        };
+        if engine.path.exists() {
+            if let Err(e) = engine.reload() {
+                error!("Failed to load EVE policy {}: {}", engine.path.display(), e.message());
+            }
+        } else {
+            info!("No EVE policy at {}, forwarding all events", engine.path.display());
+        }
+        engine
+    }
+
+    // 이벤트를 유지할지 판단하고 규칙별 건수 기록
+    pub fn evaluate(&self, event: &EveEvent) -> bool {
+        let policy = self.current();
+        let (rule, action, rate) = match policy.rules.iter().find(|rule| rule.matches(event)) {
+            Some(compiled) => (compiled.rule.name.as_str(), compiled.rule.action, compiled.rule.rate),
+            None => (DEFAULT_RULE, policy.config.default_action, None),
+        };
+        let keep = match action {
+            PolicyAction::Keep => true,
+            PolicyAction::Drop => false,
+            PolicyAction::Sample => random_fraction() < rate.unwrap_or(1.0),
+        };
+
+        self.metrics.policy_event(rule, keep);
+        let mut counters = self.lock_counters();
+        let counter = counters.entry(rule.to_string()).or_insert_with(|| PolicyRuleCounter {
+            rule: rule.to_string(),
+            ..PolicyRuleCounter::default()
+        });
+        if keep {
+            counter.kept += 1;
+        } else {
+            counter.dropped += 1;
+        }
+        keep
+    }
+
+    pub fn status(&self) -> PolicyStatus {
+        let policy = self.current();
+        PolicyStatus {
+            path: self.path.display().to_string(),
+            loaded_at: policy.loaded_at,
+            policy: policy.config.clone(),
+            counters: self.lock_counters().values().cloned().collect(),
+        }
+    }
+
+    // 정책 파일 다시 읽기
+    pub fn reload(&self) -> Result<(), PolicyError> {
+        let modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+        let text = fs::read_to_string(&self.path)
+            .map_err(|e| PolicyError::Io(format!("Failed to read {}: {}", self.path.display(), e)))?;
+        // 실패해도 같은 파일을 반복해서 읽지 않도록 먼저 기록
+        *self.lock_modified() = modified;
+
+        let config: PolicyConfig = serde_yaml::from_str(&text)
+            .map_err(|e| PolicyError::Invalid(format!("Invalid policy {}: {}", self.path.display(), e)))?;
+        self.apply(compile(config)?);
+        info!("Loaded EVE policy from {}", self.path.display());
+        Ok(())
+    }
+
+    // 검증 후 파일에 저장하고 적용
+    pub fn replace(&self, config: PolicyConfig) -> Result<PolicyStatus, PolicyError> {
+        let compiled = compile(config)?;
+        let text = serde_yaml::to_string(&compiled.config)
+            .map_err(|e| PolicyError::Invalid(e.to_string()))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| PolicyError::Io(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        fs::write(&self.path, text)
+            .map_err(|e| PolicyError::Io(format!("Failed to write {}: {}", self.path.display(), e)))?;
+        *self.lock_modified() = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+
+        self.apply(compiled);
+        info!("Updated EVE policy at {}", self.path.display());
+        Ok(self.status())
+    }
+
+    fn apply(&self, compiled: CompiledPolicy) {
+        // 남아 있는 규칙의 카운터만 유지
+        self.lock_counters().retain(|name, _| {
+            name == DEFAULT_RULE || compiled.rules.iter().any(|rule| &rule.rule.name == name)
+        });
+        *self.policy.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(compiled);
+    }
+
+    fn current(&self) -> Arc<CompiledPolicy> {
+        self.policy.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+    }
+
+    fn lock_counters(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, PolicyRuleCounter>> {
+        self.counters.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_modified(&self) -> std::sync::MutexGuard<'_, Option<SystemTime>> {
+        self.modified.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 파일 수정 시각이 바뀌었으면 다시 읽음
+    fn check_file(&self) {
+        let Ok(modified) = fs::metadata(&self.path).and_then(|metadata| metadata.modified()) else {
+            return;
+        };
+        if *self.lock_modified() == Some(modified) {
+            return;
+        }
+        if let Err(e) = self.reload() {
+            warn!("Keeping previous EVE policy: {}", e.message());
+        }
+    }
+}
+
+// 정책 파일 변경 감시
+pub fn spawn_policy_watcher(policy: Arc<PolicyEngine>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(policy.reload_interval);
+        loop {
+            ticker.tick().await;
+            policy.check_file();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample;
+    use crate::utils::random::random_u64;
+
+    fn event(value: Value) -> EveEvent {
+        EveEvent::try_from(value).unwrap()
+    }
+
+    fn alert(severity: u8, signature_id: u64) -> EveEvent {
+        let mut value = sample("alert");
+        value["alert"]["severity"] = severity.into();
+        value["alert"]["signature_id"] = signature_id.into();
+        event(value)
+    }
+
+    fn engine(dir: &std::path::Path, yaml: &str) -> PolicyEngine {
+        let engine = PolicyEngine {
+            metrics: Arc::new(Metrics::new()),
+            path: dir.join("policy.yaml"),
+            policy: RwLock::new(Arc::new(CompiledPolicy {
+                config: PolicyConfig::default(),
+                rules: Vec::new(),
+                loaded_at: None,
+            })),
+            counters: Mutex::new(BTreeMap::new()),
+            modified: Mutex::new(None),
+            reload_interval: Duration::from_secs(5),
+        };
+        engine.replace(serde_yaml::from_str(yaml).unwrap()).unwrap();
+        engine
+    }
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("policy-test-{}-{}", std::process::id(), random_u64()))
+    }
+
+    fn counter(engine: &PolicyEngine, rule: &str) -> (u64, u64) {
+        engine.status().counters.iter()
+            .find(|counter| counter.rule == rule)
+            .map_or((0, 0), |counter| (counter.kept, counter.dropped))
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let dir = temp_dir();
+        let engine = engine(&dir, "
+default_action: keep
+rules:
+  - name: keep-critical
+    action: keep
+    signature_ids: [2100498]
+  - name: drop-low-severity
+    action: drop
+    event_types: [alert]
+    severity_below: 2
+  - name: drop-internal-dns
+    action: drop
+    event_types: [dns]
+    src_cidrs: [10.0.0.0/8]
+    dest_cidrs: [10.0.0.1/32]
+  - name: drop-flows
+    action: sample
+    event_types: [flow]
+    rate: 0.0
+");
+        // severity 3은 2보다 덜 심각하지만 앞 규칙이 먼저 일치
+        assert!(engine.evaluate(&alert(3, 2100498)));
+        assert!(!engine.evaluate(&alert(3, 1)));
+        assert!(engine.evaluate(&alert(2, 1)));
+        assert!(engine.evaluate(&alert(1, 1)));
+        assert!(!engine.evaluate(&event(sample("dns"))));
+        assert!(!engine.evaluate(&event(sample("flow"))));
+        assert!(engine.evaluate(&event(sample("http"))));
+
+        let mut dns = sample("dns");
+        dns["dest_ip"] = "8.8.8.8".into();
+        assert!(engine.evaluate(&event(dns)));
+
+        assert_eq!(counter(&engine, "keep-critical"), (1, 0));
+        assert_eq!(counter(&engine, "drop-low-severity"), (0, 1));
+        assert_eq!(counter(&engine, "drop-internal-dns"), (0, 1));
+        assert_eq!(counter(&engine, "drop-flows"), (0, 1));
+        assert_eq!(counter(&engine, DEFAULT_RULE), (4, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn default_action_applies_when_nothing_matches() {
+        let dir = temp_dir();
+        let engine = engine(&dir, "
+default_action: drop
+rules:
+  - name: alerts
+    action: sample
+    event_types: [alert]
+    rate: 1.0
+");
+        assert!(engine.evaluate(&alert(2, 1)));
+        assert!(!engine.evaluate(&event(sample("tls"))));
+        assert_eq!(counter(&engine, DEFAULT_RULE), (0, 1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_policies_are_rejected() {
+        let invalid = [
+            "rules: [{name: a, action: sample}]",
+            "rules: [{name: a, action: sample, rate: 1.5}]",
+            "rules: [{name: a, action: drop, rate: 0.5}]",
+            "rules: [{name: a, action: drop}, {name: a, action: keep}]",
+            "rules: [{name: default, action: drop}]",
+            "rules: [{name: a, action: drop, src_cidrs: [bogus]}]",
+            "default_action: sample",
+        ];
+        for yaml in invalid {
+            let config: PolicyConfig = serde_yaml::from_str(yaml).unwrap();
+            assert!(matches!(compile(config), Err(PolicyError::Invalid(_))), "{}", yaml);
+        }
+    }
+
+    #[test]
+    fn replacing_policy_keeps_counters_of_remaining_rules() {
+        let dir = temp_dir();
+        let engine = engine(&dir, "rules: [{name: a, action: drop, event_types: [flow]}, {name: b, action: keep}]");
+        engine.evaluate(&event(sample("flow")));
+        engine.evaluate(&event(sample("dns")));
+
+        engine.replace(serde_yaml::from_str("rules: [{name: b, action: keep}]").unwrap()).unwrap();
+        assert_eq!(counter(&engine, "a"), (0, 0));
+        assert_eq!(counter(&engine, "b"), (1, 0));
+        assert!(engine.evaluate(&event(sample("flow"))));
+
+        // 저장된 파일을 다시 읽어도 같은 정책
+        engine.reload().unwrap();
+        assert_eq!(engine.status().policy.rules.len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+use std::{
+    collections::HashMap,
+    fs,
+    net::IpAddr,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tracing::{info, warn};
+
+use crate::models::eve_json_log::EveEvent;
+use crate::models::scoring::{AlertScore, ScoreFactors, ScoringWeights};
+use crate::services::enrichment::Enricher;
+use crate::utils::config::env_or;
+
+// 점수를 넣는 최상위 키
+const SCORE_KEY: &str = "score";
+// 출발지/목적지 쌍 기록 최대 개수
+const PAIR_CAPACITY: usize = 100_000;
+
+// 쌍별 등장 횟수 (구간이 지나면 다시 셈)
+struct PairSeen {
+    count: u64,
+    since: Instant,
+}
+
+fn load_weights(path: &str) -> ScoringWeights {
+    if path.is_empty() || !Path::new(path).exists() {
+        info!("No scoring weights at '{}', using defaults", path);
+        return ScoringWeights::default();
+    }
+    match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_yaml::from_str(&text).map_err(|e| e.to_string())) {
+        Ok(weights) => {
+            info!("Loaded scoring weights from {}", path);
+            weights
+        }
+        Err(e) => {
+            warn!("Failed to load scoring weights {}, using defaults: {}", path, e);
+            ScoringWeights::default()
+        }
+    }
+}
+
+// 경보 이벤트에 로컬 점수를 매김
+// severity, classtype, 목적지 자산 중요도, 차단 여부, 같은 쌍의 등장 빈도를 가중치대로 합산
+pub struct AlertScorer {
+    weights: ScoringWeights,
+    pairs: Mutex<HashMap<(String, String), PairSeen>>,
+}
+
+impl AlertScorer {
+    pub fn from_env() -> Self {
+        AlertScorer {
+            weights: load_weights(&env_or("SCORING_FILE", "/logs/scoring.yaml".to_string())),
+            pairs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // 경보에 score 필드를 추가하고 우선 전송 대상인지 반환 (경보가 아니면 false)
+    pub fn score(&self, event: &mut EveEvent, enricher: &Enricher) -> bool {
+        let Some(alert) = event.alert() else {
+            return false;
+        };
+        let weights = &self.weights;
+        let common = event.common();
+
+        let mut factors = ScoreFactors {
+            severity: weights.severity.get(&alert.severity).copied().unwrap_or(0.0),
+            classtype: weights.classtypes.get(&alert.category).copied().unwrap_or(0.0),
+            ..ScoreFactors::default()
+        };
+        if alert.action == "blocked" {
+            factors.blocked = weights.blocked;
+        }
+        if let Some(asset) = common.dest_ip.as_deref()
+            .and_then(|address| address.parse::<IpAddr>().ok())
+            .and_then(|address| enricher.asset(&address))
+            && asset.tags.iter().any(|tag| weights.critical_tags.contains(tag)) {
+            factors.critical_asset = weights.critical_asset;
+        }
+        if let (Some(src), Some(dest)) = (&common.src_ip, &common.dest_ip) {
+            let count = self.seen(src.clone(), dest.clone());
+            factors.pair = if count == 1 {
+                weights.new_pair
+            } else {
+                -weights.repeat_penalty * (count as f64).log10().floor().min(3.0)
+            };
+        }
+
+        let total = factors.severity + factors.classtype + factors.critical_asset + factors.blocked + factors.pair;
+        let value = (total.clamp(0.0, 100.0) * 10.0).round() / 10.0;
+        let priority = value >= weights.priority_threshold;
+        let score = AlertScore { value, priority, factors };
+        if let Ok(score) = serde_json::to_value(score) {
+            event.extra_mut().insert(SCORE_KEY.to_string(), score);
+        }
+        priority
+    }
+
+    // 쌍의 등장 횟수를 올리고 반환
+    fn seen(&self, src: String, dest: String) -> u64 {
+        let now = Instant::now();
+        let window = Duration::from_secs(self.weights.pair_window_secs.max(1));
+        let mut pairs = self.pairs.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if pairs.len() >= PAIR_CAPACITY {
+            pairs.retain(|_, seen| now.duration_since(seen.since) < window);
+            if pairs.len() >= PAIR_CAPACITY {
+                pairs.clear();
+            }
+        }
+        let seen = pairs.entry((src, dest)).or_insert(PairSeen { count: 0, since: now });
+        if now.duration_since(seen.since) >= window {
+            *seen = PairSeen { count: 0, since: now };
+        }
+        seen.count += 1;
+        seen.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample;
+
+    // 기본 가중치 기준 severity 2(25) + Potentially Bad Traffic(5)
+    fn alert(src_ip: &str, dest_ip: &str, action: &str) -> EveEvent {
+        let mut value = sample("alert");
+        value["src_ip"] = src_ip.into();
+        value["dest_ip"] = dest_ip.into();
+        value["alert"]["action"] = action.into();
+        EveEvent::try_from(value).unwrap()
+    }
+
+    fn scorer(weights: ScoringWeights) -> AlertScorer {
+        AlertScorer {
+            weights,
+            pairs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn enricher() -> Enricher {
+        Enricher::with_assets(&format!("{}/tests/fixtures/assets.yaml", env!("CARGO_MANIFEST_DIR")))
+    }
+
+    fn scored(scorer: &AlertScorer, mut event: EveEvent, enricher: &Enricher) -> (bool, Value) {
+        let priority = scorer.score(&mut event, enricher);
+        (priority, event.extra().get(SCORE_KEY).cloned().unwrap_or(Value::Null))
+    }
+
+    #[test]
+    fn factors_are_summed() {
+        let scorer = scorer(ScoringWeights::default());
+        let enricher = enricher();
+
+        let (priority, score) = scored(&scorer, alert("10.0.0.9", "93.184.216.34", "allowed"), &enricher);
+        assert!(!priority);
+        assert_eq!(score["value"], 40.0);
+        assert_eq!(score["factors"]["severity"], 25.0);
+        assert_eq!(score["factors"]["classtype"], 5.0);
+        assert_eq!(score["factors"]["pair"], 10.0);
+        assert_eq!(score["priority"], false);
+
+        let (priority, score) = scored(&scorer, alert("10.0.0.9", "1.1.1.1", "blocked"), &enricher);
+        assert!(priority);
+        assert_eq!(score["value"], 60.0);
+        assert_eq!(score["factors"]["blocked"], 20.0);
+    }
+
+    #[test]
+    fn critical_assets_are_matched_by_tag() {
+        let enricher = enricher();
+        let (_, score) = scored(&scorer(ScoringWeights::default()), alert("10.0.0.9", "10.0.0.5", "allowed"), &enricher);
+        assert_eq!(score["factors"]["critical_asset"], 0.0);
+
+        let weights = ScoringWeights {
+            critical_tags: vec!["ci".to_string()],
+            ..ScoringWeights::default()
+        };
+        let (priority, score) = scored(&scorer(weights), alert("10.0.0.9", "10.0.0.5", "allowed"), &enricher);
+        assert!(priority);
+        assert_eq!(score["factors"]["critical_asset"], 25.0);
+        assert_eq!(score["value"], 65.0);
+    }
+
+    #[test]
+    fn repeated_pairs_are_penalized_per_order_of_magnitude() {
+        let scorer = scorer(ScoringWeights::default());
+        let enricher = enricher();
+        let mut pair_scores = Vec::new();
+        for count in 1..=1000 {
+            let (_, score) = scored(&scorer, alert("10.0.0.9", "93.184.216.34", "allowed"), &enricher);
+            if [1, 2, 9, 10, 99, 100, 1000].contains(&count) {
+                pair_scores.push(score["factors"]["pair"].as_f64().unwrap());
+            }
+        }
+        assert_eq!(pair_scores, vec![10.0, 0.0, 0.0, -5.0, -5.0, -10.0, -15.0]);
+
+        // 다른 쌍은 따로 셈
+        let (_, score) = scored(&scorer, alert("10.0.0.9", "1.1.1.1", "allowed"), &enricher);
+        assert_eq!(score["factors"]["pair"], 10.0);
+    }
+
+    #[test]
+    fn total_is_clamped_and_non_alerts_are_skipped() {
+        let weights = ScoringWeights {
+            severity: BTreeMap::from([(2, 150.0)]),
+            ..ScoringWeights::default()
+        };
+        let scorer = scorer(weights);
+        let enricher = enricher();
+        let (priority, score) = scored(&scorer, alert("10.0.0.9", "1.1.1.1", "allowed"), &enricher);
+        assert!(priority);
+        assert_eq!(score["value"], 100.0);
+
+        let (priority, score) = scored(&scorer, EveEvent::try_from(sample("flow")).unwrap(), &enricher);
+        assert!(!priority);
+        assert!(score.is_null());
+    }
+}
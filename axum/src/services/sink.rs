@@ -0,0 +1,741 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use reqwest::{header::CONTENT_TYPE, StatusCode};
+use serde_json::Value;
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncWrite, AsyncWriteExt},
+    net::{lookup_host, TcpStream, UdpSocket},
+    sync::Notify,
+};
+use tokio_rustls::{
+    client::TlsStream,
+    rustls::{pki_types::ServerName, ClientConfig, RootCertStore},
+    TlsConnector,
+};
+use tracing::{error, info, warn};
+
+use crate::models::sink::{SinkConfig, SinkRoute, SinkStatus, SinkTransport, SinksFile, SyslogFraming};
+use crate::services::metrics::Metrics;
+use crate::services::sink_format::MessageFormatter;
+use crate::services::sink_route::{Route, RoutedEvent};
+use crate::services::spool::Spool;
+use crate::utils::config::env_or;
+use crate::utils::random::random_u64;
+use crate::utils::time::now_secs;
+
+// 연결/전송 제한 시간
+const IO_TIMEOUT: Duration = Duration::from_secs(10);
+// 큐가 비었을 때 새 이벤트를 기다리는 최대 시간 (보관 기간 정리 주기)
+const IDLE_WAIT: Duration = Duration::from_secs(1);
+const SEGMENT_BYTES: u64 = 8 * 1024 * 1024;
+// 다시 보내도 실패할 데이터그램 (Linux EMSGSIZE)
+const EMSGSIZE: i32 = 90;
+
+fn load_sinks(path: &str) -> SinksFile {
+    if path.is_empty() || !Path::new(path).exists() {
+        info!("No forwarding sinks at '{}'", path);
+        return SinksFile::default();
+    }
+    match fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| serde_yaml::from_str::<SinksFile>(&text).map_err(|e| e.to_string())) {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Failed to load forwarding sinks {}: {}", path, e);
+            SinksFile::default()
+        }
+    }
+}
+
+// 최상위 event_types (이전 형식)를 route로 옮기고, 지정하지 않은 syslog 대상은 경보만
+fn effective_route(config: &SinkConfig) -> Result<SinkRoute, String> {
+    let mut route = config.route.clone();
+    match &config.event_types {
+        Some(_) if !route.event_types.is_empty() => {
+            return Err("set either event_types or route.event_types, not both".to_string());
+        }
+        Some(event_types) => {
+            warn!("Sink '{}': event_types is deprecated, use route.event_types", config.name);
+            route.event_types = event_types.clone();
+        }
+        None if route.event_types.is_empty() && config.transport.is_syslog() => {
+            route.event_types = vec!["alert".to_string()];
+        }
+        None => {}
+    }
+    Ok(route)
+}
+
+// 이름을 바꿔 가며 이전 파일 보관 (path → path.1 → path.2 ...)
+async fn rotate_file(path: &str, keep: usize) -> std::io::Result<()> {
+    if keep == 0 {
+        return tokio::fs::remove_file(path).await;
+    }
+    for index in (1..keep).rev() {
+        let _ = tokio::fs::rename(format!("{}.{}", path, index), format!("{}.{}", path, index + 1)).await;
+    }
+    tokio::fs::rename(path, format!("{}.1", path)).await
+}
+
+fn tls_connector(config: &SinkConfig) -> Result<TlsConnector, String> {
+    let read_pem = |path: &str| File::open(path)
+        .map(BufReader::new)
+        .map_err(|e| format!("Failed to open {}: {}", path, e));
+
+    let mut roots = RootCertStore::empty();
+    match &config.tls.ca_file {
+        Some(ca_file) => {
+            for cert in rustls_pemfile::certs(&mut read_pem(ca_file)?) {
+                let cert = cert.map_err(|e| format!("Invalid certificate in {}: {}", ca_file, e))?;
+                roots.add(cert).map_err(|e| format!("Invalid CA certificate in {}: {}", ca_file, e))?;
+            }
+        }
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+    let client = match (&config.tls.cert_file, &config.tls.key_file) {
+        (Some(cert_file), Some(key_file)) => {
+            let certs = rustls_pemfile::certs(&mut read_pem(cert_file)?)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid certificate in {}: {}", cert_file, e))?;
+            let key = rustls_pemfile::private_key(&mut read_pem(key_file)?)
+                .map_err(|e| format!("Invalid private key in {}: {}", key_file, e))?
+                .ok_or_else(|| format!("No private key in {}", key_file))?;
+            builder.with_client_auth_cert(certs, key)
+                .map_err(|e| format!("Invalid client certificate: {}", e))?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => return Err("tls.cert_file and tls.key_file must be set together".to_string()),
+    };
+    Ok(TlsConnector::from(Arc::new(client)))
+}
+
+enum Connection {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+    // 열린 파일과 현재 크기
+    File(tokio::fs::File, u64),
+}
+
+// 배치 하나의 전송 결과
+enum Outcome {
+    Sent,
+    // 재시도해도 받아들여지지 않을 메시지 수와 사유 (버리고 나머지는 전송 완료)
+    Rejected(u64, String),
+}
+
+// 같은 메시지를 다시 보내도 실패할 오류인지
+fn is_permanent(error: &std::io::Error) -> bool {
+    error.kind() == std::io::ErrorKind::InvalidInput || error.raw_os_error() == Some(EMSGSIZE)
+}
+
+#[derive(Default)]
+struct SinkState {
+    connected: bool,
+    attempts: u32,
+    routed_events: u64,
+    sent_events: u64,
+    rejected_events: u64,
+    last_sent_at: Option<u64>,
+    last_error: Option<String>,
+}
+
+// 전달 대상 하나 (자체 디스크 큐, 라우팅 조건, 재시도 상태를 가짐)
+pub struct Sink {
+    config: SinkConfig,
+    formatter: MessageFormatter,
+    route: Route,
+    tls: Option<TlsConnector>,
+    http: Option<reqwest::Client>,
+    // Kafka 레코드 키 (JSON pointer)
+    key: Option<String>,
+    queue: Spool,
+    notify: Notify,
+    state: Mutex<SinkState>,
+    metrics: Arc<Metrics>,
+}
+
+impl Sink {
+    fn open(config: SinkConfig, dir: PathBuf, metrics: Arc<Metrics>) -> Result<Self, String> {
+        match config.transport {
+            SinkTransport::Udp | SinkTransport::Tcp | SinkTransport::Tls => {
+                if config.address.rsplit_once(':').is_none() {
+                    return Err(format!("address '{}' must be host:port", config.address));
+                }
+            }
+            SinkTransport::Http | SinkTransport::Webhook | SinkTransport::Kafka => {
+                let url = config.url.as_deref().unwrap_or_default();
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(format!("url '{}' must start with http:// or https://", url));
+                }
+                if config.transport == SinkTransport::Kafka
+                    && config.topic.as_deref().is_none_or(|topic| topic.trim().is_empty()) {
+                    return Err("kafka sinks need a topic".to_string());
+                }
+            }
+            SinkTransport::File => {
+                if config.path.as_deref().is_none_or(str::is_empty) {
+                    return Err("file sinks need a path".to_string());
+                }
+            }
+        }
+        let mut config = config;
+        config.route = effective_route(&config)?;
+        let route = Route::compile(&config.route)?;
+        let tls = match config.transport {
+            SinkTransport::Tls => Some(tls_connector(&config)?),
+            _ => None,
+        };
+        let http = match config.transport {
+            SinkTransport::Http | SinkTransport::Webhook | SinkTransport::Kafka => Some(
+                reqwest::Client::builder()
+                    .timeout(IO_TIMEOUT)
+                    .build()
+                    .map_err(|e| format!("Failed to build HTTP client: {}", e))?,
+            ),
+            _ => None,
+        };
+        let queue = Spool::open(
+            dir,
+            SEGMENT_BYTES,
+            config.queue.max_mb * 1024 * 1024,
+            Duration::from_secs(config.queue.max_age_hours * 3600),
+            env_or("SPOOL_FSYNC", true),
+        )?;
+        metrics.sink_queue(&config.name, queue.pending_events());
+        metrics.sink_up(&config.name, true);
+
+        Ok(Sink {
+            formatter: MessageFormatter::new(&config),
+            route,
+            tls,
+            http,
+            key: config.key.as_ref().map(|key| format!("/{}", key.replace('.', "/"))),
+            queue,
+            notify: Notify::new(),
+            state: Mutex::new(SinkState::default()),
+            metrics,
+            config,
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, SinkState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    // address, url 또는 path
+    fn target(&self) -> &str {
+        match self.config.transport {
+            SinkTransport::Udp | SinkTransport::Tcp | SinkTransport::Tls => &self.config.address,
+            SinkTransport::Http | SinkTransport::Webhook | SinkTransport::Kafka => self.config.url.as_deref().unwrap_or_default(),
+            SinkTransport::File => self.config.path.as_deref().unwrap_or_default(),
+        }
+    }
+
+    // 한 번에 보낼 최대 이벤트 수 (webhook은 이벤트마다 요청 하나)
+    fn batch_limit(&self) -> usize {
+        match self.config.transport {
+            SinkTransport::Webhook => 1,
+            _ => self.config.queue.batch_size.max(1),
+        }
+    }
+
+    fn append(&self, messages: &[String]) {
+        match self.queue.append(messages) {
+            Ok(pending) => {
+                self.lock().routed_events += messages.len() as u64;
+                self.metrics.sink_queue(self.name(), pending);
+                self.notify.notify_one();
+            }
+            Err(e) => {
+                error!("Failed to queue {} events for sink {}: {}", messages.len(), self.name(), e);
+                self.metrics.sink_events(self.name(), "dropped", messages.len() as u64);
+            }
+        }
+    }
+
+    fn status(&self) -> SinkStatus {
+        let state = self.lock();
+        SinkStatus {
+            name: self.config.name.clone(),
+            transport: self.config.transport,
+            address: self.target().to_string(),
+            format: self.config.transport.is_syslog().then_some(self.config.format),
+            route: self.route.config().clone(),
+            queue: self.queue.status(),
+            healthy: state.attempts == 0,
+            connected: state.connected,
+            retrying: state.attempts > 0,
+            retry_attempts: state.attempts,
+            routed_events: state.routed_events,
+            sent_events: state.sent_events,
+            rejected_events: state.rejected_events,
+            last_sent_at: state.last_sent_at,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    async fn connect(&self) -> Result<Connection, String> {
+        if let SinkTransport::File = self.config.transport {
+            let path = self.target();
+            if let Some(parent) = Path::new(path).parent()
+                && !parent.as_os_str().is_empty() {
+                tokio::fs::create_dir_all(parent).await
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(path).await
+                .map_err(|e| format!("Failed to open {}: {}", path, e))?;
+            let size = file.metadata().await.map(|metadata| metadata.len()).unwrap_or(0);
+            return Ok(Connection::File(file, size));
+        }
+
+        let address = lookup_host(&self.config.address).await
+            .map_err(|e| format!("Failed to resolve {}: {}", self.config.address, e))?
+            .next()
+            .ok_or_else(|| format!("No address for {}", self.config.address))?;
+
+        match self.config.transport {
+            SinkTransport::Udp => {
+                let local = if address.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+                let socket = UdpSocket::bind(local).await.map_err(|e| e.to_string())?;
+                socket.connect(address).await.map_err(|e| e.to_string())?;
+                Ok(Connection::Udp(socket))
+            }
+            _ => {
+                let stream = tokio::time::timeout(IO_TIMEOUT, TcpStream::connect(address)).await
+                    .map_err(|_| format!("Timed out connecting to {}", self.config.address))?
+                    .map_err(|e| format!("Failed to connect to {}: {}", self.config.address, e))?;
+                let _ = stream.set_nodelay(true);
+                let Some(connector) = &self.tls else {
+                    return Ok(Connection::Tcp(stream));
+                };
+
+                let host = self.config.tls.server_name.clone()
+                    .or_else(|| self.config.address.rsplit_once(':')
+                        .map(|(host, _)| host.trim_start_matches('[').trim_end_matches(']').to_string()))
+                    .unwrap_or_default();
+                let server_name = ServerName::try_from(host.clone())
+                    .map_err(|_| format!("Invalid TLS server name '{}'", host))?;
+                let stream = tokio::time::timeout(IO_TIMEOUT, connector.connect(server_name, stream)).await
+                    .map_err(|_| format!("TLS handshake with {} timed out", self.config.address))?
+                    .map_err(|e| format!("TLS handshake with {} failed: {}", self.config.address, e))?;
+                Ok(Connection::Tls(Box::new(stream)))
+            }
+        }
+    }
+
+    // 배치 전송 (UDP는 메시지마다 데이터그램 하나, TCP/TLS는 RFC 6587 구분 방식으로 이어서 씀)
+    // HTTP 계열은 요청 하나, 파일은 줄 단위로 추가
+    async fn send(&self, connection: &mut Option<Connection>, messages: &[String]) -> Result<Outcome, String> {
+        if self.http.is_some() {
+            return self.post(messages).await;
+        }
+        if connection.is_none() {
+            *connection = Some(self.connect().await?);
+            self.lock().connected = true;
+            info!("Sink {} connected to {}", self.name(), self.target());
+        }
+        let Some(open) = connection.as_mut() else {
+            return Ok(Outcome::Sent);
+        };
+
+        let mut rejected: Option<(u64, String)> = None;
+        let sent = match open {
+            // 보낼 수 없는 데이터그램 하나 때문에 배치 전체를 계속 재시도하지 않도록 그 메시지만 버림
+            Connection::Udp(socket) => async {
+                for message in messages {
+                    match socket.send(message.as_bytes()).await {
+                        Ok(_) => {}
+                        Err(e) if is_permanent(&e) => {
+                            let count = rejected.as_ref().map_or(0, |(count, _)| *count);
+                            rejected = Some((count + 1, e.to_string()));
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                Ok(())
+            }.await,
+            Connection::Tcp(stream) => self.write_framed(stream, messages).await,
+            Connection::Tls(stream) => self.write_framed(stream.as_mut(), messages).await,
+            Connection::File(file, size) => async {
+                let mut buffer = messages.join("\n");
+                buffer.push('\n');
+                file.write_all(buffer.as_bytes()).await?;
+                file.flush().await?;
+                *size += buffer.len() as u64;
+                Ok(())
+            }.await,
+        };
+        sent.map_err(|e| format!("Failed to write to {}: {}", self.target(), e))?;
+
+        // 한도를 넘은 파일은 닫고 밀어냄 (다음 배치에서 새 파일)
+        if let Some(Connection::File(_, size)) = connection
+            && *size >= self.config.file.max_mb.max(1) * 1024 * 1024 {
+            *connection = None;
+            if let Err(e) = rotate_file(self.target(), self.config.file.keep).await {
+                warn!("Failed to rotate {} for sink {}: {}", self.target(), self.name(), e);
+            }
+        }
+        Ok(match rejected {
+            Some((count, reason)) => Outcome::Rejected(count, reason),
+            None => Outcome::Sent,
+        })
+    }
+
+    // HTTP JSON 배열, webhook 객체 하나, Kafka REST 프록시 레코드 목록을 POST
+    async fn post(&self, messages: &[String]) -> Result<Outcome, String> {
+        let (Some(client), Some(url)) = (&self.http, &self.config.url) else {
+            return Ok(Outcome::Sent);
+        };
+        let (url, content_type, body) = match self.config.transport {
+            SinkTransport::Kafka => (
+                format!("{}/topics/{}", url.trim_end_matches('/'), self.config.topic.as_deref().unwrap_or_default()),
+                "application/vnd.kafka.json.v2+json",
+                self.kafka_records(messages),
+            ),
+            SinkTransport::Webhook => (url.clone(), "application/json", messages.join("\n")),
+            _ => (url.clone(), "application/json", format!("[{}]", messages.join(","))),
+        };
+
+        let mut request = client.post(&url).header(CONTENT_TYPE, content_type).body(body);
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        let response = request.send().await
+            .map_err(|e| format!("Request to {} failed: {}", url, e))?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(Outcome::Sent)
+        } else if status.is_client_error()
+            && status != StatusCode::REQUEST_TIMEOUT
+            && status != StatusCode::TOO_MANY_REQUESTS {
+            Ok(Outcome::Rejected(messages.len() as u64, format!("Rejected with {}", status)))
+        } else {
+            Err(format!("{} responded with {}", url, status))
+        }
+    }
+
+    // {"records":[{"key":..., "value":{EVE}}]}
+    fn kafka_records(&self, messages: &[String]) -> String {
+        let records: Vec<String> = messages.iter()
+            .map(|message| {
+                let key = self.key.as_ref().and_then(|pointer| {
+                    serde_json::from_str::<Value>(message).ok()?.pointer(pointer).cloned()
+                });
+                match key {
+                    Some(key) => format!("{{\"key\":{},\"value\":{}}}", key, message),
+                    None => format!("{{\"value\":{}}}", message),
+                }
+            })
+            .collect();
+        format!("{{\"records\":[{}]}}", records.join(","))
+    }
+
+    async fn write_framed<W: AsyncWrite + Unpin>(&self, writer: &mut W, messages: &[String]) -> std::io::Result<()> {
+        let mut buffer = Vec::new();
+        for message in messages {
+            match self.config.syslog.framing {
+                SyslogFraming::OctetCounting => {
+                    buffer.extend_from_slice(format!("{} ", message.len()).as_bytes());
+                    buffer.extend_from_slice(message.as_bytes());
+                }
+                SyslogFraming::NonTransparent => {
+                    buffer.extend_from_slice(message.as_bytes());
+                    buffer.push(b'\n');
+                }
+            }
+        }
+        let write = async {
+            writer.write_all(&buffer).await?;
+            writer.flush().await
+        };
+        tokio::time::timeout(IO_TIMEOUT, write).await
+            .unwrap_or_else(|_| Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "write timed out")))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let base = Duration::from_millis(self.config.retry.base_ms.max(1));
+        let exponential = base.saturating_mul(1u32 << attempt.saturating_sub(1).min(20));
+        let ceiling = exponential.min(Duration::from_secs(self.config.retry.max_secs.max(1)));
+        // 절반은 고정, 나머지 절반은 무작위
+        let half = ceiling / 2;
+        let jitter = random_u64() % (half.as_millis() as u64 + 1);
+        half + Duration::from_millis(jitter)
+    }
+
+    // 큐에 쌓인 메시지를 순서대로 전송, 실패하면 다시 연결해 같은 배치부터 재시도
+    async fn run(&self) {
+        let mut cursor = self.queue.committed();
+        let mut connection: Option<Connection> = None;
+        loop {
+            cursor = cursor.max(self.queue.committed());
+            let (messages, end) = match self.queue.read(cursor, self.batch_limit()) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    error!("Failed to read queue of sink {}: {}", self.name(), e);
+                    tokio::time::sleep(IDLE_WAIT).await;
+                    continue;
+                }
+            };
+            if messages.is_empty() {
+                let _ = tokio::time::timeout(IDLE_WAIT, self.notify.notified()).await;
+                self.queue.enforce_age();
+                continue;
+            }
+
+            let count = messages.len() as u64;
+            match self.send(&mut connection, &messages).await {
+                Ok(outcome) => {
+                    self.queue.commit(end, count);
+                    cursor = end;
+                    self.metrics.sink_queue(self.name(), self.queue.pending_events());
+                    self.metrics.sink_up(self.name(), true);
+                    let (rejected, reason) = match outcome {
+                        Outcome::Sent => (0, None),
+                        Outcome::Rejected(rejected, reason) => (rejected.min(count), Some(reason)),
+                    };
+                    let sent = count - rejected;
+                    let mut state = self.lock();
+                    if sent > 0 {
+                        self.metrics.sink_events(self.name(), "sent", sent);
+                        state.sent_events += sent;
+                        state.last_sent_at = Some(now_secs());
+                        state.last_error = None;
+                    }
+                    if let Some(reason) = reason {
+                        warn!("Dropped {} of {} events rejected by sink {} ({})", rejected, count, self.config.name, reason);
+                        self.metrics.sink_events(self.name(), "rejected", rejected);
+                        state.rejected_events += rejected;
+                        state.last_error = Some(reason);
+                    }
+                    state.connected = true;
+                    if state.attempts > 0 {
+                        info!("Sink {} recovered after {} attempts", self.config.name, state.attempts);
+                    }
+                    state.attempts = 0;
+                }
+                Err(e) => {
+                    connection = None;
+                    self.metrics.sink_events(self.name(), "failed", count);
+                    self.metrics.sink_up(self.name(), false);
+                    let attempts = {
+                        let mut state = self.lock();
+                        state.connected = false;
+                        state.attempts = state.attempts.saturating_add(1);
+                        state.last_error = Some(e.clone());
+                        state.attempts
+                    };
+                    let wait = self.backoff(attempts);
+                    warn!("Sink {} failed (attempt {}), retrying in {:?}: {}", self.name(), attempts, wait, e);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+}
+
+// 수신 요청 하나에서 싱크별로 모은 메시지
+pub struct SinkBatch(Vec<Vec<String>>);
+
+// 중앙 API 외 추가 전달 대상 목록과 중앙 API 라우팅 조건 (SINKS_FILE)
+pub struct SinkManager {
+    central: Route,
+    sinks: Vec<Arc<Sink>>,
+}
+
+impl SinkManager {
+    pub fn from_env(metrics: Arc<Metrics>) -> Self {
+        let path = env_or("SINKS_FILE", "/logs/sinks.yaml".to_string());
+        let dir = PathBuf::from(env_or("SPOOL_DIR", "/logs/spool".to_string())).join("sinks");
+        let file = load_sinks(&path);
+        let central = Route::compile(&file.central.route).unwrap_or_else(|e| {
+            error!("Ignoring central API route in {}: {}", path, e);
+            Route::default()
+        });
+        let mut names = HashSet::new();
+        let mut sinks = Vec::new();
+        for config in file.sinks {
+            let name = config.name.clone();
+            let valid_name = !name.is_empty()
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid_name || !names.insert(name.clone()) {
+                error!("Skipping sink '{}': name must be unique and use only letters, digits, '-' or '_'", name);
+                continue;
+            }
+            match Sink::open(config, dir.join(&name), metrics.clone()) {
+                Ok(sink) => {
+                    info!("Forwarding to sink {} ({:?} {})", name, sink.config.transport, sink.target());
+                    sinks.push(Arc::new(sink));
+                }
+                Err(e) => error!("Skipping sink '{}': {}", name, e),
+            }
+        }
+        SinkManager { central, sinks }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    // 중앙 API로도 보낼 이벤트인지
+    pub fn to_central(&self, routed: &RoutedEvent) -> bool {
+        self.central.matches(routed)
+    }
+
+    pub fn batch(&self) -> SinkBatch {
+        SinkBatch(vec![Vec::new(); self.sinks.len()])
+    }
+
+    // 라우팅 조건에 맞는 싱크마다 형식에 맞춰 변환해 모음 (하나라도 받으면 true)
+    pub fn collect(&self, batch: &mut SinkBatch, routed: &RoutedEvent) -> bool {
+        let mut accepted = false;
+        for (sink, messages) in self.sinks.iter().zip(batch.0.iter_mut()) {
+            if sink.route.matches(routed)
+                && let Some(message) = sink.formatter.format(routed) {
+                messages.push(message);
+                accepted = true;
+            }
+        }
+        accepted
+    }
+
+    // 싱크별 큐에 기록 (싱크 실패는 중앙 전달에 영향 없음)
+    pub fn append(&self, batch: SinkBatch) {
+        for (sink, messages) in self.sinks.iter().zip(batch.0) {
+            if !messages.is_empty() {
+                sink.append(&messages);
+            }
+        }
+    }
+
+    pub fn status(&self) -> Vec<SinkStatus> {
+        self.sinks.iter().map(|sink| sink.status()).collect()
+    }
+}
+
+pub fn spawn_sinks(manager: Arc<SinkManager>) {
+    for sink in &manager.sinks {
+        let sink = Arc::clone(sink);
+        tokio::spawn(async move { sink.run().await });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample_event;
+
+    fn parse(yaml: &str) -> SinkConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    // 임시 디렉터리에 큐를 둔 싱크
+    fn open(yaml: &str) -> (Sink, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("sink-test-{}-{}", std::process::id(), random_u64()));
+        let sink = Sink::open(parse(yaml), dir.clone(), Arc::new(Metrics::new())).unwrap();
+        (sink, dir)
+    }
+
+    fn messages(sink: &Sink) -> Vec<String> {
+        let event = sample_event("alert");
+        vec![sink.formatter.format(&RoutedEvent::new(&event)).unwrap()]
+    }
+
+    #[test]
+    fn legacy_event_types_move_into_route() {
+        let config = parse("name: siem\naddress: 127.0.0.1:514\nevent_types: [alert, anomaly]\n");
+        assert_eq!(effective_route(&config).unwrap().event_types, ["alert", "anomaly"]);
+    }
+
+    #[test]
+    fn syslog_sinks_default_to_alerts() {
+        let config = parse("name: siem\naddress: 127.0.0.1:514\n");
+        assert_eq!(effective_route(&config).unwrap().event_types, ["alert"]);
+
+        let config = parse("name: bulk\ntype: http\nurl: http://127.0.0.1/\n");
+        assert!(effective_route(&config).unwrap().event_types.is_empty());
+    }
+
+    #[test]
+    fn both_event_types_are_rejected() {
+        let config = parse("name: siem\naddress: 127.0.0.1:514\nevent_types: [alert]\nroute:\n  event_types: [flow]\n");
+        assert!(effective_route(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn udp_sends_rfc5424_cef() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let yaml = format!("name: siem\naddress: {}\nsyslog:\n  hostname: sensor1\n", server.local_addr().unwrap());
+        let (sink, dir) = open(&yaml);
+
+        let mut connection = None;
+        assert!(matches!(sink.send(&mut connection, &messages(&sink)).await, Ok(Outcome::Sent)));
+        let mut buffer = vec![0u8; 65536];
+        let size = server.recv(&mut buffer).await.unwrap();
+        let message = String::from_utf8_lossy(&buffer[..size]).to_string();
+
+        // local0 (16) * 8 + err (severity 2)
+        assert!(message.starts_with("<131>1 2024-05-14T10:12:33.512345+00:00 sensor1 suricata - alert - "), "{}", message);
+        assert!(message.contains("CEF:0|OISF|Suricata|7.0|2100498|GPL ATTACK_RESPONSE id check returned root|7|"), "{}", message);
+        assert!(message.contains(" src=10.0.0.5 spt=49822 dst=93.184.216.34 dpt=80 "), "{}", message);
+        assert!(message.contains("rt=1715681553512 "), "{}", message);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn udp_drops_datagrams_that_cannot_be_sent() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let (sink, dir) = open(&format!("name: siem\naddress: {}\n", server.local_addr().unwrap()));
+
+        let mut connection = None;
+        let batch = vec!["x".repeat(70000), "small".to_string()];
+        let outcome = sink.send(&mut connection, &batch).await;
+        assert!(matches!(outcome, Ok(Outcome::Rejected(1, _))));
+        let mut buffer = vec![0u8; 65536];
+        let size = server.recv(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..size], b"small");
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn tcp_sends_octet_counted_leef() {
+        let server = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let yaml = format!(
+            "name: qradar\ntype: tcp\nformat: leef\naddress: {}\nsyslog:\n  hostname: sensor1\n  facility: 4\n",
+            server.local_addr().unwrap(),
+        );
+        let (sink, dir) = open(&yaml);
+
+        let batch = messages(&sink);
+        let mut connection = None;
+        let (sent, accepted) = tokio::join!(sink.send(&mut connection, &batch), server.accept());
+        assert!(matches!(sent, Ok(Outcome::Sent)));
+        drop(connection);
+        let mut received = String::new();
+        accepted.unwrap().0.read_to_string(&mut received).await.unwrap();
+
+        let (length, message) = received.split_once(' ').unwrap();
+        assert_eq!(length.parse::<usize>().unwrap(), message.len());
+        assert!(message.starts_with("<35>1 2024-05-14T10:12:33.512345+00:00 sensor1 suricata - alert - "), "{}", message);
+        assert!(message.contains("LEEF:1.0|OISF|Suricata|7.0|2100498|devTime=1715681553512\tsrc=10.0.0.5\t"), "{}", message);
+        assert!(message.ends_with("\tsev=7"), "{}", message);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
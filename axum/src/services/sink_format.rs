@@ -0,0 +1,334 @@
+use serde_json::Value;
+
+use crate::models::sink::{DeviceOptions, SinkConfig, SinkFormat, SinkTransport};
+use crate::services::sink_route::RoutedEvent;
+use crate::utils::time::parse_timestamp_ms;
+
+// UDP 데이터그램 하나에 담을 수 있는 최대 크기 (IPv4 기준)
+const UDP_MAX_PAYLOAD: usize = 65507;
+
+// 유닉스 밀리초로 바꿔 넣는 시각 필드 (CEF rt/start/end, LEEF devTime)
+const TIME_KEYS: [&str; 4] = ["rt", "start", "end", "devTime"];
+
+const DEFAULT_CEF_MAPPING: [(&str, &str); 13] = [
+    ("rt", "timestamp"),
+    ("src", "src_ip"),
+    ("spt", "src_port"),
+    ("dst", "dest_ip"),
+    ("dpt", "dest_port"),
+    ("proto", "proto"),
+    ("app", "app_proto"),
+    ("act", "alert.action"),
+    ("cat", "alert.category"),
+    ("deviceInboundInterface", "in_iface"),
+    ("externalId", "flow_id"),
+    ("cs1Label", "=community_id"),
+    ("cs1", "community_id"),
+];
+
+const DEFAULT_LEEF_MAPPING: [(&str, &str); 11] = [
+    ("devTime", "timestamp"),
+    ("src", "src_ip"),
+    ("srcPort", "src_port"),
+    ("dst", "dest_ip"),
+    ("dstPort", "dest_port"),
+    ("proto", "proto"),
+    ("cat", "alert.category"),
+    ("action", "alert.action"),
+    ("signatureId", "alert.signature_id"),
+    ("signature", "alert.signature"),
+    ("app", "app_proto"),
+];
+
+enum FieldSource {
+    // EVE JSON 안의 경로 (JSON pointer)
+    Path(String),
+    Constant(String),
+}
+
+// EVE 이벤트를 RFC 5424 syslog 메시지 한 줄로 변환
+// MSG 부분은 EVE JSON, CEF, LEEF 중 하나 (syslog 대상이 아니면 EVE JSON 한 줄)
+pub struct MessageFormatter {
+    syslog: bool,
+    format: SinkFormat,
+    facility: u8,
+    hostname: String,
+    app_name: String,
+    // 전송 한도 (넘는 메시지는 잘라냄)
+    max_bytes: Option<usize>,
+    device: DeviceOptions,
+    mapping: Vec<(String, FieldSource)>,
+}
+
+impl MessageFormatter {
+    pub fn new(config: &SinkConfig) -> Self {
+        let defaults: &[(&str, &str)] = match config.format {
+            SinkFormat::Cef => &DEFAULT_CEF_MAPPING,
+            SinkFormat::Leef => &DEFAULT_LEEF_MAPPING,
+            SinkFormat::Json => &[],
+        };
+        let mapping: Vec<(String, String)> = if config.mapping.is_empty() {
+            defaults.iter().map(|(key, path)| (key.to_string(), path.to_string())).collect()
+        } else {
+            config.mapping.iter().map(|(key, path)| (key.clone(), path.clone())).collect()
+        };
+        let hostname = config.syslog.hostname.clone()
+            .or_else(|| dns_lookup::get_hostname().ok())
+            .unwrap_or_default();
+        let max_bytes = match config.transport {
+            SinkTransport::Udp => Some(config.syslog.max_message_bytes.unwrap_or(UDP_MAX_PAYLOAD).min(UDP_MAX_PAYLOAD)),
+            _ => config.syslog.max_message_bytes,
+        };
+
+        MessageFormatter {
+            syslog: config.transport.is_syslog(),
+            format: config.format,
+            facility: config.syslog.facility.min(23),
+            hostname: header_field(&hostname, 255),
+            app_name: header_field(&config.syslog.app_name, 48),
+            max_bytes: max_bytes.map(|max| max.max(1)),
+            device: config.device.clone(),
+            mapping: mapping.into_iter()
+                .map(|(key, path)| {
+                    let source = match path.strip_prefix('=') {
+                        Some(constant) => FieldSource::Constant(constant.to_string()),
+                        None => FieldSource::Path(format!("/{}", path.replace('.', "/"))),
+                    };
+                    (key, source)
+                })
+                .collect(),
+        }
+    }
+
+    pub fn format(&self, routed: &RoutedEvent) -> Option<String> {
+        let event = routed.event;
+        if !self.syslog {
+            return serde_json::to_string(event).ok();
+        }
+        let value = routed.value()?;
+        let severity = value.pointer("/alert/severity").and_then(Value::as_u64);
+        let message = match self.format {
+            SinkFormat::Json => serde_json::to_string(value).ok()?,
+            SinkFormat::Cef => self.cef(value, severity),
+            SinkFormat::Leef => self.leef(value, severity),
+        };
+
+        // Suricata 시각(+0900)을 RFC 3339(+09:00)로
+        let timestamp = value.get("timestamp")
+            .and_then(Value::as_str)
+            .filter(|timestamp| parse_timestamp_ms(timestamp).is_some())
+            .map(rfc3339)
+            .unwrap_or_else(|| "-".to_string());
+        let priority = self.facility as u32 * 8 + syslog_severity(event.event_type(), severity);
+        let mut line = format!(
+            "<{}>1 {} {} {} - {} - {}",
+            priority,
+            timestamp,
+            self.hostname,
+            self.app_name,
+            header_field(event.event_type(), 32),
+            message,
+        );
+        if let Some(max) = self.max_bytes {
+            truncate(&mut line, max);
+        }
+        Some(line)
+    }
+
+    // CEF:0|Vendor|Product|Version|SignatureID|Name|Severity|확장 필드
+    fn cef(&self, value: &Value, severity: Option<u64>) -> String {
+        let (id, name) = signature(value);
+        let extension: Vec<String> = self.fields(value)
+            .map(|(key, field)| format!("{}={}", key, escape_cef_value(&field)))
+            .collect();
+        format!(
+            "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+            escape_header(&self.device.vendor),
+            escape_header(&self.device.product),
+            escape_header(&self.device.version),
+            escape_header(&id),
+            escape_header(&name),
+            event_severity(severity),
+            extension.join(" "),
+        )
+    }
+
+    // LEEF:1.0|Vendor|Product|Version|EventID|탭으로 구분한 속성
+    fn leef(&self, value: &Value, severity: Option<u64>) -> String {
+        let (id, _) = signature(value);
+        let mut attributes: Vec<String> = self.fields(value)
+            .map(|(key, field)| format!("{}={}", key, clean_leef_value(&field)))
+            .collect();
+        if !self.mapping.iter().any(|(key, _)| key == "sev") {
+            attributes.push(format!("sev={}", event_severity(severity)));
+        }
+        format!(
+            "LEEF:1.0|{}|{}|{}|{}|{}",
+            escape_header(&self.device.vendor),
+            escape_header(&self.device.product),
+            escape_header(&self.device.version),
+            escape_header(&id),
+            attributes.join("\t"),
+        )
+    }
+
+    // 매핑된 필드 중 값이 있는 것만
+    fn fields<'a>(&'a self, value: &'a Value) -> impl Iterator<Item = (&'a str, String)> + 'a {
+        self.mapping.iter().filter_map(move |(key, source)| {
+            let field = match source {
+                FieldSource::Constant(constant) => constant.clone(),
+                FieldSource::Path(pointer) => match value.pointer(pointer)? {
+                    Value::Null => return None,
+                    Value::String(text) if TIME_KEYS.contains(&key.as_str()) => {
+                        parse_timestamp_ms(text).map(|ms| ms.to_string()).unwrap_or_else(|| text.clone())
+                    }
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                },
+            };
+            Some((key.as_str(), field))
+        })
+    }
+}
+
+// 경보면 (시그니처 ID, 이름), 아니면 event_type
+fn signature(value: &Value) -> (String, String) {
+    let event_type = value.get("event_type").and_then(Value::as_str).unwrap_or("unknown");
+    match value.get("alert") {
+        Some(alert) => (
+            alert.get("signature_id").map(Value::to_string).unwrap_or_else(|| event_type.to_string()),
+            alert.get("signature").and_then(Value::as_str).unwrap_or(event_type).to_string(),
+        ),
+        None => (event_type.to_string(), event_type.to_string()),
+    }
+}
+
+// Suricata severity (1이 가장 심각) → CEF/LEEF 0~10
+fn event_severity(severity: Option<u64>) -> u8 {
+    match severity {
+        Some(1) => 10,
+        Some(2) => 7,
+        Some(3) => 4,
+        Some(_) => 2,
+        None => 3,
+    }
+}
+
+// Suricata severity → syslog severity (crit, err, warning, notice / 경보가 아니면 info)
+fn syslog_severity(event_type: &str, severity: Option<u64>) -> u32 {
+    match (event_type, severity) {
+        (_, Some(1)) => 2,
+        (_, Some(2)) => 3,
+        (_, Some(3)) => 4,
+        ("alert", _) => 5,
+        _ => 6,
+    }
+}
+
+fn rfc3339(timestamp: &str) -> String {
+    let bytes = timestamp.as_bytes();
+    let len = bytes.len();
+    if len > 5 && matches!(bytes[len - 5], b'+' | b'-') && bytes[len - 4..].iter().all(u8::is_ascii_digit) {
+        format!("{}:{}", &timestamp[..len - 2], &timestamp[len - 2..])
+    } else {
+        timestamp.to_string()
+    }
+}
+
+// RFC 5424 헤더 필드: 출력 가능한 ASCII만, 공백 없이
+fn header_field(value: &str, max: usize) -> String {
+    let field: String = value.chars()
+        .filter(|c| c.is_ascii_graphic())
+        .take(max)
+        .collect();
+    if field.is_empty() { "-".to_string() } else { field }
+}
+
+// UTF-8 문자 경계에서 자름
+fn truncate(message: &mut String, max: usize) {
+    if message.len() > max {
+        let mut end = max;
+        while !message.is_char_boundary(end) {
+            end -= 1;
+        }
+        message.truncate(end);
+    }
+}
+
+fn escape_header(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+fn escape_cef_value(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+fn clean_leef_value(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::eve_json_log::EveEvent;
+
+    fn formatter(yaml: &str) -> MessageFormatter {
+        MessageFormatter::new(&serde_yaml::from_str::<SinkConfig>(yaml).unwrap())
+    }
+
+    fn event(value: Value) -> EveEvent {
+        EveEvent::try_from(value).unwrap()
+    }
+
+    #[test]
+    fn cef_values_are_escaped() {
+        assert_eq!(escape_cef_value("a=b\\c\r\nd|e"), "a\\=b\\\\c\\r\\nd|e");
+        assert_eq!(escape_header("a|b\\c\nd=e"), "a\\|b\\\\c d=e");
+    }
+
+    #[test]
+    fn leef_values_drop_separators() {
+        assert_eq!(clean_leef_value("a\tb\r\nc=d|e"), "a b  c=d|e");
+    }
+
+    #[test]
+    fn header_fields_are_printable_ascii() {
+        assert_eq!(header_field("my host\tname", 255), "myhostname");
+        assert_eq!(header_field("한글", 255), "-");
+        assert_eq!(header_field("abcdef", 3), "abc");
+    }
+
+    #[test]
+    fn suricata_offsets_become_rfc3339() {
+        assert_eq!(rfc3339("2024-05-14T10:12:33.512345+0900"), "2024-05-14T10:12:33.512345+09:00");
+        assert_eq!(rfc3339("2024-05-14T10:12:33Z"), "2024-05-14T10:12:33Z");
+    }
+
+    #[test]
+    fn cef_extension_escapes_event_fields() {
+        let formatter = formatter("name: siem\naddress: 127.0.0.1:514\nsyslog:\n  hostname: h\nmapping:\n  msg: alert.signature\n");
+        let event = event(serde_json::json!({
+            "event_type": "alert",
+            "alert": {"action": "allowed", "gid": 1, "signature_id": 7, "rev": 1, "severity": 1, "signature": "a|b=c\nd"},
+        }));
+        let message = formatter.format(&RoutedEvent::new(&event)).unwrap();
+        assert_eq!(message, "<130>1 - h suricata - alert - CEF:0|OISF|Suricata|7.0|7|a\\|b=c d|10|msg=a|b\\=c\\nd");
+    }
+
+    #[test]
+    fn messages_are_truncated_to_the_limit() {
+        let formatter = formatter("name: siem\naddress: 127.0.0.1:514\nformat: json\nsyslog:\n  max_message_bytes: 40\n");
+        let event = event(serde_json::json!({"event_type": "dns", "dns": {"rrname": "가나다라마바사아자차카타파하"}}));
+        let message = formatter.format(&RoutedEvent::new(&event)).unwrap();
+        assert!(message.len() <= 40);
+
+        let mut text = "가나다".to_string();
+        truncate(&mut text, 4);
+        assert_eq!(text, "가");
+    }
+}
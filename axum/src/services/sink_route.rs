@@ -0,0 +1,185 @@
+use std::cell::OnceCell;
+
+use serde_json::Value;
+
+use crate::models::eve_json_log::EveEvent;
+use crate::models::sink::SinkRoute;
+use crate::utils::cidr::{in_ranges, Cidr};
+
+// 라우팅 판단과 형식 변환에 함께 쓰는 이벤트 (JSON 변환은 필요할 때 한 번만)
+pub struct RoutedEvent<'a> {
+    pub event: &'a EveEvent,
+    value: OnceCell<Option<Value>>,
+}
+
+impl<'a> RoutedEvent<'a> {
+    pub fn new(event: &'a EveEvent) -> Self {
+        RoutedEvent { event, value: OnceCell::new() }
+    }
+
+    pub fn value(&self) -> Option<&Value> {
+        self.value.get_or_init(|| self.event.to_value().ok()).as_ref()
+    }
+}
+
+// 검증을 마치고 CIDR, 필드 경로를 해석해 둔 라우팅 조건 (기본값은 모든 이벤트)
+#[derive(Default)]
+pub struct Route {
+    route: SinkRoute,
+    src: Vec<Cidr>,
+    dest: Vec<Cidr>,
+    // (JSON pointer, 허용 값)
+    fields: Vec<(String, Vec<String>)>,
+}
+
+impl Route {
+    pub fn compile(route: &SinkRoute) -> Result<Route, String> {
+        let parse = |ranges: &[String]| ranges.iter()
+            .map(|range| Cidr::parse(range))
+            .collect::<Result<Vec<_>, _>>();
+        if let Some(path) = route.fields.keys().find(|path| path.trim().is_empty()) {
+            return Err(format!("Invalid route field '{}'", path));
+        }
+        Ok(Route {
+            route: route.clone(),
+            src: parse(&route.src_cidrs)?,
+            dest: parse(&route.dest_cidrs)?,
+            fields: route.fields.iter()
+                .map(|(path, values)| (format!("/{}", path.replace('.', "/")), values.clone()))
+                .collect(),
+        })
+    }
+
+    pub fn config(&self) -> &SinkRoute {
+        &self.route
+    }
+
+    pub fn matches(&self, routed: &RoutedEvent) -> bool {
+        let event = routed.event;
+        if !self.route.event_types.is_empty()
+            && !self.route.event_types.iter().any(|event_type| event_type == event.event_type()) {
+            return false;
+        }
+        if let Some(max) = self.route.max_severity
+            && event.alert().is_none_or(|alert| alert.severity > max) {
+            return false;
+        }
+        if !self.route.signature_ids.is_empty()
+            && event.alert().is_none_or(|alert| !self.route.signature_ids.contains(&alert.signature_id)) {
+            return false;
+        }
+        if let Some(min) = self.route.min_score {
+            let score = event.extra().get("score")
+                .and_then(|score| score.get("value"))
+                .and_then(Value::as_f64);
+            if score.is_none_or(|score| score < min) {
+                return false;
+            }
+        }
+        let common = event.common();
+        if !in_ranges(&self.src, common.src_ip.as_deref()) || !in_ranges(&self.dest, common.dest_ip.as_deref()) {
+            return false;
+        }
+        if self.fields.is_empty() {
+            return true;
+        }
+        // 필드 조건은 문자열로 비교 (숫자, 불리언도 JSON 표기 그대로)
+        let Some(value) = routed.value() else {
+            return false;
+        };
+        self.fields.iter().all(|(pointer, allowed)| match value.pointer(pointer) {
+            Some(Value::String(text)) => allowed.iter().any(|item| item == text),
+            Some(Value::Null) | None => false,
+            Some(other) => allowed.contains(&other.to_string()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::eve_json_log::test_support::sample;
+
+    fn event(value: Value) -> EveEvent {
+        EveEvent::try_from(value).unwrap()
+    }
+
+    fn route(yaml: &str) -> Route {
+        Route::compile(&serde_yaml::from_str(yaml).unwrap()).unwrap()
+    }
+
+    fn matches(route: &Route, event: &EveEvent) -> bool {
+        route.matches(&RoutedEvent::new(event))
+    }
+
+    #[test]
+    fn empty_route_matches_everything() {
+        let route = Route::default();
+        for event_type in ["alert", "flow", "dns", "stats"] {
+            assert!(matches(&route, &event(sample(event_type))), "{}", event_type);
+        }
+    }
+
+    #[test]
+    fn alert_conditions() {
+        let route = route("{event_types: [alert], max_severity: 2, signature_ids: [2100498], min_score: 50}");
+        let mut alert = sample("alert");
+        alert["score"] = serde_json::json!({"value": 65.0});
+        assert!(matches(&route, &event(alert.clone())));
+
+        let mut low_score = alert.clone();
+        low_score["score"]["value"] = 49.9.into();
+        assert!(!matches(&route, &event(low_score)));
+
+        let mut unscored = alert.clone();
+        unscored.as_object_mut().unwrap().remove("score");
+        assert!(!matches(&route, &event(unscored)));
+
+        let mut minor = alert.clone();
+        minor["alert"]["severity"] = 3.into();
+        assert!(!matches(&route, &event(minor)));
+
+        let mut other_signature = alert;
+        other_signature["alert"]["signature_id"] = 1.into();
+        assert!(!matches(&route, &event(other_signature)));
+
+        // 경보 조건은 경보가 아닌 이벤트와 일치하지 않음
+        let route = self::route("max_severity: 3");
+        assert!(!matches(&route, &event(sample("flow"))));
+    }
+
+    #[test]
+    fn address_ranges() {
+        let route = route("{src_cidrs: [10.0.0.0/24], dest_cidrs: [10.0.0.1/32, 1.1.1.0/24]}");
+        assert!(matches(&route, &event(sample("dns"))));
+        assert!(matches(&route, &event(sample("tls"))));
+        assert!(!matches(&route, &event(sample("alert"))));
+        assert!(!matches(&route, &event(sample("drop"))));
+        // 주소가 없는 이벤트는 범위 조건과 일치하지 않음
+        assert!(!matches(&route, &event(sample("stats"))));
+    }
+
+    #[test]
+    fn field_values_compare_as_text() {
+        let route = route("fields: {dns.rrname: [example.com, example.org], dns.id: ['4242'], dns.qr: ['true']}");
+        assert!(matches(&route, &event(sample("dns"))));
+
+        let mut other = sample("dns");
+        other["dns"]["rrname"] = "example.net".into();
+        assert!(!matches(&route, &event(other)));
+
+        let mut missing = sample("dns");
+        missing["dns"].as_object_mut().unwrap().remove("qr");
+        assert!(!matches(&route, &event(missing)));
+
+        assert!(!matches(&route, &event(sample("tls"))));
+    }
+
+    #[test]
+    fn invalid_routes_are_rejected() {
+        for yaml in ["src_cidrs: [bogus]", "dest_cidrs: [10.0.0.0/33]", "fields: {' ': [x]}"] {
+            let config: SinkRoute = serde_yaml::from_str(yaml).unwrap();
+            assert!(Route::compile(&config).is_err(), "{}", yaml);
+        }
+    }
+}
@@ -0,0 +1,482 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use tracing::{info, warn};
+
+use crate::models::spool::SpoolStatus;
+
+const SEGMENT_PREFIX: &str = "segment-";
+const SEGMENT_SUFFIX: &str = ".jsonl";
+const CURSOR_FILE: &str = "cursor";
+
+// 스풀 안의 위치 (세그먼트 번호, 바이트 오프셋)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Position {
+    pub segment: u64,
+    pub offset: u64,
+}
+
+struct SpoolState {
+    active: u64,
+    writer: File,
+    active_bytes: u64,
+    // 세그먼트별 크기 (쓰는 중인 세그먼트 제외)와 전체 크기
+    sealed: BTreeMap<u64, u64>,
+    total_bytes: u64,
+    // 전달이 확인된 위치 (재시작 시 여기부터 다시 읽음)
+    committed: Position,
+    pending_events: u64,
+    dropped_events: u64,
+}
+
+impl SpoolState {
+    // 지운 세그먼트를 크기 집계에서 뺌
+    fn forget(&mut self, segment: u64) {
+        if let Some(bytes) = self.sealed.remove(&segment) {
+            self.total_bytes = self.total_bytes.saturating_sub(bytes);
+        }
+    }
+}
+
+// 한 줄에 이벤트 하나씩 기록하는 세그먼트 파일 기반 디스크 스풀
+pub struct Spool {
+    dir: PathBuf,
+    segment_bytes: u64,
+    max_bytes: u64,
+    max_age: Duration,
+    fsync: bool,
+    state: Mutex<SpoolState>,
+}
+
+impl Spool {
+    pub fn open(
+        dir: PathBuf,
+        segment_bytes: u64,
+        max_bytes: u64,
+        max_age: Duration,
+        fsync: bool,
+    ) -> Result<Self, String> {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create spool directory {}: {}", dir.display(), e))?;
+
+        let segments = list_segments(&dir)?;
+        let mut committed = read_cursor(&dir).unwrap_or_default();
+        // 커서가 가리키는 세그먼트가 이미 지워졌으면 남은 첫 세그먼트부터
+        if let Some(first) = segments.first()
+            && committed.segment < *first {
+            committed = Position { segment: *first, offset: 0 };
+        }
+
+        // 기록 도중 중단되어 마지막 세그먼트 끝에 남은 불완전한 줄은 잘라냄
+        if let Some(last) = segments.last() {
+            truncate_partial_line(&segment_path(&dir, *last))?;
+        }
+
+        let mut pending_events = 0;
+        for segment in segments.iter().filter(|segment| **segment >= committed.segment) {
+            let offset = if *segment == committed.segment { committed.offset } else { 0 };
+            pending_events += count_lines(&segment_path(&dir, *segment), offset);
+        }
+
+        let active = segments.last().copied().unwrap_or(committed.segment).max(committed.segment);
+        let (writer, active_bytes) = open_segment(&dir, active)?;
+        let sealed: BTreeMap<u64, u64> = segments.iter()
+            .filter(|segment| **segment != active)
+            .map(|segment| (*segment, fs::metadata(segment_path(&dir, *segment)).map(|metadata| metadata.len()).unwrap_or(0)))
+            .collect();
+        let total_bytes = sealed.values().sum::<u64>() + active_bytes;
+        if pending_events > 0 {
+            info!("Spool at {} has {} events pending replay", dir.display(), pending_events);
+        }
+
+        Ok(Spool {
+            dir,
+            segment_bytes: segment_bytes.max(1024),
+            max_bytes,
+            max_age,
+            fsync,
+            state: Mutex::new(SpoolState {
+                active,
+                writer,
+                active_bytes,
+                sealed,
+                total_bytes,
+                committed,
+                pending_events,
+                dropped_events: 0,
+            }),
+        })
+    }
+
+    // 이벤트(JSON 한 줄) 추가 후 대기 중인 이벤트 수 반환
+    pub fn append(&self, lines: &[String]) -> Result<u64, String> {
+        let mut state = self.lock();
+
+        if state.active_bytes >= self.segment_bytes {
+            let next = state.active + 1;
+            let (writer, bytes) = open_segment(&self.dir, next)?;
+            let (active, active_bytes) = (state.active, state.active_bytes);
+            state.sealed.insert(active, active_bytes);
+            state.active = next;
+            state.writer = writer;
+            state.active_bytes = bytes;
+            state.total_bytes += bytes;
+        }
+
+        let mut buffer = String::new();
+        for line in lines {
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+        let written = state.writer.write_all(buffer.as_bytes())
+            .map_err(|e| format!("Failed to write spool segment: {}", e))
+            .and_then(|_| match self.fsync {
+                true => state.writer.sync_data().map_err(|e| format!("Failed to sync spool segment: {}", e)),
+                false => Ok(()),
+            });
+        if let Err(e) = written {
+            // 일부만 기록된 줄이 다음 이벤트와 섞이지 않도록 잘라냄
+            let _ = state.writer.set_len(state.active_bytes);
+            return Err(e);
+        }
+        state.active_bytes += buffer.len() as u64;
+        state.total_bytes += buffer.len() as u64;
+        state.pending_events += lines.len() as u64;
+
+        if self.max_bytes > 0 && state.total_bytes > self.max_bytes {
+            self.drop_oldest(&mut state, |_| true);
+        }
+        Ok(state.pending_events)
+    }
+
+    // from 위치부터 최대 max개 이벤트와 다음 읽을 위치 반환
+    pub fn read(&self, from: Position, max: usize) -> Result<(Vec<String>, Position), String> {
+        let (active, committed) = {
+            let state = self.lock();
+            (state.active, state.committed)
+        };
+        let mut position = from.max(committed);
+        let mut lines = Vec::new();
+
+        while lines.len() < max && position.segment <= active {
+            let path = segment_path(&self.dir, position.segment);
+            let file = match File::open(&path) {
+                Ok(file) => file,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    position = Position { segment: position.segment + 1, offset: 0 };
+                    continue;
+                }
+                Err(e) => return Err(format!("Failed to open {}: {}", path.display(), e)),
+            };
+
+            let mut reader = BufReader::new(file);
+            reader.seek(SeekFrom::Start(position.offset))
+                .map_err(|e| format!("Failed to seek {}: {}", path.display(), e))?;
+            let mut line = String::new();
+            while lines.len() < max {
+                line.clear();
+                let read = reader.read_line(&mut line)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                // 쓰는 중인 마지막 줄은 건너뜀
+                if read == 0 || !line.ends_with('\n') {
+                    break;
+                }
+                position.offset += read as u64;
+                lines.push(line.trim_end().to_string());
+            }
+
+            if lines.len() < max && position.segment < active {
+                position = Position { segment: position.segment + 1, offset: 0 };
+            } else {
+                break;
+            }
+        }
+        Ok((lines, position))
+    }
+
+    // 전달 확인된 위치까지 커서 이동 후 다 읽은 세그먼트 삭제
+    pub fn commit(&self, position: Position, events: u64) {
+        let mut state = self.lock();
+        if position <= state.committed {
+            return;
+        }
+        state.committed = position;
+        state.pending_events = state.pending_events.saturating_sub(events);
+
+        if let Err(e) = write_cursor(&self.dir, position) {
+            warn!("Failed to persist spool cursor: {}", e);
+        }
+        let done: Vec<u64> = state.sealed.range(..position.segment).map(|(segment, _)| *segment).collect();
+        for segment in done {
+            remove_segment(&self.dir, segment);
+            state.forget(segment);
+        }
+    }
+
+    // 보관 기간을 넘긴 세그먼트 삭제
+    pub fn enforce_age(&self) {
+        if self.max_age.is_zero() {
+            return;
+        }
+        let mut state = self.lock();
+        let max_age = self.max_age;
+        let dir = self.dir.clone();
+        self.drop_oldest(&mut state, |segment| {
+            fs::metadata(segment_path(&dir, segment))
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > max_age)
+        });
+    }
+
+    pub fn committed(&self) -> Position {
+        self.lock().committed
+    }
+
+    pub fn pending_events(&self) -> u64 {
+        self.lock().pending_events
+    }
+
+    pub fn status(&self) -> SpoolStatus {
+        let state = self.lock();
+        let oldest = state.sealed.keys().next().copied().unwrap_or(state.active);
+        let oldest_age_secs = fs::metadata(segment_path(&self.dir, oldest)).ok()
+            .and_then(|metadata| metadata.modified().ok())
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .map(|age| age.as_secs());
+
+        SpoolStatus {
+            pending_events: state.pending_events,
+            pending_bytes: state.total_bytes.saturating_sub(state.committed.offset),
+            segments: state.sealed.len() + 1,
+            oldest_segment_age_secs: oldest_age_secs,
+            dropped_events: state.dropped_events,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SpoolState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 조건에 맞는 오래된 세그먼트부터 삭제 (쓰는 중인 세그먼트 제외), 크기 한도 안으로 들어오면 중단
+    fn drop_oldest<F: Fn(u64) -> bool>(&self, state: &mut SpoolState, expired: F) {
+        let sealed: Vec<u64> = state.sealed.keys().copied().collect();
+        for segment in sealed {
+            let over_size = self.max_bytes > 0 && state.total_bytes > self.max_bytes;
+            if !over_size && !expired(segment) {
+                continue;
+            }
+
+            let offset = if segment == state.committed.segment { state.committed.offset } else { 0 };
+            let lost = if segment >= state.committed.segment {
+                count_lines(&segment_path(&self.dir, segment), offset)
+            } else {
+                0
+            };
+            remove_segment(&self.dir, segment);
+            state.forget(segment);
+            state.pending_events = state.pending_events.saturating_sub(lost);
+            state.dropped_events += lost;
+            if state.committed.segment <= segment {
+                state.committed = Position { segment: segment + 1, offset: 0 };
+                if let Err(e) = write_cursor(&self.dir, state.committed) {
+                    warn!("Failed to persist spool cursor: {}", e);
+                }
+            }
+            if lost > 0 {
+                warn!("Dropped {} spooled events from segment {} (size or age cap)", lost, segment);
+            }
+        }
+    }
+}
+
+fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!("{}{:020}{}", SEGMENT_PREFIX, segment, SEGMENT_SUFFIX))
+}
+
+fn list_segments(dir: &Path) -> Result<Vec<u64>, String> {
+    let entries = fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read spool directory {}: {}", dir.display(), e))?;
+    let mut segments: Vec<u64> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            entry.file_name()
+                .to_str()?
+                .strip_prefix(SEGMENT_PREFIX)?
+                .strip_suffix(SEGMENT_SUFFIX)?
+                .parse()
+                .ok()
+        })
+        .collect();
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+fn open_segment(dir: &Path, segment: u64) -> Result<(File, u64), String> {
+    let path = segment_path(dir, segment);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| format!("Failed to open spool segment {}: {}", path.display(), e))?;
+    let bytes = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+    Ok((file, bytes))
+}
+
+fn truncate_partial_line(path: &Path) -> Result<(), String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read spool segment {}: {}", path.display(), e))?;
+    if data.is_empty() || data.ends_with(b"\n") {
+        return Ok(());
+    }
+    let complete = data.iter().rposition(|byte| *byte == b'\n').map_or(0, |index| index + 1);
+    warn!("Discarding {} bytes of an incomplete event at the end of {}", data.len() - complete, path.display());
+    OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_len(complete as u64))
+        .map_err(|e| format!("Failed to repair spool segment {}: {}", path.display(), e))
+}
+
+fn remove_segment(dir: &Path, segment: u64) {
+    let path = segment_path(dir, segment);
+    if let Err(e) = fs::remove_file(&path)
+        && e.kind() != std::io::ErrorKind::NotFound {
+        warn!("Failed to remove spool segment {}: {}", path.display(), e);
+    }
+}
+
+fn count_lines(path: &Path, offset: u64) -> u64 {
+    let Ok(mut file) = File::open(path) else {
+        return 0;
+    };
+    if file.seek(SeekFrom::Start(offset)).is_err() {
+        return 0;
+    }
+    BufReader::new(file).lines().map_while(Result::ok).count() as u64
+}
+
+fn read_cursor(dir: &Path) -> Option<Position> {
+    let text = fs::read_to_string(dir.join(CURSOR_FILE)).ok()?;
+    let (segment, offset) = text.trim().split_once(' ')?;
+    Some(Position {
+        segment: segment.parse().ok()?,
+        offset: offset.parse().ok()?,
+    })
+}
+
+// 임시 파일에 쓴 뒤 교체해 중간 상태가 남지 않게 함
+fn write_cursor(dir: &Path, position: Position) -> Result<(), String> {
+    let temp = dir.join(format!("{}.tmp", CURSOR_FILE));
+    fs::write(&temp, format!("{} {}\n", position.segment, position.offset))
+        .and_then(|_| fs::rename(&temp, dir.join(CURSOR_FILE)))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::random::random_u64;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("spool-test-{}-{}", std::process::id(), random_u64()))
+    }
+
+    fn open(dir: &Path, max_bytes: u64) -> Spool {
+        Spool::open(dir.to_path_buf(), 1024, max_bytes, Duration::ZERO, false).unwrap()
+    }
+
+    fn disk_bytes(dir: &Path) -> u64 {
+        list_segments(dir).unwrap().iter()
+            .map(|segment| fs::metadata(segment_path(dir, *segment)).unwrap().len())
+            .sum()
+    }
+
+    #[test]
+    fn size_cap_drops_oldest_segments() {
+        let dir = temp_dir();
+        let spool = open(&dir, 4096);
+        let line = "x".repeat(499);
+        for _ in 0..40 {
+            spool.append(std::slice::from_ref(&line)).unwrap();
+        }
+
+        let status = spool.status();
+        assert!(status.dropped_events > 0);
+        assert_eq!(status.pending_events + status.dropped_events, 40);
+        assert_eq!(status.segments, list_segments(&dir).unwrap().len());
+        // 따로 세어 둔 크기가 디스크와 같아야 함
+        assert_eq!(status.pending_bytes, disk_bytes(&dir));
+        assert!(status.pending_bytes <= 4096 + 1024);
+
+        let reopened = open(&dir, 4096);
+        assert_eq!(reopened.status().pending_bytes, status.pending_bytes);
+        assert_eq!(reopened.pending_events(), status.pending_events);
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    fn lines(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn reopen_resumes_after_committed_events() {
+        let dir = temp_dir();
+        let spool = open(&dir, 0);
+        spool.append(&lines(&["{\"n\":1}", "{\"n\":2}", "{\"n\":3}"])).unwrap();
+        let (read, end) = spool.read(spool.committed(), 2).unwrap();
+        assert_eq!(read, lines(&["{\"n\":1}", "{\"n\":2}"]));
+        spool.commit(end, 2);
+        drop(spool);
+
+        let reopened = open(&dir, 0);
+        assert_eq!(reopened.pending_events(), 1);
+        assert_eq!(reopened.committed(), end);
+        let (read, _) = reopened.read(reopened.committed(), 10).unwrap();
+        assert_eq!(read, lines(&["{\"n\":3}"]));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reopen_discards_incomplete_last_line() {
+        let dir = temp_dir();
+        let spool = open(&dir, 0);
+        spool.append(&lines(&["{\"n\":1}"])).unwrap();
+        drop(spool);
+        let segment = segment_path(&dir, list_segments(&dir).unwrap()[0]);
+        let mut file = OpenOptions::new().append(true).open(&segment).unwrap();
+        file.write_all(b"{\"n\":").unwrap();
+
+        let reopened = open(&dir, 0);
+        assert_eq!(reopened.pending_events(), 1);
+        reopened.append(&lines(&["{\"n\":2}"])).unwrap();
+        let (read, _) = reopened.read(reopened.committed(), 10).unwrap();
+        assert_eq!(read, lines(&["{\"n\":1}", "{\"n\":2}"]));
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn reads_continue_across_segments() {
+        let dir = temp_dir();
+        let spool = open(&dir, 0);
+        let line = "y".repeat(600);
+        for _ in 0..5 {
+            spool.append(std::slice::from_ref(&line)).unwrap();
+        }
+        assert!(list_segments(&dir).unwrap().len() > 1);
+
+        let (read, end) = spool.read(spool.committed(), 10).unwrap();
+        assert_eq!(read.len(), 5);
+        spool.commit(end, 5);
+        // 다 읽은 세그먼트는 지워짐
+        assert_eq!(list_segments(&dir).unwrap(), vec![end.segment]);
+        assert_eq!(open(&dir, 0).pending_events(), 0);
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+use tracing::{error, info, warn};
+
+use crate::models::health::{EngineHealth, EngineState, Incident};
+use crate::services::controller::SuricataController;
+use crate::services::counters::poll_counters;
+use crate::services::forwarder::Forwarder;
+use crate::utils::config::env_or;
+use crate::utils::time::now_secs;
+
+// Suricata 프로세스 감시 및 상태 관리
+pub struct Supervisor {
+    controller: Arc<dyn SuricataController>,
+    health: RwLock<EngineHealth>,
+    started_at: u64,
+    interval_secs: u64,
+    // 연속 실패/정체 횟수 임계값 (정체 감지는 0이면 비활성화)
+    failure_threshold: u32,
+    stall_threshold: u32,
+    auto_restart: bool,
+    restart_cooldown_secs: u64,
+    // 사건 보고 경로 (중앙 API 스풀)
+    incidents: Option<Arc<Forwarder>>,
+    // 계획된 재시작 중에는 점검 중단
+    maintenance: AtomicBool,
+}
+
+impl Supervisor {
+    // forwarder: 사건을 중앙 서버로 보낼 전달기 (SUPERVISOR_REPORT_INCIDENTS가 켜져 있을 때만 사용)
+    pub fn from_env(controller: Arc<dyn SuricataController>, forwarder: Option<Arc<Forwarder>>) -> Self {
+        Supervisor {
+            controller,
+            health: RwLock::new(EngineHealth {
+                state: EngineState::Unknown,
+                message: None,
+                last_check: None,
+                engine_uptime: None,
+                kernel_packets: None,
+                last_packet_change: None,
+                consecutive_failures: 0,
+                stalled_polls: 0,
+                restarts: 0,
+                last_restart: None,
+                last_incident: None,
+            }),
+            started_at: now_secs(),
+            interval_secs: env_or("SUPERVISOR_INTERVAL_SECS", 15u64).max(1),
+            failure_threshold: env_or("SUPERVISOR_FAILURE_THRESHOLD", 3u32).max(1),
+            stall_threshold: env_or("SUPERVISOR_STALL_THRESHOLD", 0u32),
+            auto_restart: env_or("SUPERVISOR_AUTO_RESTART", false),
+            restart_cooldown_secs: env_or("SUPERVISOR_RESTART_COOLDOWN_SECS", 300u64),
+            incidents: forwarder.filter(|_| env_or("SUPERVISOR_REPORT_INCIDENTS", false)),
+            maintenance: AtomicBool::new(false),
+        }
+    }
+
+    pub fn uptime(&self) -> u64 {
+        now_secs().saturating_sub(self.started_at)
+    }
+
+    pub async fn health(&self) -> EngineHealth {
+        self.health.read().await.clone()
+    }
+
+    pub fn set_maintenance(&self, maintenance: bool) {
+        self.maintenance.store(maintenance, Ordering::Relaxed);
+    }
+
+    // 한 번의 점검 수행 후 상태 전이 처리
+    // 사건은 상태가 바뀔 때만 기록하고, 자동 재시작은 비정상인 동안 쿨다운마다 다시 시도
+    async fn check(&self) {
+        if self.maintenance.load(Ordering::Relaxed) {
+            return;
+        }
+        let uptime = self.controller.suricatasc("uptime").await.and_then(|output| parse_uptime(&output));
+        let packets = match &uptime {
+            Ok(_) => poll_counters(self.controller.as_ref()).await
+                .map(|counters| counters.pointer("/capture/kernel_packets").and_then(Value::as_u64)),
+            Err(e) => Err(e.clone()),
+        };
+        let now = now_secs();
+
+        let (previous, current) = {
+            let mut health = self.health.write().await;
+            let previous = health.state;
+            health.last_check = Some(now);
+
+            match (uptime, packets) {
+                (Ok(uptime), Ok(packets)) => {
+                    health.consecutive_failures = 0;
+                    health.engine_uptime = Some(uptime);
+
+                    let moved = match (health.kernel_packets, packets) {
+                        // 아직 패킷을 받은 적 없는 유휴 링크는 정체로 보지 않음
+                        (Some(before), Some(after)) => after != before || after == 0,
+                        // 최초 점검이거나 캡처 카운터가 없는 모드
+                        _ => true,
+                    };
+                    if moved {
+                        health.stalled_polls = 0;
+                        health.last_packet_change = Some(now);
+                    } else {
+                        health.stalled_polls += 1;
+                    }
+                    health.kernel_packets = packets;
+
+                    if self.stall_threshold > 0 && health.stalled_polls >= self.stall_threshold {
+                        health.state = EngineState::Stalled;
+                        health.message = Some(format!(
+                            "capture.kernel_packets has not changed for {} checks",
+                            health.stalled_polls
+                        ));
+                    } else {
+                        health.state = EngineState::Healthy;
+                        health.message = None;
+                    }
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    health.consecutive_failures += 1;
+                    if health.consecutive_failures >= self.failure_threshold {
+                        health.state = EngineState::Unreachable;
+                    }
+                    health.message = Some(e);
+                }
+            }
+            (previous, health.state)
+        };
+
+        if previous != current {
+            match current {
+                EngineState::Healthy => {
+                    if previous != EngineState::Unknown {
+                        self.incident("recovered", "Suricata is healthy again".to_string()).await;
+                    }
+                }
+                EngineState::Stalled | EngineState::Unreachable => {
+                    let message = self.health.read().await.message.clone().unwrap_or_default();
+                    let kind = if current == EngineState::Stalled { "stalled" } else { "unreachable" };
+                    self.incident(kind, message).await;
+                }
+                EngineState::Unknown => {}
+            }
+        }
+
+        if self.auto_restart && matches!(current, EngineState::Stalled | EngineState::Unreachable) {
+            self.restart().await;
+        }
+    }
+
+    // 쿨다운을 지켜 컨테이너 재시작 (쿨다운 중이면 다음 점검에서 다시 시도)
+    async fn restart(&self) {
+        let now = now_secs();
+        if let Some(last) = self.health.read().await.last_restart
+            && now.saturating_sub(last) < self.restart_cooldown_secs {
+            return;
+        }
+
+        {
+            let mut health = self.health.write().await;
+            health.restarts += 1;
+            health.last_restart = Some(now);
+        }
+
+        match self.controller.restart().await {
+            Ok(()) => {
+                info!("Suricata restarted by supervisor");
+                self.incident("restarted", "Suricata restarted by supervisor".to_string()).await;
+            }
+            Err(e) => {
+                self.incident("restart_failed", e).await;
+            }
+        }
+    }
+
+    // 사건 기록 및 중앙 서버 보고
+    async fn incident(&self, kind: &str, message: String) {
+        let incident = Incident {
+            kind: kind.to_string(),
+            message,
+            timestamp: now_secs(),
+        };
+        warn!("Suricata incident [{}]: {}", incident.kind, incident.message);
+        self.health.write().await.last_incident = Some(incident.clone());
+
+        // 중앙 서버에 EVE 형식의 agent_incident 이벤트로 보고 (연결이 끊겨도 스풀에서 재전송)
+        if let Some(forwarder) = &self.incidents {
+            let event = json!({
+                "event_type": "agent_incident",
+                "incident": incident,
+            });
+            if let Err(e) = forwarder.submit(event).await {
+                error!("Failed to report incident to central server: {}", e.message());
+            }
+        }
+    }
+}
+
+// 주기 점검 태스크
+pub fn spawn_supervisor(supervisor: Arc<Supervisor>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(supervisor.interval_secs));
+        loop {
+            ticker.tick().await;
+            supervisor.check().await;
+        }
+    });
+}
+
+// uptime 명령 응답 파싱 ({"message": 123, "return": "OK"})
+fn parse_uptime(output: &str) -> Result<u64, String> {
+    let response: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Invalid uptime output: {}", e))?;
+
+    if response.get("return").and_then(Value::as_str) != Some("OK") {
+        return Err(format!("uptime returned error: {}", response));
+    }
+
+    response.get("message")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "uptime output has no message".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, AtomicU64};
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::services::controller::CommandOutput;
+
+    // 응답 여부와 패킷 카운터를 테스트에서 바꿀 수 있는 백엔드
+    #[derive(Default)]
+    struct ScriptedController {
+        down: AtomicBool,
+        packets: AtomicU64,
+        restarts: AtomicU32,
+        fail_restart: AtomicBool,
+    }
+
+    #[async_trait]
+    impl SuricataController for ScriptedController {
+        async fn suricatasc(&self, command: &str) -> Result<String, String> {
+            if self.down.load(Ordering::Relaxed) {
+                return Err("Unix socket is not available".to_string());
+            }
+            let message = match command {
+                "uptime" => json!(10),
+                _ => json!({ "capture": { "kernel_packets": self.packets.load(Ordering::Relaxed) } }),
+            };
+            Ok(json!({ "return": "OK", "message": message }).to_string())
+        }
+
+        async fn test_config(&self, _path: &str) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn restart(&self) -> Result<(), String> {
+            self.restarts.fetch_add(1, Ordering::Relaxed);
+            if self.fail_restart.load(Ordering::Relaxed) {
+                return Err("container did not start".to_string());
+            }
+            Ok(())
+        }
+
+        async fn exec(&self, _args: &[&str]) -> Result<CommandOutput, String> {
+            Err("not supported".to_string())
+        }
+    }
+
+    fn supervisor(controller: Arc<ScriptedController>, auto_restart: bool, stall_threshold: u32) -> Supervisor {
+        let mut supervisor = Supervisor::from_env(controller, None);
+        supervisor.failure_threshold = 2;
+        supervisor.stall_threshold = stall_threshold;
+        supervisor.auto_restart = auto_restart;
+        supervisor.restart_cooldown_secs = 300;
+        supervisor
+    }
+
+    async fn state(supervisor: &Supervisor) -> (EngineState, Option<String>) {
+        let health = supervisor.health().await;
+        (health.state, health.last_incident.map(|incident| incident.kind))
+    }
+
+    #[tokio::test]
+    async fn failures_and_recovery_are_reported_on_transitions() {
+        let controller = Arc::new(ScriptedController::default());
+        let supervisor = supervisor(controller.clone(), false, 0);
+
+        supervisor.check().await;
+        assert_eq!(state(&supervisor).await, (EngineState::Healthy, None));
+
+        controller.down.store(true, Ordering::Relaxed);
+        supervisor.check().await;
+        // 임계값 전에는 상태 유지
+        assert_eq!(state(&supervisor).await, (EngineState::Healthy, None));
+        supervisor.check().await;
+        assert_eq!(state(&supervisor).await, (EngineState::Unreachable, Some("unreachable".to_string())));
+
+        controller.down.store(false, Ordering::Relaxed);
+        supervisor.check().await;
+        assert_eq!(state(&supervisor).await, (EngineState::Healthy, Some("recovered".to_string())));
+        assert_eq!(controller.restarts.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn restart_is_retried_after_cooldown_while_engine_stays_down() {
+        let controller = Arc::new(ScriptedController::default());
+        controller.down.store(true, Ordering::Relaxed);
+        controller.fail_restart.store(true, Ordering::Relaxed);
+        let supervisor = supervisor(controller.clone(), true, 0);
+
+        supervisor.check().await;
+        supervisor.check().await;
+        assert_eq!(controller.restarts.load(Ordering::Relaxed), 1);
+        assert_eq!(state(&supervisor).await.1.as_deref(), Some("restart_failed"));
+
+        // 쿨다운 중에는 다시 시도하지 않음
+        supervisor.check().await;
+        assert_eq!(controller.restarts.load(Ordering::Relaxed), 1);
+
+        // 상태가 그대로여도 쿨다운이 지나면 다시 시도
+        supervisor.health.write().await.last_restart = Some(now_secs() - 301);
+        controller.fail_restart.store(false, Ordering::Relaxed);
+        supervisor.check().await;
+        assert_eq!(controller.restarts.load(Ordering::Relaxed), 2);
+        assert_eq!(supervisor.health().await.restarts, 2);
+        assert_eq!(state(&supervisor).await.1.as_deref(), Some("restarted"));
+    }
+
+    #[tokio::test]
+    async fn stall_detection_is_opt_in_and_ignores_idle_links() {
+        let controller = Arc::new(ScriptedController::default());
+        let disabled = supervisor(controller.clone(), false, 0);
+        let enabled = supervisor(controller.clone(), false, 2);
+
+        // 패킷이 한 번도 들어오지 않은 링크
+        for _ in 0..4 {
+            disabled.check().await;
+            enabled.check().await;
+        }
+        assert_eq!(state(&enabled).await.0, EngineState::Healthy);
+
+        controller.packets.store(500, Ordering::Relaxed);
+        for _ in 0..4 {
+            disabled.check().await;
+            enabled.check().await;
+        }
+        assert_eq!(state(&disabled).await.0, EngineState::Healthy);
+        assert_eq!(state(&enabled).await, (EngineState::Stalled, Some("stalled".to_string())));
+
+        controller.packets.store(600, Ordering::Relaxed);
+        enabled.check().await;
+        assert_eq!(state(&enabled).await, (EngineState::Healthy, Some("recovered".to_string())));
+    }
+
+    #[tokio::test]
+    async fn maintenance_pauses_checks() {
+        let controller = Arc::new(ScriptedController::default());
+        controller.down.store(true, Ordering::Relaxed);
+        let supervisor = supervisor(controller.clone(), true, 0);
+        supervisor.set_maintenance(true);
+        for _ in 0..3 {
+            supervisor.check().await;
+        }
+        assert_eq!(state(&supervisor).await, (EngineState::Unknown, None));
+        assert_eq!(controller.restarts.load(Ordering::Relaxed), 0);
+    }
+}
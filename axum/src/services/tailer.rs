@@ -0,0 +1,484 @@
+use std::{
+    fs::{self, File},
+    io::{BufRead, BufReader, ErrorKind, Seek, SeekFrom},
+    os::unix::fs::MetadataExt,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{info, warn};
+
+use crate::models::tailer::{TailPosition, TailStatus};
+use crate::services::ingest::LineIngest;
+use crate::utils::config::env_or;
+
+// 따라 읽는 중인 파일
+struct Follower {
+    file: File,
+    position: TailPosition,
+    // 경로가 다른 파일로 바뀐 것을 처음 본 시각 (남은 줄을 마저 읽은 뒤 전환)
+    rotated_at: Option<Instant>,
+}
+
+// 한 번 읽은 결과
+enum Step {
+    // 완성된 줄과 소비한 바이트 수
+    Read(Vec<Vec<u8>>, u64),
+    // 회전/잘림으로 읽을 위치가 바뀜
+    Moved,
+    Idle,
+}
+
+#[derive(Default)]
+struct TailProgress {
+    position: Option<TailPosition>,
+    lines_read: u64,
+    invalid_lines: u64,
+    rotations: u64,
+    truncations: u64,
+    last_error: Option<String>,
+}
+
+// fluent-bit 없이 Suricata eve.json을 직접 따라 읽는 tailer
+// 읽은 줄은 HTTP 수신 경로와 같은 전달기로 넘기고, 스풀에 기록된 뒤에만 읽기 위치를 저장
+// 로그 회전(새 inode)과 잘림(파일 크기 감소)을 감지해 새 파일 처음부터 다시 읽음
+pub struct EveTailer {
+    ingest: Arc<LineIngest>,
+    enabled: bool,
+    path: PathBuf,
+    state_file: PathBuf,
+    read_from_head: bool,
+    poll_interval: Duration,
+    batch_lines: usize,
+    rotate_wait: Duration,
+    progress: Mutex<TailProgress>,
+}
+
+impl EveTailer {
+    pub fn from_env(ingest: Arc<LineIngest>) -> Self {
+        EveTailer {
+            ingest,
+            enabled: env_or("EVE_TAIL_ENABLED", false),
+            path: PathBuf::from(env_or("SURICATA_LOG_FILE", "/var/log/suricata/eve.json".to_string())),
+            state_file: PathBuf::from(env_or("EVE_TAIL_STATE_FILE", "/logs/eve_tail.json".to_string())),
+            read_from_head: env_or("EVE_TAIL_READ_FROM_HEAD", false),
+            poll_interval: Duration::from_millis(env_or("EVE_TAIL_POLL_MS", 250u64).max(10)),
+            batch_lines: env_or("EVE_TAIL_BATCH_LINES", 500usize).max(1),
+            rotate_wait: Duration::from_secs(env_or("EVE_TAIL_ROTATE_WAIT_SECS", 5u64)),
+            progress: Mutex::new(TailProgress::default()),
+        }
+    }
+
+    pub fn status(&self) -> TailStatus {
+        let progress = self.lock();
+        let metadata = fs::metadata(&self.path).ok();
+        let lag_bytes = match (&metadata, progress.position) {
+            (Some(metadata), Some(position)) if metadata.ino() == position.inode => {
+                Some(metadata.len().saturating_sub(position.offset))
+            }
+            _ => None,
+        };
+        TailStatus {
+            enabled: self.enabled,
+            path: self.path.display().to_string(),
+            position: progress.position,
+            file_size: metadata.map(|metadata| metadata.len()),
+            lag_bytes,
+            lines_read: progress.lines_read,
+            invalid_lines: progress.invalid_lines,
+            rotations: progress.rotations,
+            truncations: progress.truncations,
+            last_error: progress.last_error.clone(),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TailProgress> {
+        self.progress.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    // 저장된 위치에서 이어 읽기 (다른 파일이면 처음부터, 저장된 위치가 없으면 설정에 따라 처음 또는 끝부터)
+    fn open(&self) -> Result<Option<Follower>, String> {
+        let file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("Failed to open {}: {}", self.path.display(), e)),
+        };
+        let metadata = file.metadata()
+            .map_err(|e| format!("Failed to stat {}: {}", self.path.display(), e))?;
+        let inode = metadata.ino();
+        let offset = match self.load_position() {
+            Some(saved) if saved.inode == inode && saved.offset <= metadata.len() => saved.offset,
+            Some(saved) if saved.inode == inode => {
+                warn!("{} shrank while the agent was stopped, reading from the start", self.path.display());
+                0
+            }
+            Some(_) => {
+                warn!("{} was rotated while the agent was stopped, reading the new file from the start", self.path.display());
+                0
+            }
+            None if self.read_from_head => 0,
+            None => metadata.len(),
+        };
+        info!("Tailing {} from offset {}", self.path.display(), offset);
+
+        let position = TailPosition { inode, offset };
+        self.save_position(position)?;
+        Ok(Some(Follower { file, position, rotated_at: None }))
+    }
+
+    fn load_position(&self) -> Option<TailPosition> {
+        let text = fs::read_to_string(&self.state_file).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    fn save_position(&self, position: TailPosition) -> Result<(), String> {
+        self.lock().position = Some(position);
+        if let Some(parent) = self.state_file.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let text = serde_json::to_string(&position).map_err(|e| e.to_string())?;
+        let tmp = self.state_file.with_extension("tmp");
+        fs::write(&tmp, text)
+            .and_then(|_| fs::rename(&tmp, &self.state_file))
+            .map_err(|e| format!("Failed to save tail position {}: {}", self.state_file.display(), e))
+    }
+
+    // 한 번 읽고 전달 (더 읽을 것이 남아 있을 수 있으면 true)
+    // 파일 읽기와 위치 저장은 비동기 작업 스레드를 막지 않도록 블로킹 스레드에서
+    async fn poll(self: &Arc<Self>, follower: &mut Option<Follower>) -> Result<bool, String> {
+        let mut current = follower.take();
+        let (current, step) = self.blocking(move |tailer| {
+            let step = tailer.step(&mut current);
+            (current, step)
+        }).await;
+        *follower = current;
+
+        let (lines, consumed) = match step? {
+            Step::Read(lines, consumed) => (lines, consumed),
+            Step::Moved => return Ok(true),
+            Step::Idle => return Ok(false),
+        };
+        if !lines.is_empty() {
+            self.forward(lines).await?;
+        }
+        let Some(current) = follower.as_mut() else {
+            return Ok(true);
+        };
+        current.position.offset += consumed;
+        let position = current.position;
+        self.blocking(move |tailer| tailer.save_position(position)).await?;
+        Ok(true)
+    }
+
+    async fn blocking<T, F>(self: &Arc<Self>, work: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(&EveTailer) -> T + Send + 'static,
+    {
+        let tailer = Arc::clone(self);
+        tokio::task::spawn_blocking(move || work(&tailer)).await
+            .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()))
+    }
+
+    // 파일을 열고 다음 줄들을 읽음, 끝까지 읽었으면 회전/잘림 확인
+    // 읽은 줄의 위치는 전달이 끝난 뒤에 옮김
+    fn step(&self, follower: &mut Option<Follower>) -> Result<Step, String> {
+        if follower.is_none() {
+            *follower = self.open()?;
+        }
+        let Some(current) = follower.as_mut() else {
+            return Ok(Step::Idle);
+        };
+
+        let (lines, consumed) = read_lines(&mut current.file, current.position.offset, self.batch_lines)
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        if consumed > 0 {
+            return Ok(Step::Read(lines, consumed));
+        }
+
+        let metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            // 회전 직후 새 파일이 아직 없음
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Step::Idle),
+            Err(e) => return Err(format!("Failed to stat {}: {}", self.path.display(), e)),
+        };
+        if metadata.ino() != current.position.inode {
+            let rotated_at = *current.rotated_at.get_or_insert_with(Instant::now);
+            if rotated_at.elapsed() < self.rotate_wait {
+                return Ok(Step::Idle);
+            }
+            info!("{} was rotated, following the new file", self.path.display());
+            let file = File::open(&self.path)
+                .map_err(|e| format!("Failed to open {}: {}", self.path.display(), e))?;
+            let inode = file.metadata()
+                .map_err(|e| format!("Failed to stat {}: {}", self.path.display(), e))?
+                .ino();
+            *current = Follower { file, position: TailPosition { inode, offset: 0 }, rotated_at: None };
+            self.lock().rotations += 1;
+            self.save_position(current.position)?;
+            return Ok(Step::Moved);
+        }
+        if metadata.len() < current.position.offset {
+            warn!("{} was truncated, reading from the start", self.path.display());
+            current.position.offset = 0;
+            self.lock().truncations += 1;
+            self.save_position(current.position)?;
+            return Ok(Step::Moved);
+        }
+        Ok(Step::Idle)
+    }
+
+    // HTTP 수신과 같은 경로로 전달 (스풀 기록에 실패하면 위치를 옮기지 않고 다음에 다시 읽음)
+    async fn forward(&self, lines: Vec<Vec<u8>>) -> Result<(), String> {
+        let count = lines.len() as u64;
+        let invalid = self.ingest.ingest("tail", lines).await?;
+
+        let mut progress = self.lock();
+        progress.lines_read += count;
+        progress.invalid_lines += invalid;
+        progress.last_error = None;
+        Ok(())
+    }
+}
+
+// offset부터 완성된 줄(개행으로 끝나는 줄)을 최대 max개 읽음, 빈 줄은 건너뜀
+// 반환: (줄 목록, 소비한 바이트 수)
+fn read_lines(file: &mut File, offset: u64, max: usize) -> std::io::Result<(Vec<Vec<u8>>, u64)> {
+    file.seek(SeekFrom::Start(offset))?;
+    let mut reader = BufReader::new(file);
+    let mut lines = Vec::new();
+    let mut consumed = 0;
+    while lines.len() < max {
+        let mut line = Vec::new();
+        let read = reader.read_until(b'\n', &mut line)?;
+        // 끝에 도달했거나 아직 쓰는 중인 줄
+        if read == 0 || line.last() != Some(&b'\n') {
+            break;
+        }
+        consumed += read as u64;
+        if line.iter().any(|byte| !byte.is_ascii_whitespace()) {
+            lines.push(line);
+        }
+    }
+    Ok((lines, consumed))
+}
+
+// 설정된 경우 eve.json을 따라 읽어 전달
+pub fn spawn_eve_tailer(tailer: Arc<EveTailer>) {
+    if !tailer.enabled {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut follower: Option<Follower> = None;
+        loop {
+            match tailer.poll(&mut follower).await {
+                Ok(true) => continue,
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("EVE tailer: {}", e);
+                    tailer.lock().last_error = Some(e);
+                }
+            }
+            tokio::time::sleep(tailer.poll_interval).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::path::Path;
+
+    use super::*;
+    use crate::services::counters::CounterStore;
+    use crate::services::forwarder::test_support::{forwarder, pending};
+    use crate::services::forwarder::Forwarder;
+    use crate::services::metrics::Metrics;
+    use crate::utils::random::random_u64;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir()
+            .join(format!("tailer-test-{}-{}", std::process::id(), random_u64()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // 같은 상태 파일을 쓰는 tailer (재시작은 새 tailer로)
+    fn tailer(dir: &Path, forwarder: &Arc<Forwarder>) -> Arc<EveTailer> {
+        let metrics = Arc::new(Metrics::new());
+        let ingest = LineIngest::new(forwarder.clone(), Arc::new(CounterStore::from_env()), metrics);
+        Arc::new(EveTailer {
+            ingest: Arc::new(ingest),
+            enabled: true,
+            path: dir.join("eve.json"),
+            state_file: dir.join("state").join("eve_tail.json"),
+            read_from_head: true,
+            poll_interval: Duration::from_millis(10),
+            batch_lines: 2,
+            rotate_wait: Duration::ZERO,
+            progress: Mutex::new(TailProgress::default()),
+        })
+    }
+
+    fn append(path: &Path, text: &str) {
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    fn events(range: std::ops::Range<u32>) -> String {
+        range.map(|seq| format!("{{\"event_type\":\"smb\",\"seq\":{}}}\n", seq)).collect()
+    }
+
+    // 더 읽을 것이 없을 때까지
+    async fn drain(tailer: &Arc<EveTailer>, follower: &mut Option<Follower>) {
+        while tailer.poll(follower).await.unwrap() {}
+    }
+
+    fn saved(tailer: &EveTailer) -> TailPosition {
+        tailer.load_position().unwrap()
+    }
+
+    #[tokio::test]
+    async fn offset_is_saved_and_resumed_after_restart() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        let complete = events(0..3);
+        append(&path, &complete);
+        // 아직 쓰는 중인 줄은 읽지 않음
+        append(&path, "{\"event_type\":\"smb\",");
+
+        let first = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&first, &mut follower).await;
+        assert_eq!(pending(&forwarder), 3);
+        assert_eq!(saved(&first).offset, complete.len() as u64);
+        assert_eq!(first.status().lines_read, 3);
+
+        // 재시작 후 저장된 위치부터 이어 읽음
+        append(&path, "\"seq\":3}\n");
+        append(&path, &events(4..5));
+        let second = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&second, &mut follower).await;
+        assert_eq!(pending(&forwarder), 5);
+        assert_eq!(second.status().lines_read, 2);
+        assert_eq!(saved(&second).offset, fs::metadata(&path).unwrap().len());
+        assert_eq!(second.status().lag_bytes, Some(0));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn without_a_saved_offset_reading_starts_at_the_configured_end() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        append(&path, &events(0..3));
+
+        let mut tail = tailer(&dir, &forwarder);
+        Arc::get_mut(&mut tail).unwrap().read_from_head = false;
+        let mut follower = None;
+        drain(&tail, &mut follower).await;
+        assert_eq!(pending(&forwarder), 0);
+
+        append(&path, &events(3..4));
+        drain(&tail, &mut follower).await;
+        assert_eq!(pending(&forwarder), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rotated_files_are_finished_then_followed_from_the_start() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        append(&path, &events(0..2));
+
+        let tail = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&tail, &mut follower).await;
+        let before = saved(&tail);
+
+        // 회전 직전에 기록된 줄은 이전 파일에서 마저 읽음
+        let rotated = dir.join("eve.json.1");
+        fs::rename(&path, &rotated).unwrap();
+        append(&rotated, &events(2..3));
+        append(&path, &events(3..6));
+        drain(&tail, &mut follower).await;
+
+        assert_eq!(pending(&forwarder), 6);
+        let status = tail.status();
+        assert_eq!((status.rotations, status.truncations), (1, 0));
+        let after = saved(&tail);
+        assert_ne!(after.inode, before.inode);
+        assert_eq!(after.offset, fs::metadata(&path).unwrap().len());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn rotation_waits_for_the_configured_time() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        append(&path, &events(0..1));
+
+        let mut tail = tailer(&dir, &forwarder);
+        Arc::get_mut(&mut tail).unwrap().rotate_wait = Duration::from_secs(3600);
+        let mut follower = None;
+        drain(&tail, &mut follower).await;
+
+        fs::rename(&path, dir.join("eve.json.1")).unwrap();
+        append(&path, &events(1..2));
+        drain(&tail, &mut follower).await;
+        assert_eq!(pending(&forwarder), 1);
+        assert_eq!(tail.status().rotations, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn truncated_files_are_read_from_the_start() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        append(&path, &events(0..4));
+
+        let tail = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&tail, &mut follower).await;
+        let inode = saved(&tail).inode;
+
+        // 같은 파일(inode)이 더 짧아짐
+        fs::OpenOptions::new().write(true).truncate(true).open(&path).unwrap();
+        append(&path, &events(4..5));
+        drain(&tail, &mut follower).await;
+
+        assert_eq!(pending(&forwarder), 5);
+        let status = tail.status();
+        assert_eq!((status.rotations, status.truncations), (0, 1));
+        assert_eq!(saved(&tail).inode, inode);
+        assert_eq!(saved(&tail).offset, fs::metadata(&path).unwrap().len());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn a_file_that_shrank_while_stopped_is_read_from_the_start() {
+        let dir = temp_dir();
+        let (forwarder, _) = forwarder(&dir, Some("http://127.0.0.1:9/"), 10);
+        let path = dir.join("eve.json");
+        append(&path, &events(0..4));
+        let first = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&first, &mut follower).await;
+        drop(follower);
+
+        fs::OpenOptions::new().write(true).truncate(true).open(&path).unwrap();
+        append(&path, &events(4..5));
+        let second = tailer(&dir, &forwarder);
+        let mut follower = None;
+        drain(&second, &mut follower).await;
+        assert_eq!(pending(&forwarder), 5);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use crate::services::aggregation::AlertAggregator;
+use crate::services::alert_stats::AlertStats;
+use crate::services::alert_store::AlertStore;
+use crate::services::capture::CaptureManager;
+use crate::services::config::ConfigManager;
+use crate::services::controller::{controller_from_env, SuricataController};
+use crate::services::counters::CounterStore;
+use crate::services::engine::EngineController;
+use crate::services::enrichment::Enricher;
+use crate::services::event_stream::EventStream;
+use crate::services::forwarder::{Forwarder, LocalViews};
+use crate::services::ingest::LineIngest;
+use crate::services::listener::EveListener;
+use crate::services::metrics::Metrics;
+use crate::services::pcap_jobs::PcapJobManager;
+use crate::services::policy::PolicyEngine;
+use crate::services::scoring::AlertScorer;
+use crate::services::sink::SinkManager;
+use crate::services::supervisor::Supervisor;
+use crate::services::tailer::EveTailer;
+
+// 핸들러와 백그라운드 태스크가 공유하는 상태
+#[derive(Clone)]
+pub struct AppState {
+    pub alert_stats: Arc<AlertStats>,
+    pub alerts: Arc<AlertStore>,
+    pub captures: Arc<CaptureManager>,
+    pub config: Arc<ConfigManager>,
+    pub controller: Arc<dyn SuricataController>,
+    pub counters: Arc<CounterStore>,
+    pub engine: Arc<EngineController>,
+    pub event_stream: Arc<EventStream>,
+    pub forwarder: Arc<Forwarder>,
+    pub listener: Arc<EveListener>,
+    pub metrics: Arc<Metrics>,
+    pub pcap_jobs: Arc<PcapJobManager>,
+    pub policy: Arc<PolicyEngine>,
+    pub sinks: Arc<SinkManager>,
+    pub supervisor: Arc<Supervisor>,
+    pub tailer: Arc<EveTailer>,
+}
+
+impl AppState {
+    pub fn from_env() -> Result<Self, String> {
+        let controller = controller_from_env();
+        let config = Arc::new(ConfigManager::from_env(controller.clone()));
+        let metrics = Arc::new(Metrics::new());
+        let policy = Arc::new(PolicyEngine::from_env(metrics.clone()));
+        let counters = Arc::new(CounterStore::from_env());
+        let alerts = Arc::new(AlertStore::from_env());
+        let alert_stats = Arc::new(AlertStats::from_env());
+        let event_stream = Arc::new(EventStream::from_env(metrics.clone()));
+        let sinks = Arc::new(SinkManager::from_env(metrics.clone()));
+        let forwarder = Arc::new(Forwarder::from_env(
+            metrics.clone(),
+            policy.clone(),
+            Enricher::from_env(),
+            AlertAggregator::from_env(metrics.clone()),
+            AlertScorer::from_env(),
+            LocalViews {
+                store: alerts.clone(),
+                stats: alert_stats.clone(),
+                stream: event_stream.clone(),
+            },
+            sinks.clone(),
+        ).map_err(|e| e.message().to_string())?);
+        let supervisor = Arc::new(Supervisor::from_env(controller.clone(), Some(forwarder.clone())));
+        let ingest = Arc::new(LineIngest::new(forwarder.clone(), counters.clone(), metrics.clone()));
+        Ok(AppState {
+            alert_stats,
+            alerts,
+            captures: Arc::new(CaptureManager::from_env(controller.clone())),
+            config: config.clone(),
+            controller: controller.clone(),
+            counters,
+            engine: Arc::new(EngineController::from_env(controller.clone(), config, supervisor.clone())),
+            event_stream,
+            forwarder,
+            listener: Arc::new(EveListener::from_env(ingest.clone())),
+            metrics,
+            pcap_jobs: Arc::new(PcapJobManager::from_env(controller)),
+            policy,
+            sinks,
+            supervisor,
+            tailer: Arc::new(EveTailer::from_env(ingest)),
+        })
+    }
+}
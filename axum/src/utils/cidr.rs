@@ -0,0 +1,57 @@
+use std::net::IpAddr;
+
+// IPv4/IPv6 CIDR 대역 (접두사 없으면 단일 주소)
+#[derive(Debug, Clone, Copy)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    pub fn parse(text: &str) -> Result<Cidr, String> {
+        let text = text.trim();
+        let (address, prefix) = match text.split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (text, None),
+        };
+        let network: IpAddr = address.parse()
+            .map_err(|_| format!("Invalid CIDR address: {}", text))?;
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix.parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max)
+                .ok_or_else(|| format!("Invalid CIDR prefix: {}", text))?,
+            None => max,
+        };
+        Ok(Cidr { network, prefix })
+    }
+
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+
+    pub fn contains(&self, address: &IpAddr) -> bool {
+        match (self.network, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix as u32).unwrap_or(0);
+                u32::from(network) & mask == u32::from(*address) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix as u32).unwrap_or(0);
+                u128::from(network) & mask == u128::from(*address) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+// 대역 조건이 없으면 통과, 있으면 주소가 하나라도 포함되어야 통과
+pub fn in_ranges(ranges: &[Cidr], address: Option<&str>) -> bool {
+    if ranges.is_empty() {
+        return true;
+    }
+    address
+        .and_then(|address| address.parse::<IpAddr>().ok())
+        .is_some_and(|address| ranges.iter().any(|range| range.contains(&address)))
+}
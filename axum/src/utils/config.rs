@@ -0,0 +1,12 @@
+use std::{env, str::FromStr};
+
+use dotenvy::dotenv;
+
+// 환경변수를 원하는 타입으로 조회 (없거나 파싱 실패 시 기본값)
+pub fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    dotenv().ok();
+    env::var(key)
+        .ok()
+        .and_then(|value| value.trim().parse::<T>().ok())
+        .unwrap_or(default)
+}
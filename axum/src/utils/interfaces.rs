@@ -0,0 +1,38 @@
+use std::{fs, path::Path};
+
+use crate::models::interface::HostInterface;
+
+// 인터페이스 이름 검증 (명령어 주입 방지)
+pub fn is_valid_interface_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 15
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+// sysfs에서 호스트 인터페이스 목록 조회
+pub fn list_host_interfaces(sys_dir: &str) -> Result<Vec<HostInterface>, String> {
+    let net_dir = Path::new(sys_dir).join("class/net");
+    let entries = fs::read_dir(&net_dir)
+        .map_err(|e| format!("Failed to read {}: {}", net_dir.display(), e))?;
+
+    let read = |name: &str, file: &str| {
+        fs::read_to_string(net_dir.join(name).join(file))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut interfaces: Vec<HostInterface> = entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .map(|name| HostInterface {
+            state: read(&name, "operstate"),
+            mac: read(&name, "address"),
+            mtu: read(&name, "mtu").and_then(|mtu| mtu.parse().ok()),
+            monitored: false,
+            name,
+        })
+        .collect();
+    interfaces.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(interfaces)
+}
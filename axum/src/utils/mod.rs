@@ -1 +1,7 @@
-pub mod suricata;
\ No newline at end of file
+pub mod cidr;
+pub mod config;
+pub mod interfaces;
+pub mod random;
+pub mod suricata;
+pub mod time;
+pub mod yaml;
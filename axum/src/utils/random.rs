@@ -0,0 +1,14 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+// 암호학적 용도가 아닌 난수 (재시도 지터, 샘플링)
+pub fn random_u64() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+// 0.0 이상 1.0 미만
+pub fn random_fraction() -> f64 {
+    (random_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -2,6 +2,8 @@ use std::{env, hash::{DefaultHasher, Hash, Hasher}};
 
 use dotenvy::dotenv;
 
+use crate::utils::cidr::Cidr;
+
 // 환경변수 조회
 pub fn get_env() -> (String, String) {
     dotenv().ok();
@@ -82,8 +84,8 @@ pub fn validate_rule_syntax(rule: &str) -> Result<(), String> {
     // 8. sid 형식 검증 (숫자여야 함)
     for opt in option_parts {
         let opt = opt.trim();
-        if opt.starts_with("sid:") {
-            let sid_value = opt[4..].trim();
+        if let Some(sid_value) = opt.strip_prefix("sid:") {
+            let sid_value = sid_value.trim();
             if sid_value.parse::<u64>().is_err() {
                 return Err(format!("Invalid sid format: {}. Must be a number", sid_value));
             }
@@ -104,24 +106,245 @@ pub fn generate_rule_id(rule_content: &str) -> String {
 
 // 옵션 추출 헬퍼 함수
 pub fn extract_option(rule: &str, option_name: &str) -> Option<String> {
-    if let Some(options_part) = rule.split('(').nth(1) {
-        if let Some(options_end) = options_part.rfind(')') {
-            let options = &options_part[..options_end];
-            for option in options.split(';') {
-                let option = option.trim();
-                if option.starts_with(&format!("{}:", option_name)) {
-                    let value_start = option_name.len() + 1; // +1 for ":"
-                    if option.len() > value_start {
-                        let value = &option[value_start..];
-                        // 따옴표 처리
-                        if value.starts_with('"') && value.ends_with('"') && value.len() >= 2 {
-                            return Some(value[1..value.len()-1].to_string());
-                        }
-                        return Some(value.to_string());
+    if let Some(options_part) = rule.split('(').nth(1)
+        && let Some(options_end) = options_part.rfind(')') {
+        let options = &options_part[..options_end];
+        for option in options.split(';') {
+            let option = option.trim();
+            if option.starts_with(&format!("{}:", option_name)) {
+                let value_start = option_name.len() + 1; // +1 for ":"
+                if option.len() > value_start {
+                    let value = &option[value_start..];
+                    // 따옴표 처리
+                    if value.starts_with('"') && value.ends_with('"') && value.len() >= 2 {
+                        return Some(value[1..value.len()-1].to_string());
                     }
+                    return Some(value.to_string());
                 }
             }
         }
     }
     None
 }
+
+
+// 그룹 변수 값 최대 길이와 목록 중첩 깊이
+const MAX_VAR_VALUE_BYTES: usize = 8192;
+const MAX_VAR_NESTING: usize = 16;
+
+// 주소 그룹 값 검증 (CIDR/IP, any, $VAR, 부정(!), 중첩 목록)
+pub fn validate_address_group(value: &str, address_vars: &[String]) -> Result<(), String> {
+    validate_var_list(value, &|element| {
+        if element == "any" {
+            return Ok(());
+        }
+        if let Some(name) = element.strip_prefix('$') {
+            return check_var_reference(name, address_vars, "vars.address-groups");
+        }
+        Cidr::parse(element).map(|_| ())
+    })
+}
+
+// 포트 그룹 값 검증 (포트, 범위 a:b / a: / :b, any, $VAR, 부정(!), 중첩 목록)
+pub fn validate_port_group(value: &str, port_vars: &[String]) -> Result<(), String> {
+    validate_var_list(value, &|element| {
+        if element == "any" {
+            return Ok(());
+        }
+        if let Some(name) = element.strip_prefix('$') {
+            return check_var_reference(name, port_vars, "vars.port-groups");
+        }
+        validate_port_range(element)
+    })
+}
+
+fn validate_var_list(value: &str, validate: &dyn Fn(&str) -> Result<(), String>) -> Result<(), String> {
+    if value.len() > MAX_VAR_VALUE_BYTES {
+        return Err(format!("Value is longer than {} bytes", MAX_VAR_VALUE_BYTES));
+    }
+    validate_var_element(value, validate, 0)
+}
+
+fn validate_var_element(
+    value: &str,
+    validate: &dyn Fn(&str) -> Result<(), String>,
+    depth: usize,
+) -> Result<(), String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err("Value cannot be empty".to_string());
+    }
+
+    let value = value.strip_prefix('!').unwrap_or(value).trim();
+    if let Some(inner) = value.strip_prefix('[') {
+        if depth >= MAX_VAR_NESTING {
+            return Err(format!("Lists cannot be nested more than {} levels deep", MAX_VAR_NESTING));
+        }
+        let inner = inner.strip_suffix(']')
+            .ok_or_else(|| format!("Unbalanced brackets in: {}", value))?;
+        let elements = split_top_level(inner)?;
+        if elements.iter().all(|element| element.trim().is_empty()) {
+            return Err("List cannot be empty".to_string());
+        }
+        for element in elements {
+            validate_var_element(element, validate, depth + 1)?;
+        }
+        return Ok(());
+    }
+
+    if value.contains([',', '[', ']']) {
+        return Err(format!("Lists must be enclosed in brackets: {}", value));
+    }
+    validate(value)
+}
+
+// 최상위 쉼표 기준 분리 ("a,[b,c],d" → ["a", "[b,c]", "d"])
+fn split_top_level(list: &str) -> Result<Vec<&str>, String> {
+    let mut elements = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth < 0 {
+                    return Err(format!("Unbalanced brackets in: {}", list));
+                }
+            }
+            ',' if depth == 0 => {
+                elements.push(&list[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        return Err(format!("Unbalanced brackets in: {}", list));
+    }
+    elements.push(&list[start..]);
+    Ok(elements)
+}
+
+fn check_var_reference(name: &str, defined: &[String], section: &str) -> Result<(), String> {
+    if defined.iter().any(|var| var == name) {
+        Ok(())
+    } else {
+        Err(format!("Undefined variable ${}. Define it under {} first", name, section))
+    }
+}
+
+fn validate_port_range(value: &str) -> Result<(), String> {
+    let parse = |port: &str| -> Result<Option<u16>, String> {
+        if port.is_empty() {
+            return Ok(None);
+        }
+        port.parse::<u16>()
+            .map(Some)
+            .map_err(|_| format!("Invalid port: {}", value))
+    };
+
+    match value.split_once(':') {
+        Some((low, high)) => match (parse(low)?, parse(high)?) {
+            (None, None) => Err(format!("Invalid port range: {}", value)),
+            (Some(low), Some(high)) if low > high => Err(format!("Invalid port range: {}", value)),
+            _ => Ok(()),
+        },
+        None => parse(value).map(|_| ()),
+    }
+}
+
+// 규칙 헤더의 $변수 참조가 suricata.yaml에 정의되어 있는지 검증
+pub fn validate_rule_variables(
+    rule: &str,
+    address_vars: &[String],
+    port_vars: &[String],
+) -> Result<(), String> {
+    let rule = rule.trim();
+    if rule.starts_with('#') {
+        return Ok(());
+    }
+    let header = rule.split('(').next().unwrap_or_default();
+    let parts: Vec<&str> = header.split_whitespace().collect();
+    if parts.len() < 7 {
+        return Ok(());
+    }
+
+    let fields = [
+        (parts[2], "source address", address_vars, "vars.address-groups"),
+        (parts[3], "source port", port_vars, "vars.port-groups"),
+        (parts[5], "destination address", address_vars, "vars.address-groups"),
+        (parts[6], "destination port", port_vars, "vars.port-groups"),
+    ];
+    for (field, position, defined, section) in fields {
+        for name in var_references(field) {
+            if !defined.iter().any(|var| var == name) {
+                return Err(format!(
+                    "Undefined variable ${} in {}. Define it under {} first",
+                    name, position, section
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 값 안에 $name 참조가 있는지 확인
+pub fn references_var(value: &str, name: &str) -> bool {
+    var_references(value).contains(&name)
+}
+
+fn var_references(field: &str) -> Vec<&str> {
+    field.split('$')
+        .skip(1)
+        .map(|rest| {
+            let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+            &rest[..end]
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn nested(depth: usize, inner: &str) -> String {
+        format!("{}{}{}", "[".repeat(depth), inner, "]".repeat(depth))
+    }
+
+    #[test]
+    fn address_groups() {
+        let defined = vars(&["HOME_NET"]);
+        for value in ["any", "10.0.0.0/8", "!$HOME_NET", "[192.168.0.0/16, ![10.0.0.1, fe80::/10]]", "2001:db8::1"] {
+            assert!(validate_address_group(value, &defined).is_ok(), "{}", value);
+        }
+        for value in ["", "[]", "[ ]", "10.0.0.0/33", "bogus", "$EXTERNAL_NET", "10.0.0.1,10.0.0.2", "[10.0.0.1", "[10.0.0.1]]", "[10.0.0.1,,10.0.0.2]"] {
+            assert!(validate_address_group(value, &defined).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn port_groups() {
+        let defined = vars(&["HTTP_PORTS"]);
+        for value in ["80", "[80, 443, 8000:8100, 1024:, :1023]", "![$HTTP_PORTS, 22]"] {
+            assert!(validate_port_group(value, &defined).is_ok(), "{}", value);
+        }
+        for value in ["65536", "100:10", ":", "http", "$SSH_PORTS"] {
+            assert!(validate_port_group(value, &defined).is_err(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn deeply_nested_values_are_rejected() {
+        assert!(validate_address_group(&nested(MAX_VAR_NESTING, "10.0.0.1"), &[]).is_ok());
+        assert!(validate_address_group(&nested(MAX_VAR_NESTING + 1, "10.0.0.1"), &[]).is_err());
+        // 스택을 넘길 만큼 깊은 값도 길이 제한에서 바로 거부
+        assert!(validate_address_group(&nested(500_000, "10.0.0.1"), &[]).is_err());
+        assert!(validate_port_group(&"[".repeat(40_000), &[]).is_err());
+    }
+}
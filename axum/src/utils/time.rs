@@ -0,0 +1,103 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// 현재 유닉스 시각 (초)
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+// Suricata/RFC 3339 시각을 유닉스 시각(밀리초)으로 변환
+// 예: 2025-01-01T12:00:00.123456+0900, 2025-01-01T03:00:00Z, 2025-01-01 (자정 UTC)
+pub fn parse_timestamp_ms(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = value.get(range)?;
+        digits.bytes().all(|byte| byte.is_ascii_digit()).then(|| digits.parse().ok())?
+    };
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    if value.get(4..5) != Some("-") || value.get(7..8) != Some("-") || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let days = days_from_civil(year, month, day);
+    if value.len() == 10 {
+        return Some(days * 86_400_000);
+    }
+    if !matches!(value.get(10..11), Some("T") | Some(" ")) || value.get(13..14) != Some(":") || value.get(16..17) != Some(":") {
+        return None;
+    }
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    // 소수 초 (밀리초까지만 사용)
+    let mut rest = &value[19..];
+    let mut millis = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        millis = format!("{:0<3}", &fraction[..digits.min(3)]).parse::<i64>().ok()?;
+        rest = &fraction[digits..];
+    }
+
+    // 시간대 (없으면 UTC)
+    let offset_secs = match rest {
+        "" | "Z" | "z" => 0,
+        _ => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let zone = rest[1..].replace(':', "");
+            if zone.len() != 4 || !zone.bytes().all(|byte| byte.is_ascii_digit()) {
+                return None;
+            }
+            sign * (zone[..2].parse::<i64>().ok()? * 3600 + zone[2..].parse::<i64>().ok()? * 60)
+        }
+    };
+
+    let secs = days * 86_400 + hour * 3600 + minute * 60 + second - offset_secs;
+    Some(secs * 1000 + millis)
+}
+
+// 1970-01-01부터의 일 수 (proleptic Gregorian)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+// 조회 조건의 시각 (유닉스 초 또는 RFC 3339)을 밀리초로 (밀리초로 나타낼 수 없으면 None)
+pub fn parse_query_time_ms(value: &str) -> Option<i64> {
+    match value.trim().parse::<i64>() {
+        Ok(secs) => secs.checked_mul(1000),
+        Err(_) => parse_timestamp_ms(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn query_times_accept_seconds_and_rfc3339() {
+        assert_eq!(parse_query_time_ms("1715681553"), Some(1_715_681_553_000));
+        assert_eq!(parse_query_time_ms("2024-05-14T10:12:33.512+0000"), Some(1_715_681_553_512));
+        assert_eq!(parse_query_time_ms("2024-05-14T19:12:33+09:00"), Some(1_715_681_553_000));
+        assert_eq!(parse_query_time_ms("yesterday"), None);
+    }
+
+    #[test]
+    fn query_times_that_overflow_are_rejected() {
+        assert_eq!(parse_query_time_ms(&i64::MAX.to_string()), None);
+        assert_eq!(parse_query_time_ms(&(i64::MIN / 999).to_string()), None);
+    }
+}
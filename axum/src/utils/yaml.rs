@@ -0,0 +1,662 @@
+// 주석과 %YAML 헤더를 보존하는 줄 단위 YAML 편집기
+//
+// suricata.yaml 처럼 블록 스타일로 작성된 문서만 대상으로 한다.
+// 경로 형식: "vars.address-groups.HOME_NET", "af-packet[interface=eth0].copy-mode"
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    // 매핑 키 (또는 "- key:" 형태의 시퀀스 항목)
+    Key(String),
+    // 첫 키/값으로 식별하는 시퀀스 항목 (예: [interface=eth0])
+    Item { key: String, value: String },
+}
+
+// 경로 문자열 파싱
+pub fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut chars = path.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                if !current.is_empty() {
+                    segments.push(Segment::Key(std::mem::take(&mut current)));
+                }
+            }
+            '[' => {
+                if !current.is_empty() {
+                    segments.push(Segment::Key(std::mem::take(&mut current)));
+                }
+                let mut selector = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => selector.push(c),
+                        None => return Err(format!("Unclosed '[' in path: {}", path)),
+                    }
+                }
+                let (key, value) = selector.split_once('=')
+                    .ok_or_else(|| format!("Item selector must be [key=value]: {}", path))?;
+                if key.trim().is_empty() || value.trim().is_empty() {
+                    return Err(format!("Item selector must be [key=value]: {}", path));
+                }
+                segments.push(Segment::Item {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                });
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        segments.push(Segment::Key(current));
+    }
+
+    if segments.is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+    Ok(segments)
+}
+
+// 문자열 값을 YAML 스칼라로 변환 (필요 시 큰따옴표)
+// YAML 1.1에서 불리언/널로 읽히는 값은 대소문자와 관계없이 따옴표 (숫자 모양 값은 그대로)
+pub fn quote_scalar(value: &str) -> String {
+    const RESERVED: [&str; 10] = ["y", "n", "yes", "no", "true", "false", "on", "off", "null", "~"];
+    let plain = !value.is_empty()
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | '/'))
+        && !RESERVED.iter().any(|reserved| value.eq_ignore_ascii_case(reserved));
+    if plain {
+        value.to_string()
+    } else {
+        serde_json::Value::String(value.to_string()).to_string()
+    }
+}
+
+struct LineInfo {
+    indent: usize,
+    key_col: usize,
+    dash: bool,
+    key: Option<String>,
+    value: Option<String>,
+}
+
+fn analyze(line: &str) -> Option<LineInfo> {
+    let trimmed = line.trim_start();
+    if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('%') || trimmed == "---" {
+        return None;
+    }
+
+    let indent = line.len() - trimmed.len();
+    let mut rest = trimmed;
+    let mut key_col = indent;
+    let mut dash = false;
+    while let Some(after) = rest.strip_prefix('-') {
+        if !(after.is_empty() || after.starts_with(' ')) {
+            break;
+        }
+        let stripped = after.trim_start();
+        key_col += rest.len() - stripped.len();
+        rest = stripped;
+        dash = true;
+    }
+
+    let (key, value) = match split_key(rest) {
+        Some((key, value)) => (Some(key), value),
+        None => (None, Some(strip_comment(rest).to_string())),
+    };
+    Some(LineInfo { indent, key_col, dash, key, value })
+}
+
+// "key: value # comment" → (key, Some(value))
+fn split_key(content: &str) -> Option<(String, Option<String>)> {
+    if content.starts_with(['"', '\'', '[', '{']) {
+        return None;
+    }
+    let bytes = content.as_bytes();
+    let pos = (0..bytes.len()).find(|&i| {
+        bytes[i] == b':' && (i + 1 == bytes.len() || bytes[i + 1] == b' ')
+    })?;
+    let key = content[..pos].trim().to_string();
+    let value = strip_comment(&content[pos + 1..]).trim().to_string();
+    Some((key, if value.is_empty() { None } else { Some(value) }))
+}
+
+// 따옴표 밖의 " #" 이후 주석 제거
+fn strip_comment(value: &str) -> &str {
+    comment_start(value).map(|pos| &value[..pos]).unwrap_or(value).trim()
+}
+
+fn comment_start(value: &str) -> Option<usize> {
+    let mut quote: Option<char> = None;
+    let mut previous = ' ';
+    for (i, c) in value.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' && previous.is_whitespace() => return Some(i),
+            None => {}
+        }
+        previous = c;
+    }
+    None
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    if value.len() >= 2
+        && ((value.starts_with('"') && value.ends_with('"'))
+            || (value.starts_with('\'') && value.ends_with('\'')))
+    {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+// 문서 내 노드 위치
+#[derive(Clone, Copy)]
+struct Node {
+    // 노드 첫 줄
+    line: usize,
+    // 자식 탐색 범위 [start, end)
+    start: usize,
+    end: usize,
+    // 자식 키가 위치할 열 (없으면 None)
+    child_col: Option<usize>,
+    // 자식이 없을 때 사용할 들여쓰기
+    default_child_col: usize,
+    // 시퀀스 항목 자체인지 여부
+    item: bool,
+}
+
+struct Document {
+    lines: Vec<String>,
+    info: Vec<Option<LineInfo>>,
+}
+
+impl Document {
+    fn parse(text: &str) -> Self {
+        let lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+        let info = lines.iter().map(|line| analyze(line)).collect();
+        Document { lines, info }
+    }
+
+    fn render(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    fn root(&self) -> Node {
+        let end = self.lines.len();
+        Node {
+            line: 0,
+            start: 0,
+            end,
+            child_col: self.min_col(0, end),
+            default_child_col: 0,
+            item: false,
+        }
+    }
+
+    fn min_col(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end).filter_map(|i| self.info[i].as_ref().map(|info| info.key_col)).min()
+    }
+
+    // 키 노드의 블록 끝 (다음 형제 또는 상위 키 직전)
+    fn key_block_end(&self, line: usize, col: usize) -> usize {
+        (line + 1..self.lines.len())
+            .find(|&i| self.info[i].as_ref().is_some_and(|info| info.key_col <= col))
+            .unwrap_or(self.lines.len())
+    }
+
+    // 시퀀스 항목의 블록 끝 (같거나 얕은 들여쓰기의 다음 줄 직전)
+    fn item_block_end(&self, line: usize, indent: usize) -> usize {
+        (line + 1..self.lines.len())
+            .find(|&i| self.info[i].as_ref().is_some_and(|info| info.indent <= indent))
+            .unwrap_or(self.lines.len())
+    }
+
+    // 범위 내 마지막 유효 줄
+    fn last_significant(&self, start: usize, end: usize) -> Option<usize> {
+        (start..end).rev().find(|&i| self.info[i].is_some())
+    }
+
+    fn key_node(&self, line: usize) -> Node {
+        let info = self.info[line].as_ref().expect("key line must be significant");
+        let end = self.key_block_end(line, info.key_col);
+        Node {
+            line,
+            start: line + 1,
+            end,
+            child_col: self.min_col(line + 1, end),
+            default_child_col: info.key_col + 2,
+            item: false,
+        }
+    }
+
+    fn item_node(&self, line: usize) -> Node {
+        let info = self.info[line].as_ref().expect("item line must be significant");
+        let end = self.item_block_end(line, info.indent);
+        Node {
+            line,
+            start: line,
+            end,
+            child_col: Some(info.key_col),
+            default_child_col: info.key_col,
+            item: true,
+        }
+    }
+
+    fn child(&self, parent: &Node, segment: &Segment) -> Option<Node> {
+        let col = parent.child_col?;
+        (parent.start..parent.end).find_map(|i| {
+            let info = self.info[i].as_ref()?;
+            if info.key_col != col {
+                return None;
+            }
+            match segment {
+                Segment::Key(key) => (info.key.as_deref() == Some(key.as_str()))
+                    .then(|| self.key_node(i)),
+                Segment::Item { key, value } => (info.dash
+                    && info.key.as_deref() == Some(key.as_str())
+                    && info.value.as_deref().map(unquote) == Some(value.as_str()))
+                    .then(|| self.item_node(i)),
+            }
+        })
+    }
+
+    fn resolve(&self, segments: &[Segment]) -> Result<Node, String> {
+        let mut node = self.root();
+        for (depth, segment) in segments.iter().enumerate() {
+            node = self.child(&node, segment).ok_or_else(|| {
+                format!("Path not found: {}", display_path(&segments[..=depth]))
+            })?;
+        }
+        Ok(node)
+    }
+
+    // 블록 안의 자식 줄이 있는지 (스칼라 여부 판단)
+    fn has_children(&self, node: &Node) -> bool {
+        (node.start..node.end).any(|i| i != node.line && self.info[i].is_some())
+    }
+
+    // 노드 블록 마지막 유효 줄 다음 위치 (새 자식 삽입 지점)
+    fn insert_position(&self, node: &Node) -> usize {
+        self.last_significant(node.line, node.end).unwrap_or(node.line) + 1
+    }
+}
+
+fn display_path(segments: &[Segment]) -> String {
+    let mut path = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Key(key) => {
+                if !path.is_empty() {
+                    path.push('.');
+                }
+                path.push_str(key);
+            }
+            Segment::Item { key, value } => path.push_str(&format!("[{}={}]", key, value)),
+        }
+    }
+    path
+}
+
+// 스칼라 값 설정 (키가 없으면 부모 블록 끝에 추가)
+pub fn set_value(text: &str, path: &str, value: &str) -> Result<String, String> {
+    let segments = parse_path(path)?;
+    let Some((Segment::Key(key), parents)) = segments.split_last() else {
+        return Err(format!("Path must end with a key: {}", path));
+    };
+
+    let mut doc = Document::parse(text);
+    let parent = doc.resolve(parents)?;
+
+    match doc.child(&parent, &Segment::Key(key.clone())) {
+        Some(node) => {
+            if doc.has_children(&node) {
+                return Err(format!("{} is not a scalar value", path));
+            }
+            let line = &doc.lines[node.line];
+            let colon = line.find(&format!("{}:", key))
+                .ok_or_else(|| format!("Failed to locate key {}", key))? + key.len() + 1;
+            let comment = comment_start(&line[colon..])
+                .map(|pos| format!(" {}", &line[colon + pos..]))
+                .unwrap_or_default();
+            doc.lines[node.line] = format!("{} {}{}", &line[..colon], value, comment);
+        }
+        None => {
+            let col = parent.child_col.unwrap_or(parent.default_child_col);
+            let position = doc.insert_position(&parent);
+            doc.lines.insert(position, format!("{}{}: {}", " ".repeat(col), key, value));
+        }
+    }
+    Ok(doc.render())
+}
+
+// 키와 그 하위 블록 제거
+pub fn remove_key(text: &str, path: &str) -> Result<String, String> {
+    let segments = parse_path(path)?;
+    if !matches!(segments.last(), Some(Segment::Key(_))) {
+        return Err(format!("Path must end with a key: {}", path));
+    }
+
+    let mut doc = Document::parse(text);
+    let node = doc.resolve(&segments)?;
+    if doc.info[node.line].as_ref().is_some_and(|info| info.dash) {
+        return Err(format!("{} is the first key of a sequence item, remove the item instead", path));
+    }
+    remove_lines(&mut doc, &node);
+    Ok(doc.render())
+}
+
+// 시퀀스 항목 제거
+pub fn remove_item(text: &str, path: &str) -> Result<String, String> {
+    let segments = parse_path(path)?;
+    if !matches!(segments.last(), Some(Segment::Item { .. })) {
+        return Err(format!("Path must end with an item selector: {}", path));
+    }
+
+    let mut doc = Document::parse(text);
+    let node = doc.resolve(&segments)?;
+    remove_lines(&mut doc, &node);
+    Ok(doc.render())
+}
+
+fn remove_lines(doc: &mut Document, node: &Node) {
+    let last = doc.last_significant(node.line, node.end).unwrap_or(node.line);
+    doc.lines.drain(node.line..=last);
+}
+
+// 시퀀스에 "- key: value" 항목 추가 (before 항목이 있으면 그 앞에)
+pub fn insert_item(
+    text: &str,
+    sequence_path: &str,
+    fields: &[(String, String)],
+    before: Option<&Segment>,
+) -> Result<String, String> {
+    if fields.is_empty() {
+        return Err("Sequence item needs at least one field".to_string());
+    }
+
+    let segments = parse_path(sequence_path)?;
+    let mut doc = Document::parse(text);
+    let sequence = doc.resolve(&segments)?;
+    if sequence.item {
+        return Err(format!("{} is not a sequence", sequence_path));
+    }
+
+    // 기존 항목과 같은 들여쓰기 사용
+    let dash_indent = (sequence.start..sequence.end)
+        .filter_map(|i| doc.info[i].as_ref())
+        .find(|info| info.dash)
+        .map(|info| info.indent)
+        .unwrap_or(sequence.default_child_col);
+
+    let position = match before.and_then(|segment| doc.child(&sequence, segment)) {
+        Some(node) => leading_comment_start(&doc, node.line),
+        None => doc.insert_position(&sequence),
+    };
+
+    let mut lines = Vec::with_capacity(fields.len());
+    for (i, (key, value)) in fields.iter().enumerate() {
+        let prefix = if i == 0 {
+            format!("{}- ", " ".repeat(dash_indent))
+        } else {
+            " ".repeat(dash_indent + 2)
+        };
+        lines.push(format!("{}{}: {}", prefix, key, value));
+    }
+    for (offset, line) in lines.into_iter().enumerate() {
+        doc.lines.insert(position + offset, line);
+    }
+    Ok(doc.render())
+}
+
+// 항목 바로 위에 붙어 있는 주석 블록의 시작 위치
+fn leading_comment_start(doc: &Document, line: usize) -> usize {
+    let mut start = line;
+    while start > 0 {
+        let previous = doc.lines[start - 1].trim_start();
+        if previous.starts_with('#') {
+            start -= 1;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const SURICATA_YAML: &str = include_str!("../../../docker/suricata/suricata.yaml");
+
+    // 앞뒤 공통 줄을 뺀 (삭제된 줄, 추가된 줄)
+    fn changed<'a>(before: &'a str, after: &'a str) -> (Vec<&'a str>, Vec<&'a str>) {
+        let before: Vec<&str> = before.split('\n').collect();
+        let after: Vec<&str> = after.split('\n').collect();
+        let prefix = before.iter().zip(&after).take_while(|(a, b)| a == b).count();
+        let suffix = before[prefix..].iter().rev()
+            .zip(after[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        (
+            before[prefix..before.len() - suffix].to_vec(),
+            after[prefix..after.len() - suffix].to_vec(),
+        )
+    }
+
+    fn parsed(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn paths_are_parsed() {
+        let key = |key: &str| Segment::Key(key.to_string());
+        let item = |key: &str, value: &str| Segment::Item { key: key.to_string(), value: value.to_string() };
+        let cases = [
+            ("stats", vec![key("stats")]),
+            ("vars.address-groups.HOME_NET", vec![key("vars"), key("address-groups"), key("HOME_NET")]),
+            ("af-packet[interface=eth0].cluster-id", vec![key("af-packet"), item("interface", "eth0"), key("cluster-id")]),
+            ("af-packet[ interface = eth0 ]", vec![key("af-packet"), item("interface", "eth0")]),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(parse_path(path).unwrap(), expected, "{}", path);
+        }
+
+        for path in ["", ".", "af-packet[interface=eth0", "af-packet[eth0]", "af-packet[=eth0]", "outputs.eve-log.types[alert=].x"] {
+            assert!(parse_path(path).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn special_scalars_are_quoted() {
+        let cases = [
+            ("eth0", "eth0"),
+            ("cluster_flow", "cluster_flow"),
+            ("/var/log/suricata/", "/var/log/suricata/"),
+            ("", "\"\""),
+            ("yes", "\"yes\""),
+            ("no", "\"no\""),
+            ("true", "\"true\""),
+            ("null", "\"null\""),
+            ("~", "\"~\""),
+            ("Yes", "\"Yes\""),
+            ("OFF", "\"OFF\""),
+            ("on", "\"on\""),
+            ("y", "\"y\""),
+            ("$HOME_NET", "\"$HOME_NET\""),
+            ("[10.0.0.0/8,!10.1.0.0/16]", "\"[10.0.0.0/8,!10.1.0.0/16]\""),
+            ("0000:3b:00.0", "\"0000:3b:00.0\""),
+            ("a # b", "\"a # b\""),
+            ("say \"hi\"", "\"say \\\"hi\\\"\""),
+        ];
+        for (value, expected) in cases {
+            let quoted = quote_scalar(value);
+            assert_eq!(quoted, expected, "{}", value);
+            // 따옴표를 붙인 값은 YAML로 다시 읽으면 원래 문자열
+            let reparsed: Value = serde_yaml::from_str(&format!("key: {}", quoted)).unwrap();
+            assert_eq!(reparsed["key"].as_str(), Some(value), "{}", value);
+        }
+        // 숫자 모양 값은 따옴표 없이 (Suricata가 숫자로 읽음)
+        assert_eq!(quote_scalar("1.5"), "1.5");
+        assert_eq!(quote_scalar("32768"), "32768");
+    }
+
+    #[test]
+    fn set_value_rewrites_only_the_target_line() {
+        let cases = [
+            ("vars.address-groups.HOME_NET", "\"[10.0.0.0/8]\"", "    HOME_NET: \"[10.0.0.0/8]\""),
+            ("vars.port-groups.HTTP_PORTS", "\"[80,8080]\"", "    HTTP_PORTS: \"[80,8080]\""),
+            ("stats.interval", "30", "  interval: 30"),
+            ("default-log-dir", "/data/log/", "default-log-dir: /data/log/"),
+            // 시퀀스 안의 매핑, 줄 끝 주석 유지
+            ("outputs.eve-log.filetype", "unix_stream", "      filetype: unix_stream #regular|syslog|unix_dgram|unix_stream|redis"),
+            ("af-packet[interface=eth0].cluster-id", "98", "    cluster-id: 98"),
+        ];
+        for (path, value, line) in cases {
+            let edited = set_value(SURICATA_YAML, path, value).unwrap();
+            let (removed, added) = changed(SURICATA_YAML, &edited);
+            assert_eq!(removed.len(), 1, "{}", path);
+            assert_eq!(added, vec![line], "{}", path);
+        }
+
+        let edited = set_value(SURICATA_YAML, "af-packet[interface=eth0].cluster-id", "98").unwrap();
+        let config = parsed(&edited);
+        assert_eq!(config["af-packet"][0]["cluster-id"], 98);
+        assert_eq!(config["af-packet"][1]["interface"], "default");
+        assert!(edited.starts_with("%YAML 1.1\n---\n"));
+    }
+
+    #[test]
+    fn set_value_adds_missing_keys_to_the_parent_block() {
+        let cases = [
+            ("stats.decoder-events", "true", "  decoder-events: true"),
+            ("af-packet[interface=eth0].threads", "4", "    threads: 4"),
+            ("outputs.eve-log.threaded", "yes", "      threaded: yes"),
+        ];
+        for (path, value, line) in cases {
+            let edited = set_value(SURICATA_YAML, path, value).unwrap();
+            let (removed, added) = changed(SURICATA_YAML, &edited);
+            assert!(removed.is_empty(), "{}", path);
+            assert_eq!(added, vec![line], "{}", path);
+        }
+
+        let config = parsed(&set_value(SURICATA_YAML, "af-packet[interface=eth0].threads", "4").unwrap());
+        assert_eq!(config["af-packet"][0]["threads"], 4);
+        assert_eq!(config["af-packet"][1].get("threads"), None);
+    }
+
+    #[test]
+    fn missing_or_invalid_paths_are_errors() {
+        let cases = [
+            ("vars.address-groups.NOPE.inner", "Path not found: vars.address-groups.NOPE"),
+            ("nope.key", "Path not found: nope"),
+            ("af-packet[interface=eth9].threads", "Path not found: af-packet[interface=eth9]"),
+            ("vars.address-groups", "vars.address-groups is not a scalar value"),
+            ("af-packet[interface=eth0]", "Path must end with a key: af-packet[interface=eth0]"),
+        ];
+        for (path, error) in cases {
+            assert_eq!(set_value(SURICATA_YAML, path, "x").unwrap_err(), error);
+        }
+
+        assert_eq!(remove_key(SURICATA_YAML, "stats.nope").unwrap_err(), "Path not found: stats.nope");
+        assert!(remove_key(SURICATA_YAML, "af-packet[interface=eth0]").is_err());
+        assert!(remove_key(SURICATA_YAML, "af-packet[interface=eth0].interface").is_err());
+        assert!(remove_item(SURICATA_YAML, "af-packet").is_err());
+        assert_eq!(remove_item(SURICATA_YAML, "af-packet[interface=eth9]").unwrap_err(), "Path not found: af-packet[interface=eth9]");
+        assert!(insert_item(SURICATA_YAML, "af-packet", &[], None).is_err());
+        assert!(insert_item(SURICATA_YAML, "af-packet[interface=eth0]", &fields(&[("interface", "eth1")]), None).is_err());
+        assert_eq!(insert_item(SURICATA_YAML, "nope", &fields(&[("interface", "eth1")]), None).unwrap_err(), "Path not found: nope");
+    }
+
+    #[test]
+    fn remove_key_drops_the_key_and_its_block() {
+        let edited = remove_key(SURICATA_YAML, "outputs.eve-log.filename").unwrap();
+        let (removed, added) = changed(SURICATA_YAML, &edited);
+        assert_eq!(removed, vec!["      filename: eve.json"]);
+        assert!(added.is_empty());
+
+        let edited = remove_key(SURICATA_YAML, "stats").unwrap();
+        let (removed, added) = changed(SURICATA_YAML, &edited);
+        assert_eq!(removed.first(), Some(&"stats:"));
+        assert_eq!(removed.last(), Some(&"  interval: 8"));
+        assert!(added.is_empty());
+        let config = parsed(&edited);
+        assert_eq!(config.get("stats"), None);
+        assert_eq!(config["default-log-dir"], "/var/log/suricata/");
+        assert_eq!(config["plugins"], Value::Null);
+    }
+
+    #[test]
+    fn sequence_items_are_removed_and_inserted() {
+        let edited = remove_item(SURICATA_YAML, "af-packet[interface=eth0]").unwrap();
+        let config = parsed(&edited);
+        assert_eq!(config["af-packet"].as_array().map(Vec::len), Some(1));
+        assert_eq!(config["af-packet"][0]["interface"], "default");
+        // 다른 시퀀스의 같은 항목은 그대로
+        assert_eq!(config["pcap"][0]["interface"], "eth0");
+
+        // before 항목 위의 주석 블록 앞에 삽입
+        let item = fields(&[("interface", "eth1"), ("cluster-id", "97")]);
+        let before = Segment::Item { key: "interface".to_string(), value: "default".to_string() };
+        let edited = insert_item(SURICATA_YAML, "af-packet", &item, Some(&before)).unwrap();
+        let (removed, added) = changed(SURICATA_YAML, &edited);
+        assert!(removed.is_empty());
+        assert_eq!(added, vec!["  - interface: eth1", "    cluster-id: 97"]);
+        assert!(edited.contains("    cluster-id: 97\n  # Put default values here."));
+        let config = parsed(&edited);
+        assert_eq!(config["af-packet"][1]["interface"], "eth1");
+        assert_eq!(config["af-packet"][1]["cluster-id"], 97);
+        assert_eq!(config["af-packet"][2]["interface"], "default");
+
+        // before가 없으면 시퀀스 끝에 추가
+        let edited = insert_item(SURICATA_YAML, "af-packet", &item, None).unwrap();
+        let config = parsed(&edited);
+        assert_eq!(config["af-packet"][2]["interface"], "eth1");
+    }
+
+    #[test]
+    fn comments_and_anchors_are_untouched() {
+        let text = "\
+# header comment
+defaults: &defaults
+  enabled: yes # keep me
+  mode: fast
+first:
+  <<: *defaults
+  # mode comment
+  mode: slow # inline
+list:
+  - &item name: a
+    value: 1
+  - name: b
+    value: 2
+";
+        let edited = set_value(text, "first.mode", "quick").unwrap();
+        let (removed, added) = changed(text, &edited);
+        assert_eq!(removed, vec!["  mode: slow # inline"]);
+        assert_eq!(added, vec!["  mode: quick # inline"]);
+
+        let edited = set_value(text, "defaults.enabled", "no").unwrap();
+        assert_eq!(changed(text, &edited).1, vec!["  enabled: no # keep me"]);
+        assert!(edited.contains("defaults: &defaults\n"));
+        assert!(edited.contains("  <<: *defaults\n"));
+
+        let edited = remove_item(text, "list[name=b]").unwrap();
+        assert_eq!(changed(text, &edited).0, vec!["  - name: b", "    value: 2"]);
+        assert!(edited.contains("  - &item name: a\n"));
+
+        // 주석 안의 키는 경로로 찾지 않음
+        let edited = set_value(SURICATA_YAML, "stats.stream-events", "true").unwrap();
+        assert_eq!(edited.matches("#stream-events: false").count(), 1);
+    }
+}
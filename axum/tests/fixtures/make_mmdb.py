@@ -0,0 +1,66 @@
+# 보강 테스트용 작은 GeoIP City / ASN 데이터베이스 생성 (MaxMind DB 형식 2.0, IPv4만)
+# 사용법: python3 make_mmdb.py (이 디렉터리에 geoip-city.mmdb, geoip-asn.mmdb 생성)
+import os, struct, ipaddress
+def enc_size(t, size):
+    if t <= 7: first = t << 5
+    else: first = 0
+    if size < 29: b = bytes([first | size]); extra=b''
+    elif size < 29+256: b = bytes([first | 29]); extra = bytes([size-29])
+    elif size < 285+65536: b = bytes([first | 30]); extra = struct.pack('>H', size-285)
+    else: b = bytes([first | 31]); extra = struct.pack('>I', size-65821)[1:]
+    if t > 7: b += bytes([t-7])
+    return b + extra
+def enc(v):
+    if isinstance(v, bool): return enc_size(14, 1 if v else 0)
+    if isinstance(v, str): d=v.encode(); return enc_size(2, len(d)) + d
+    if isinstance(v, float): return enc_size(3, 8) + struct.pack('>d', v)
+    if isinstance(v, int):
+        d = v.to_bytes((v.bit_length()+7)//8, 'big') if v else b''
+        t = 6 if v < 2**32 else 9
+        return enc_size(t, len(d)) + d
+    if isinstance(v, dict):
+        out = enc_size(7, len(v))
+        for k, x in v.items(): out += enc(k) + enc(x)
+        return out
+    if isinstance(v, list):
+        out = enc_size(11, len(v))
+        for x in v: out += enc(x)
+        return out
+def build(entries, dbtype, path):
+    nodes = [[None, None]]
+    data = b''
+    for net, rec in entries:
+        net = ipaddress.ip_network(net)
+        off = len(data); data += enc(rec)
+        bits = format(int(net.network_address), '032b')[:net.prefixlen]
+        n = 0
+        for i, bit in enumerate(bits):
+            b = int(bit)
+            if i == len(bits)-1:
+                nodes[n][b] = ('data', off)
+            else:
+                if not (nodes[n][b] and nodes[n][b][0] == 'node'):
+                    nodes.append([None, None]); nodes[n][b] = ('node', len(nodes)-1)
+                n = nodes[n][b][1]
+    nc = len(nodes)
+    def val(r):
+        if r is None: return nc
+        if r[0] == 'node': return r[1]
+        return nc + 16 + r[1]
+    tree = b''.join(struct.pack('>I', val(l))[1:] + struct.pack('>I', val(r))[1:] for l, r in nodes)
+    meta = {"node_count": nc, "record_size": 24, "ip_version": 4, "database_type": dbtype,
+            "languages": ["en"], "binary_format_major_version": 2, "binary_format_minor_version": 0,
+            "build_epoch": 1700000000, "description": {"en": "fixture"}}
+    menc = enc_size(7, len(meta))
+    for k, v in meta.items():
+        if k in ("record_size", "ip_version", "binary_format_major_version", "binary_format_minor_version"):
+            d = v.to_bytes((v.bit_length()+7)//8, 'big'); menc += enc(k) + enc_size(5, len(d)) + d
+        elif k == "build_epoch":
+            d = v.to_bytes(8, 'big'); menc += enc(k) + enc_size(9, 8) + d
+        else: menc += enc(k) + enc(v)
+    open(path, 'wb').write(tree + b'\x00'*16 + data + b'\xab\xcd\xefMaxMind.com' + menc)
+HERE = os.path.dirname(os.path.abspath(__file__))
+build([("93.184.216.0/24", {"country": {"iso_code": "US", "names": {"en": "United States"}}, "city": {"names": {"en": "Norwell"}}, "location": {"latitude": 42.1508, "longitude": -70.8228}}),
+       ("1.1.1.0/24", {"country": {"iso_code": "AU", "names": {"en": "Australia"}}})], "GeoIP2-City", os.path.join(HERE, "geoip-city.mmdb"))
+build([("93.184.216.0/24", {"autonomous_system_number": 15133, "autonomous_system_organization": "EDGECAST"}),
+       ("1.1.1.0/24", {"autonomous_system_number": 13335, "autonomous_system_organization": "CLOUDFLARENET"})], "GeoLite2-ASN", os.path.join(HERE, "geoip-asn.mmdb"))